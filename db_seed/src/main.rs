@@ -62,7 +62,6 @@ fn insert(admin_username: Username, admin_email: Email, new_pass: Password) -> R
             first_name.eq("Admin"),
             last_name.eq("Admin"),
             date_joined.eq(now),
-            tags.eq(Vec::<String>::new()),
         ))
         .execute(&conn)
         .map_err(|e| format!("Failed to insert user: {:?}", e))?;
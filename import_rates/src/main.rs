@@ -0,0 +1,61 @@
+//! Imports exchange rates into the `exchange_rates` table.
+//!
+//! Usage: `import_rates <file>`, where the file is either the ECB reference
+//! rates XML (`*.xml`) or a CSV with `date,base,quote,rate` columns.
+//! Rates that are already known for the same date are overwritten, as are
+//! rates repeated in the file.
+
+mod parse;
+
+use models::NewExchangeRate;
+
+fn read_rates(path: &str) -> Result<Vec<NewExchangeRate>, String> {
+    let data = std::fs::read(path).map_err(|e| format!("Cannot read {}: {}", path, e))?;
+
+    if path.to_lowercase().ends_with(".xml") {
+        parse::ecb_xml(&data)
+    } else {
+        parse::csv(&data)
+    }
+}
+
+fn insert(rates: &[NewExchangeRate]) -> Result<usize, String> {
+    use diesel::pg::upsert::excluded;
+    use diesel::*;
+    use models::schema::exchange_rates::dsl::*;
+
+    let database_url =
+        std::env::var("DATABASE_URL").map_err(|_| "DATABASE_URL must be set".to_string())?;
+    let conn = pg::PgConnection::establish(&database_url)
+        .map_err(|e| format!("Error connecting to {}: {:?}", database_url, e))?;
+
+    conn.transaction(|| {
+        let mut n = 0;
+
+        // keep the number of bind parameters of a single statement within limits
+        for chunk in rates.chunks(1000) {
+            n += insert_into(exchange_rates)
+                .values(chunk)
+                .on_conflict((date, base, quote))
+                .do_update()
+                .set(rate.eq(excluded(rate)))
+                .execute(&conn)?;
+        }
+
+        Ok(n)
+    })
+    .map_err(|e: result::Error| format!("Failed to insert rates: {:?}", e))
+}
+
+fn main() -> Result<(), String> {
+    let path = std::env::args()
+        .nth(1)
+        .ok_or_else(|| "Usage: import_rates <file.csv|file.xml>".to_string())?;
+
+    let rates = parse::dedupe(read_rates(&path)?);
+    let n = insert(&rates)?;
+
+    println!("Imported {} exchange rates from {}", n, path);
+
+    Ok(())
+}
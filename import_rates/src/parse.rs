@@ -0,0 +1,166 @@
+use std::collections::{BTreeSet, HashMap};
+use std::str::FromStr;
+
+use bigdecimal::{BigDecimal, Zero};
+use chrono::NaiveDate;
+use models::{currency, NewExchangeRate};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// ECB publishes all rates against the euro.
+const ECB_BASE: &str = "EUR";
+
+/// Rates from a CSV file with a `date,base,quote,rate` header.
+pub fn csv(data: &[u8]) -> Result<Vec<NewExchangeRate>, String> {
+    let mut reader = csv::Reader::from_reader(data);
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Cannot read CSV header: {}", e))?
+        .clone();
+
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.trim() == name)
+            .ok_or_else(|| format!("Column \"{}\" is missing", name))
+    };
+    let (date, base, quote, rate) = (
+        column("date")?,
+        column("base")?,
+        column("quote")?,
+        column("rate")?,
+    );
+
+    let mut rates = vec![];
+    for (idx, record) in reader.records().enumerate() {
+        // the header is the first line
+        let line = idx + 2;
+        let record = record.map_err(|e| format!("Line {}: {}", line, e))?;
+        let field = |i: usize| record.get(i).unwrap_or_default().trim();
+
+        let rate = new_rate(field(date), field(base), field(quote), field(rate))
+            .map_err(|e| format!("Line {}: {}", line, e))?;
+        rates.push(rate);
+    }
+
+    Ok(rates)
+}
+
+/// Rates from the ECB reference rates XML, both the daily and the historical one:
+///
+/// ```xml
+/// <Cube time="2020-03-06"><Cube currency="USD" rate="1.1336"/></Cube>
+/// ```
+///
+/// The historical file has rates of currencies that were replaced by the euro
+/// or redenominated (CYP, ROL, TRL, ...), they are skipped with a warning.
+pub fn ecb_xml(data: &[u8]) -> Result<Vec<NewExchangeRate>, String> {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    let mut reader = Reader::from_reader(data);
+    let mut buf = vec![];
+    let mut time: Option<String> = None;
+    let mut rates = vec![];
+    let mut unknown = BTreeSet::new();
+
+    loop {
+        match reader.read_event(&mut buf) {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) if e.local_name() == b"Cube" => {
+                let mut currency = None;
+                let mut rate = None;
+
+                for attr in e.attributes() {
+                    let attr = attr.map_err(|e| format!("Invalid XML: {}", e))?;
+                    let value = attr
+                        .unescape_and_decode_value(&reader)
+                        .map_err(|e| format!("Invalid XML: {}", e))?;
+
+                    match attr.key {
+                        b"time" => time = Some(value),
+                        b"currency" => currency = Some(value),
+                        b"rate" => rate = Some(value),
+                        _ => {}
+                    }
+                }
+
+                match (currency, rate) {
+                    (Some(currency), Some(_)) if currency_code(&currency).is_err() => {
+                        unknown.insert(currency);
+                    }
+                    (Some(currency), Some(rate)) => {
+                        let date = time
+                            .as_deref()
+                            .ok_or_else(|| format!("Rate for {} has no date", currency))?;
+                        rates.push(new_rate(date, ECB_BASE, &currency, &rate)?);
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(format!(
+                    "Invalid XML at position {}: {}",
+                    reader.buffer_position(),
+                    e
+                ))
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    for currency in unknown {
+        eprintln!("Skipping rates of unknown currency \"{}\"", currency);
+    }
+
+    Ok(rates)
+}
+
+/// Rates with one rate per date, base and quote, the last one of `rates`
+/// wins. A single insert cannot update the same row twice.
+pub fn dedupe(rates: Vec<NewExchangeRate>) -> Vec<NewExchangeRate> {
+    let mut positions = HashMap::new();
+    let mut unique: Vec<NewExchangeRate> = vec![];
+
+    for rate in rates {
+        let key = (rate.date, rate.base.clone(), rate.quote.clone());
+
+        match positions.get(&key) {
+            Some(&idx) => unique[idx] = rate,
+            None => {
+                positions.insert(key, unique.len());
+                unique.push(rate);
+            }
+        }
+    }
+
+    unique
+}
+
+fn new_rate(date: &str, base: &str, quote: &str, rate: &str) -> Result<NewExchangeRate, String> {
+    let date = NaiveDate::parse_from_str(date, DATE_FORMAT)
+        .map_err(|_| format!("\"{}\" is not a valid date, use YYYY-MM-DD", date))?;
+    let base = currency_code(base)?;
+    let quote = currency_code(quote)?;
+    let rate = BigDecimal::from_str(rate)
+        .ok()
+        .filter(|rate| *rate > BigDecimal::zero())
+        .ok_or_else(|| format!("\"{}\" is not a valid rate", rate))?;
+
+    Ok(NewExchangeRate {
+        date,
+        base,
+        quote,
+        rate,
+    })
+}
+
+fn currency_code(code: &str) -> Result<String, String> {
+    currency::find(&code.to_uppercase())
+        .map(|currency| currency.code.to_string())
+        .ok_or_else(|| format!("\"{}\" is not a valid currency", code))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,117 @@
+use super::*;
+
+fn rate(date: (i32, u32, u32), base: &str, quote: &str, rate: &str) -> NewExchangeRate {
+    NewExchangeRate {
+        date: NaiveDate::from_ymd(date.0, date.1, date.2),
+        base: base.to_string(),
+        quote: quote.to_string(),
+        rate: BigDecimal::from_str(rate).unwrap(),
+    }
+}
+
+#[test]
+fn parses_csv() {
+    let data = b"date,base,quote,rate\n2020-03-06,EUR,USD,1.1336\n2020-03-06, usd , CAD ,1.34\n";
+
+    assert_eq!(
+        Ok(vec![
+            rate((2020, 3, 6), "EUR", "USD", "1.1336"),
+            rate((2020, 3, 6), "USD", "CAD", "1.34"),
+        ]),
+        csv(data)
+    );
+}
+
+#[test]
+fn csv_errors_point_to_the_line() {
+    assert_eq!(
+        Err("Column \"rate\" is missing".to_string()),
+        csv(b"date,base,quote\n2020-03-06,EUR,USD\n")
+    );
+    assert_eq!(
+        Err("Line 3: \"XYZ\" is not a valid currency".to_string()),
+        csv(b"date,base,quote,rate\n2020-03-06,EUR,USD,1.1\n2020-03-06,EUR,XYZ,1.1\n")
+    );
+    assert_eq!(
+        Err("Line 2: \"-1\" is not a valid rate".to_string()),
+        csv(b"date,base,quote,rate\n2020-03-06,EUR,USD,-1\n")
+    );
+    assert_eq!(
+        Err("Line 2: \"06.03.2020\" is not a valid date, use YYYY-MM-DD".to_string()),
+        csv(b"date,base,quote,rate\n06.03.2020,EUR,USD,1.1\n")
+    );
+}
+
+#[test]
+fn parses_ecb_xml() {
+    let data = br#"<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01" xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+    <gesmes:subject>Reference rates</gesmes:subject>
+    <gesmes:Sender>
+        <gesmes:name>European Central Bank</gesmes:name>
+    </gesmes:Sender>
+    <Cube>
+        <Cube time="2020-03-06">
+            <Cube currency="USD" rate="1.1336"/>
+            <Cube currency="CAD" rate="1.5225"/>
+        </Cube>
+        <Cube time="2020-03-05">
+            <Cube currency="USD" rate="1.1187"/>
+        </Cube>
+    </Cube>
+</gesmes:Envelope>"#;
+
+    assert_eq!(
+        Ok(vec![
+            rate((2020, 3, 6), "EUR", "USD", "1.1336"),
+            rate((2020, 3, 6), "EUR", "CAD", "1.5225"),
+            rate((2020, 3, 5), "EUR", "USD", "1.1187"),
+        ]),
+        ecb_xml(data)
+    );
+}
+
+#[test]
+fn ecb_xml_with_invalid_rate() {
+    let data = br#"<Cube><Cube time="2020-03-06"><Cube currency="USD" rate="n/a"/></Cube></Cube>"#;
+
+    assert_eq!(
+        Err("\"n/a\" is not a valid rate".to_string()),
+        ecb_xml(data)
+    );
+}
+
+#[test]
+fn ecb_xml_skips_retired_currencies() {
+    let data = br#"<Cube>
+        <Cube time="2007-12-31">
+            <Cube currency="USD" rate="1.4721"/>
+            <Cube currency="CYP" rate="0.585274"/>
+            <Cube currency="SIT" rate="239.64"/>
+        </Cube>
+    </Cube>"#;
+
+    assert_eq!(
+        Ok(vec![rate((2007, 12, 31), "EUR", "USD", "1.4721")]),
+        ecb_xml(data)
+    );
+}
+
+#[test]
+fn dedupe_keeps_the_last_rate() {
+    let rates = vec![
+        rate((2020, 3, 6), "EUR", "USD", "1.1"),
+        rate((2020, 3, 6), "EUR", "CAD", "1.5"),
+        rate((2020, 3, 6), "EUR", "USD", "1.2"),
+        rate((2020, 3, 5), "EUR", "USD", "1.3"),
+    ];
+
+    assert_eq!(
+        vec![
+            rate((2020, 3, 6), "EUR", "USD", "1.2"),
+            rate((2020, 3, 6), "EUR", "CAD", "1.5"),
+            rate((2020, 3, 5), "EUR", "USD", "1.3"),
+        ],
+        dedupe(rates)
+    );
+}
@@ -0,0 +1,9 @@
+CREATE TABLE "exchange_rates" (
+    "id" serial NOT NULL PRIMARY KEY,
+    "date" date NOT NULL,
+    "base" varchar(3) NOT NULL,
+    "quote" varchar(3) NOT NULL,
+    "rate" numeric(20, 10) NOT NULL CHECK ("rate" > 0)
+);
+CREATE UNIQUE INDEX "exchange_rates_date_base_quote_uniq" ON "exchange_rates" ("date", "base", "quote");
+CREATE INDEX "exchange_rates_base_quote_date" ON "exchange_rates" ("base", "quote", "date" DESC);
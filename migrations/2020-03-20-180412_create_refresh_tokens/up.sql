@@ -0,0 +1,12 @@
+CREATE TABLE "auth_refresh_tokens" (
+    "id" serial NOT NULL PRIMARY KEY,
+    "user_id" integer NOT NULL REFERENCES "auth_user" ("id") ON DELETE CASCADE,
+    "family" varchar(64) NOT NULL,
+    "token_hash" varchar(64) NOT NULL UNIQUE,
+    "created_at" timestamp with time zone NOT NULL,
+    "expires_at" timestamp with time zone NOT NULL,
+    "used_at" timestamp with time zone NULL,
+    "revoked_at" timestamp with time zone NULL
+);
+CREATE INDEX "auth_refresh_tokens_family" ON "auth_refresh_tokens" ("family");
+CREATE INDEX "auth_refresh_tokens_user_id" ON "auth_refresh_tokens" ("user_id");
@@ -0,0 +1,7 @@
+CREATE TABLE "user_settings" (
+    "user_id" integer NOT NULL PRIMARY KEY REFERENCES "auth_user" ("id") ON DELETE CASCADE,
+    "timezone" varchar(64) NOT NULL DEFAULT 'UTC',
+    "default_currency" varchar(3) NOT NULL DEFAULT 'CAD',
+    "week_start" smallint NOT NULL DEFAULT 1 CHECK ("week_start" BETWEEN 1 AND 7),
+    "month_start_day" smallint NOT NULL DEFAULT 1 CHECK ("month_start_day" BETWEEN 1 AND 28)
+);
@@ -0,0 +1,10 @@
+CREATE TABLE "auth_personal_tokens" (
+    "id" serial NOT NULL PRIMARY KEY,
+    "user_id" integer NOT NULL REFERENCES "auth_user" ("id") ON DELETE CASCADE,
+    "name" varchar(100) NOT NULL,
+    "token_hash" varchar(64) NOT NULL UNIQUE,
+    "scope" varchar(16) NOT NULL,
+    "created_at" timestamp with time zone NOT NULL,
+    "last_used_at" timestamp with time zone NULL
+);
+CREATE INDEX "auth_personal_tokens_user_id" ON "auth_personal_tokens" ("user_id");
@@ -0,0 +1,6 @@
+ALTER TABLE "auth_user" ADD COLUMN "tags" text[] NOT NULL DEFAULT '{}';
+UPDATE "auth_user" SET "tags" = ARRAY(
+    SELECT "name" FROM "tags" WHERE "tags"."user_id" = "auth_user"."id" ORDER BY "position"
+);
+ALTER TABLE "auth_user" ALTER COLUMN "tags" DROP DEFAULT;
+DROP TABLE "tags";
@@ -0,0 +1,31 @@
+CREATE TABLE "tags" (
+    "id" serial NOT NULL PRIMARY KEY,
+    "user_id" integer NOT NULL REFERENCES "auth_user" ("id") ON DELETE CASCADE,
+    "parent_id" integer NULL REFERENCES "tags" ("id") ON DELETE CASCADE,
+    "name" text NOT NULL,
+    "position" integer NOT NULL,
+    UNIQUE ("user_id", "name")
+);
+CREATE INDEX "tags_parent_id" ON "tags" ("parent_id");
+
+-- every tag comes with all its ancestors, "food/groceries" needs "food"
+INSERT INTO "tags" ("user_id", "name", "position")
+SELECT "user_id", "name", row_number() OVER (PARTITION BY "user_id" ORDER BY MIN(ARRAY["position", "depth"])) - 1
+FROM (
+    SELECT "auth_user"."id" AS "user_id",
+        array_to_string((string_to_array("tag", '/'))[1:"depth"], '/') AS "name",
+        "position",
+        "depth"
+    FROM "auth_user",
+        unnest("auth_user"."tags") WITH ORDINALITY AS "user_tags"("tag", "position"),
+        generate_series(1, array_length(string_to_array("tag", '/'), 1)) AS "depth"
+) AS "paths"
+GROUP BY "user_id", "name";
+
+UPDATE "tags" SET "parent_id" = "parents"."id"
+FROM "tags" AS "parents"
+WHERE "parents"."user_id" = "tags"."user_id"
+  AND "tags"."name" LIKE '%/%'
+  AND "parents"."name" = regexp_replace("tags"."name", '/[^/]*$', '');
+
+ALTER TABLE "auth_user" DROP COLUMN "tags";
@@ -0,0 +1,214 @@
+//! ISO 4217 currencies that can be used for records and budgets.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Currency {
+    pub code: &'static str,
+    pub name: &'static str,
+    /// number of digits after the decimal separator
+    #[serde(skip)]
+    pub minor_units: u32,
+}
+
+const fn currency(code: &'static str, name: &'static str, minor_units: u32) -> Currency {
+    Currency {
+        code,
+        name,
+        minor_units,
+    }
+}
+
+/// Active ISO 4217 currencies, sorted by code.
+pub const CURRENCIES: &[Currency] = &[
+    currency("AED", "UAE Dirham", 2),
+    currency("AFN", "Afghani", 2),
+    currency("ALL", "Lek", 2),
+    currency("AMD", "Armenian Dram", 2),
+    currency("ANG", "Netherlands Antillean Guilder", 2),
+    currency("AOA", "Kwanza", 2),
+    currency("ARS", "Argentine Peso", 2),
+    currency("AUD", "Australian Dollar", 2),
+    currency("AWG", "Aruban Florin", 2),
+    currency("AZN", "Azerbaijan Manat", 2),
+    currency("BAM", "Convertible Mark", 2),
+    currency("BBD", "Barbados Dollar", 2),
+    currency("BDT", "Taka", 2),
+    currency("BGN", "Bulgarian Lev", 2),
+    currency("BHD", "Bahraini Dinar", 3),
+    currency("BIF", "Burundi Franc", 0),
+    currency("BMD", "Bermudian Dollar", 2),
+    currency("BND", "Brunei Dollar", 2),
+    currency("BOB", "Boliviano", 2),
+    currency("BRL", "Brazilian Real", 2),
+    currency("BSD", "Bahamian Dollar", 2),
+    currency("BTN", "Ngultrum", 2),
+    currency("BWP", "Pula", 2),
+    currency("BYN", "Belarusian Ruble", 2),
+    currency("BZD", "Belize Dollar", 2),
+    currency("CAD", "Canadian Dollar", 2),
+    currency("CDF", "Congolese Franc", 2),
+    currency("CHF", "Swiss Franc", 2),
+    currency("CLP", "Chilean Peso", 0),
+    currency("CNY", "Yuan Renminbi", 2),
+    currency("COP", "Colombian Peso", 2),
+    currency("CRC", "Costa Rican Colon", 2),
+    currency("CUC", "Peso Convertible", 2),
+    currency("CUP", "Cuban Peso", 2),
+    currency("CVE", "Cabo Verde Escudo", 2),
+    currency("CZK", "Czech Koruna", 2),
+    currency("DJF", "Djibouti Franc", 0),
+    currency("DKK", "Danish Krone", 2),
+    currency("DOP", "Dominican Peso", 2),
+    currency("DZD", "Algerian Dinar", 2),
+    currency("EGP", "Egyptian Pound", 2),
+    currency("ERN", "Nakfa", 2),
+    currency("ETB", "Ethiopian Birr", 2),
+    currency("EUR", "Euro", 2),
+    currency("FJD", "Fiji Dollar", 2),
+    currency("FKP", "Falkland Islands Pound", 2),
+    currency("GBP", "Pound Sterling", 2),
+    currency("GEL", "Lari", 2),
+    currency("GHS", "Ghana Cedi", 2),
+    currency("GIP", "Gibraltar Pound", 2),
+    currency("GMD", "Dalasi", 2),
+    currency("GNF", "Guinean Franc", 0),
+    currency("GTQ", "Quetzal", 2),
+    currency("GYD", "Guyana Dollar", 2),
+    currency("HKD", "Hong Kong Dollar", 2),
+    currency("HNL", "Lempira", 2),
+    currency("HRK", "Kuna", 2),
+    currency("HTG", "Gourde", 2),
+    currency("HUF", "Forint", 2),
+    currency("IDR", "Rupiah", 2),
+    currency("ILS", "New Israeli Sheqel", 2),
+    currency("INR", "Indian Rupee", 2),
+    currency("IQD", "Iraqi Dinar", 3),
+    currency("IRR", "Iranian Rial", 2),
+    currency("ISK", "Iceland Krona", 0),
+    currency("JMD", "Jamaican Dollar", 2),
+    currency("JOD", "Jordanian Dinar", 3),
+    currency("JPY", "Yen", 0),
+    currency("KES", "Kenyan Shilling", 2),
+    currency("KGS", "Som", 2),
+    currency("KHR", "Riel", 2),
+    currency("KMF", "Comorian Franc", 0),
+    currency("KPW", "North Korean Won", 2),
+    currency("KRW", "Won", 0),
+    currency("KWD", "Kuwaiti Dinar", 3),
+    currency("KYD", "Cayman Islands Dollar", 2),
+    currency("KZT", "Tenge", 2),
+    currency("LAK", "Lao Kip", 2),
+    currency("LBP", "Lebanese Pound", 2),
+    currency("LKR", "Sri Lanka Rupee", 2),
+    currency("LRD", "Liberian Dollar", 2),
+    currency("LSL", "Loti", 2),
+    currency("LYD", "Libyan Dinar", 3),
+    currency("MAD", "Moroccan Dirham", 2),
+    currency("MDL", "Moldovan Leu", 2),
+    currency("MGA", "Malagasy Ariary", 2),
+    currency("MKD", "Denar", 2),
+    currency("MMK", "Kyat", 2),
+    currency("MNT", "Tugrik", 2),
+    currency("MOP", "Pataca", 2),
+    currency("MRU", "Ouguiya", 2),
+    currency("MUR", "Mauritius Rupee", 2),
+    currency("MVR", "Rufiyaa", 2),
+    currency("MWK", "Malawi Kwacha", 2),
+    currency("MXN", "Mexican Peso", 2),
+    currency("MYR", "Malaysian Ringgit", 2),
+    currency("MZN", "Mozambique Metical", 2),
+    currency("NAD", "Namibia Dollar", 2),
+    currency("NGN", "Naira", 2),
+    currency("NIO", "Cordoba Oro", 2),
+    currency("NOK", "Norwegian Krone", 2),
+    currency("NPR", "Nepalese Rupee", 2),
+    currency("NZD", "New Zealand Dollar", 2),
+    currency("OMR", "Rial Omani", 3),
+    currency("PAB", "Balboa", 2),
+    currency("PEN", "Sol", 2),
+    currency("PGK", "Kina", 2),
+    currency("PHP", "Philippine Peso", 2),
+    currency("PKR", "Pakistan Rupee", 2),
+    currency("PLN", "Zloty", 2),
+    currency("PYG", "Guarani", 0),
+    currency("QAR", "Qatari Rial", 2),
+    currency("RON", "Romanian Leu", 2),
+    currency("RSD", "Serbian Dinar", 2),
+    currency("RUB", "Russian Ruble", 2),
+    currency("RWF", "Rwanda Franc", 0),
+    currency("SAR", "Saudi Riyal", 2),
+    currency("SBD", "Solomon Islands Dollar", 2),
+    currency("SCR", "Seychelles Rupee", 2),
+    currency("SDG", "Sudanese Pound", 2),
+    currency("SEK", "Swedish Krona", 2),
+    currency("SGD", "Singapore Dollar", 2),
+    currency("SHP", "Saint Helena Pound", 2),
+    currency("SLL", "Leone", 2),
+    currency("SOS", "Somali Shilling", 2),
+    currency("SRD", "Surinam Dollar", 2),
+    currency("SSP", "South Sudanese Pound", 2),
+    currency("STN", "Dobra", 2),
+    currency("SVC", "El Salvador Colon", 2),
+    currency("SYP", "Syrian Pound", 2),
+    currency("SZL", "Lilangeni", 2),
+    currency("THB", "Baht", 2),
+    currency("TJS", "Somoni", 2),
+    currency("TMT", "Turkmenistan New Manat", 2),
+    currency("TND", "Tunisian Dinar", 3),
+    currency("TOP", "Pa'anga", 2),
+    currency("TRY", "Turkish Lira", 2),
+    currency("TTD", "Trinidad and Tobago Dollar", 2),
+    currency("TWD", "New Taiwan Dollar", 2),
+    currency("TZS", "Tanzanian Shilling", 2),
+    currency("UAH", "Hryvnia", 2),
+    currency("UGX", "Uganda Shilling", 0),
+    currency("USD", "US Dollar", 2),
+    currency("UYU", "Peso Uruguayo", 2),
+    currency("UZS", "Uzbekistan Sum", 2),
+    currency("VES", "Bolivar Soberano", 2),
+    currency("VND", "Dong", 0),
+    currency("VUV", "Vatu", 0),
+    currency("WST", "Tala", 2),
+    currency("XAF", "CFA Franc BEAC", 0),
+    currency("XCD", "East Caribbean Dollar", 2),
+    currency("XOF", "CFA Franc BCEAO", 0),
+    currency("XPF", "CFP Franc", 0),
+    currency("YER", "Yemeni Rial", 2),
+    currency("ZAR", "Rand", 2),
+    currency("ZMW", "Zambian Kwacha", 2),
+    currency("ZWL", "Zimbabwe Dollar", 2),
+];
+
+pub fn find(code: &str) -> Option<&'static Currency> {
+    CURRENCIES
+        .binary_search_by(|currency| currency.code.cmp(code))
+        .ok()
+        .map(|idx| &CURRENCIES[idx])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn currencies_are_sorted() {
+        assert!(CURRENCIES
+            .windows(2)
+            .all(|pair| pair[0].code < pair[1].code));
+    }
+
+    #[test]
+    fn find_known_currencies() {
+        assert_eq!(Some("Canadian Dollar"), find("CAD").map(|c| c.name));
+        assert_eq!(Some(2), find("EUR").map(|c| c.minor_units));
+        assert_eq!(Some(0), find("JPY").map(|c| c.minor_units));
+        assert_eq!(Some(3), find("KWD").map(|c| c.minor_units));
+    }
+
+    #[test]
+    fn find_unknown_currency() {
+        assert_eq!(None, find("XYZ"));
+        assert_eq!(None, find("cad"));
+    }
+}
@@ -6,8 +6,14 @@ use chrono::{NaiveDate, NaiveDateTime};
 use serde::ser::{SerializeStruct, Serializer};
 use serde::Serialize;
 
+pub mod currency;
+pub mod money;
 pub mod schema;
-use schema::{auth_user, budgets_budget, records_record};
+pub use money::{Legacy, LegacySerialize, Money};
+use schema::{
+    auth_personal_tokens, auth_refresh_tokens, auth_user, budgets_budget, budgets_yearbudget,
+    exchange_rates, records_record, user_settings,
+};
 
 #[derive(Queryable, Serialize, Debug, Clone, PartialEq, Insertable)]
 #[table_name = "auth_user"]
@@ -19,9 +25,9 @@ pub struct AuthUser {
     pub is_active: bool,
     pub is_staff: bool,
     pub is_superuser: bool,
+    pub last_login: Option<NaiveDateTime>,
     pub last_name: String,
     pub password: String,
-    pub tags: Vec<String>,
     pub username: String,
 }
 
@@ -49,40 +55,271 @@ pub struct Budget {
     pub tags: Vec<String>,
     pub tags_type: String,
     pub user_id: i32,
+    pub comment: Option<String>,
+}
+
+#[derive(Queryable, Debug, Clone, PartialEq, Insertable)]
+#[table_name = "budgets_yearbudget"]
+pub struct YearBudget {
+    pub amount: BigDecimal,
+    pub amount_currency: String,
+    pub comment: Option<String>,
+    pub id: i32,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub tags_type: String,
+    pub user_id: i32,
+    pub year: i32,
+}
+
+#[derive(Queryable, Debug, Clone, PartialEq)]
+pub struct ExchangeRate {
+    pub id: i32,
+    pub date: NaiveDate,
+    pub base: String,
+    pub quote: String,
+    pub rate: BigDecimal,
+}
+
+#[derive(Insertable, Debug, Clone, PartialEq)]
+#[table_name = "exchange_rates"]
+pub struct NewExchangeRate {
+    pub date: NaiveDate,
+    pub base: String,
+    pub quote: String,
+    pub rate: BigDecimal,
+}
+
+#[derive(Queryable, Debug, Clone, PartialEq)]
+pub struct RefreshToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub family: String,
+    pub token_hash: String,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Debug, Clone, PartialEq)]
+#[table_name = "auth_refresh_tokens"]
+pub struct NewRefreshToken {
+    pub user_id: i32,
+    pub family: String,
+    pub token_hash: String,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+}
+
+/// Long-lived token for scripts, only the hash of the token is stored.
+#[derive(Queryable, Serialize, Debug, Clone, PartialEq)]
+pub struct PersonalToken {
+    pub id: i32,
+    #[serde(skip)]
+    pub user_id: i32,
+    pub name: String,
+    #[serde(skip)]
+    pub token_hash: String,
+    /// "read" or "read_write"
+    pub scope: String,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Debug, Clone, PartialEq)]
+#[table_name = "auth_personal_tokens"]
+pub struct NewPersonalToken {
+    pub user_id: i32,
+    pub name: String,
+    pub token_hash: String,
+    pub scope: String,
+    pub created_at: NaiveDateTime,
+}
+
+/// Per-user preferences. Users without a row get the defaults of the table.
+#[derive(Queryable, Insertable, AsChangeset, Serialize, Debug, Clone, PartialEq)]
+#[table_name = "user_settings"]
+pub struct UserSettings {
+    #[serde(skip)]
+    pub user_id: i32,
+    /// IANA timezone name, e.g. "America/Vancouver"
+    pub timezone: String,
+    pub default_currency: String,
+    /// ISO weekday number, Monday is 1
+    pub week_start: i16,
+    /// day of month when a budget period starts, e.g. a payday
+    pub month_start_day: i16,
+}
+
+impl UserSettings {
+    pub fn defaults(user_id: i32) -> Self {
+        Self {
+            user_id,
+            timezone: "UTC".to_string(),
+            default_currency: "CAD".to_string(),
+            week_start: 1,
+            month_start_day: 1,
+        }
+    }
+}
+
+/// Records in `currency` made on `date` that were left out of a sum because
+/// there is no exchange rate to convert them.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MissingRate {
+    pub currency: String,
+    pub date: NaiveDate,
 }
 
-#[derive(Serialize, Default)]
 pub struct SerializedBudget {
+    pub id: i32,
     pub name: String,
     pub amount: BigDecimal,
-    pub spent: f64,
-    pub left: f64,
-    pub average_per_day: f64,
-    pub left_average_per_day: f64,
+    pub amount_currency: String,
+    pub start_date: NaiveDate,
+    pub tags: Vec<String>,
+    pub tags_type: String,
+    pub comment: Option<String>,
+    pub spent: BigDecimal,
+    pub left: BigDecimal,
+    pub average_per_day: BigDecimal,
+    pub left_average_per_day: BigDecimal,
+    pub missing_rates: Vec<MissingRate>,
 }
 
-#[derive(Debug, Serialize)]
-enum CurrencyCode {
-    #[serde(rename = "CAD")]
-    Cad,
+pub struct SerializedYearBudget {
+    pub id: i32,
+    pub name: String,
+    pub amount: BigDecimal,
+    pub amount_currency: String,
+    pub year: i32,
+    pub tags: Vec<String>,
+    pub tags_type: String,
+    pub comment: Option<String>,
+    pub spent: BigDecimal,
+    pub left: BigDecimal,
+    pub average_per_month: BigDecimal,
+    pub left_average_per_month: BigDecimal,
+    pub missing_rates: Vec<MissingRate>,
 }
 
-#[derive(Debug, Serialize)]
-enum CurrencyName {
-    #[serde(rename = "Canadian Dollar")]
-    Cad,
+fn to_f64(amount: &BigDecimal) -> f64 {
+    amount.to_f64().unwrap_or(0.0)
+}
+
+impl Serialize for SerializedBudget {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let money = |amount: &BigDecimal| Money::new(amount.clone(), &self.amount_currency);
+        let mut state = serializer.serialize_struct("SerializedBudget", 12)?;
+
+        state.serialize_field("id", &self.id)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("amount", &money(&self.amount))?;
+        state.serialize_field("start_date", &self.start_date)?;
+        state.serialize_field("tags", &self.tags)?;
+        state.serialize_field("tags_type", &self.tags_type)?;
+        state.serialize_field("comment", &self.comment)?;
+        state.serialize_field("spent", &money(&self.spent))?;
+        state.serialize_field("left", &money(&self.left))?;
+        state.serialize_field("average_per_day", &money(&self.average_per_day))?;
+        state.serialize_field("left_average_per_day", &money(&self.left_average_per_day))?;
+        state.serialize_field("missing_rates", &self.missing_rates)?;
+        state.end()
+    }
+}
+
+impl LegacySerialize for SerializedBudget {
+    fn serialize_legacy<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("SerializedBudget", 13)?;
+
+        state.serialize_field("id", &self.id)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("amount", &self.amount)?;
+        state.serialize_field("amount_currency", &self.amount_currency)?;
+        state.serialize_field("start_date", &self.start_date)?;
+        state.serialize_field("tags", &self.tags)?;
+        state.serialize_field("tags_type", &self.tags_type)?;
+        state.serialize_field("comment", &self.comment)?;
+        state.serialize_field("spent", &to_f64(&self.spent))?;
+        state.serialize_field("left", &to_f64(&self.left))?;
+        state.serialize_field("average_per_day", &to_f64(&self.average_per_day))?;
+        state.serialize_field("left_average_per_day", &to_f64(&self.left_average_per_day))?;
+        state.serialize_field("missing_rates", &self.missing_rates)?;
+        state.end()
+    }
+}
+
+impl Serialize for SerializedYearBudget {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let money = |amount: &BigDecimal| Money::new(amount.clone(), &self.amount_currency);
+        let mut state = serializer.serialize_struct("SerializedYearBudget", 12)?;
+
+        state.serialize_field("id", &self.id)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("amount", &money(&self.amount))?;
+        state.serialize_field("year", &self.year)?;
+        state.serialize_field("tags", &self.tags)?;
+        state.serialize_field("tags_type", &self.tags_type)?;
+        state.serialize_field("comment", &self.comment)?;
+        state.serialize_field("spent", &money(&self.spent))?;
+        state.serialize_field("left", &money(&self.left))?;
+        state.serialize_field("average_per_month", &money(&self.average_per_month))?;
+        state.serialize_field(
+            "left_average_per_month",
+            &money(&self.left_average_per_month),
+        )?;
+        state.serialize_field("missing_rates", &self.missing_rates)?;
+        state.end()
+    }
+}
+
+impl LegacySerialize for SerializedYearBudget {
+    fn serialize_legacy<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("SerializedYearBudget", 13)?;
+
+        state.serialize_field("id", &self.id)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("amount", &self.amount)?;
+        state.serialize_field("amount_currency", &self.amount_currency)?;
+        state.serialize_field("year", &self.year)?;
+        state.serialize_field("tags", &self.tags)?;
+        state.serialize_field("tags_type", &self.tags_type)?;
+        state.serialize_field("comment", &self.comment)?;
+        state.serialize_field("spent", &to_f64(&self.spent))?;
+        state.serialize_field("left", &to_f64(&self.left))?;
+        state.serialize_field("average_per_month", &to_f64(&self.average_per_month))?;
+        state.serialize_field(
+            "left_average_per_month",
+            &to_f64(&self.left_average_per_month),
+        )?;
+        state.serialize_field("missing_rates", &self.missing_rates)?;
+        state.end()
+    }
 }
 
 #[derive(Debug, Serialize)]
-struct Currency {
-    code: CurrencyCode,
-    name: CurrencyName,
+struct Currency<'a> {
+    code: &'a str,
+    name: &'a str,
 }
 
 #[derive(Debug, Serialize)]
-struct Amount {
+struct Amount<'a> {
     amount: f64,
-    currency: Currency,
+    currency: Currency<'a>,
 }
 
 impl Serialize for Record {
@@ -90,11 +327,33 @@ impl Serialize for Record {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Record", 6)?;
+        let mut state = serializer.serialize_struct("Record", 7)?;
+
+        let amount = Money::new(self.amount.clone(), &self.amount_currency);
+
+        state.serialize_field("amount", &amount)?;
+        state.serialize_field("created_at", &self.created_at.timestamp())?;
+        state.serialize_field("id", &self.id)?;
+        state.serialize_field("tags", &self.tags)?;
+        state.serialize_field("transaction_type", &self.transaction_type)?;
+        state.serialize_field("user_id", &self.user_id)?;
+        state.serialize_field("comment", &self.comment)?;
+        state.end()
+    }
+}
+
+impl LegacySerialize for Record {
+    fn serialize_legacy<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Record", 7)?;
 
         let currency = Currency {
-            code: CurrencyCode::Cad,
-            name: CurrencyName::Cad,
+            code: &self.amount_currency,
+            name: currency::find(&self.amount_currency)
+                .map(|currency| currency.name)
+                .unwrap_or_default(),
         };
         let amount = Amount {
             amount: self.amount.to_f64().unwrap(),
@@ -0,0 +1,120 @@
+use bigdecimal::{BigDecimal, Zero};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::currency;
+
+/// An amount of money in a currency.
+///
+/// It is serialized with the amount as a decimal string rounded to the minor
+/// units of the currency, so no precision is lost on the way to a client:
+/// `{"amount": "12.50", "currency": "CAD"}`.
+///
+/// Both that shape and the legacy one, `{"amount": 12.5, "currency": {"code": "CAD"}}`,
+/// are accepted as input.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct Money {
+    pub amount: BigDecimal,
+    #[serde(deserialize_with = "deserialize_currency")]
+    pub currency: String,
+}
+
+impl Money {
+    /// Money rounded to the minor units of the currency (half away from zero).
+    pub fn new(amount: BigDecimal, currency: &str) -> Self {
+        let scale = i64::from(minor_units(currency));
+        let half = BigDecimal::new(5.into(), scale + 1);
+        let amount = if amount < BigDecimal::zero() {
+            amount - half
+        } else {
+            amount + half
+        };
+
+        Self {
+            amount: amount.with_scale(scale),
+            currency: currency.to_string(),
+        }
+    }
+}
+
+fn minor_units(code: &str) -> u32 {
+    currency::find(code)
+        .map(|currency| currency.minor_units)
+        .unwrap_or(2)
+}
+
+fn deserialize_currency<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Currency {
+        Code(String),
+        Legacy { code: String },
+    }
+
+    match Currency::deserialize(deserializer) {
+        Ok(Currency::Code(code)) | Ok(Currency::Legacy { code }) => Ok(code),
+        Err(_) => Err(de::Error::custom(
+            "expected a currency code or an object with a \"code\" field",
+        )),
+    }
+}
+
+/// Serialization of API responses in the shape used before `Money`, with
+/// amounts as floats. It is kept for clients that did not migrate yet.
+pub trait LegacySerialize {
+    fn serialize_legacy<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+/// Serializes the wrapped value with `LegacySerialize`.
+pub struct Legacy<'a, T>(pub &'a T);
+
+impl<T: LegacySerialize> Serialize for Legacy<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_legacy(serializer)
+    }
+}
+
+impl<T: LegacySerialize> LegacySerialize for Vec<T> {
+    fn serialize_legacy<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(Legacy))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn money(amount: &str, currency: &str) -> Money {
+        Money::new(BigDecimal::from_str(amount).unwrap(), currency)
+    }
+
+    #[test]
+    fn rounds_to_minor_units() {
+        assert_eq!("12.50", money("12.5", "CAD").amount.to_string());
+        assert_eq!("0.67", money("0.666", "CAD").amount.to_string());
+        assert_eq!("-0.67", money("-0.666", "CAD").amount.to_string());
+        assert_eq!("0.33", money("0.334", "CAD").amount.to_string());
+        assert_eq!("1235", money("1234.5", "JPY").amount.to_string());
+    }
+
+    #[test]
+    fn serializes_amount_as_string() {
+        assert_eq!(
+            serde_json::json!({"amount": "12.50", "currency": "CAD"}),
+            serde_json::to_value(money("12.5", "CAD")).unwrap()
+        );
+    }
+
+    #[test]
+    fn deserializes_both_shapes() {
+        let new: Money = serde_json::from_str(r#"{"amount": "12.10", "currency": "CAD"}"#).unwrap();
+        let legacy: Money = serde_json::from_str(
+            r#"{"amount": 12.1, "currency": {"code": "CAD", "name": "Canadian Dollar"}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(BigDecimal::from_str("12.10").unwrap(), new.amount);
+        assert_eq!("CAD", new.currency);
+        assert_eq!("CAD", legacy.currency);
+    }
+}
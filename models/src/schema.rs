@@ -22,6 +22,31 @@
 //     }
 // }
 
+table! {
+    auth_personal_tokens (id) {
+        id -> Int4,
+        user_id -> Int4,
+        name -> Varchar,
+        token_hash -> Varchar,
+        scope -> Varchar,
+        created_at -> Timestamptz,
+        last_used_at -> Nullable<Timestamptz>,
+    }
+}
+
+table! {
+    auth_refresh_tokens (id) {
+        id -> Int4,
+        user_id -> Int4,
+        family -> Varchar,
+        token_hash -> Varchar,
+        created_at -> Timestamptz,
+        expires_at -> Timestamptz,
+        used_at -> Nullable<Timestamptz>,
+        revoked_at -> Nullable<Timestamptz>,
+    }
+}
+
 table! {
     auth_user (id) {
         date_joined -> Timestamptz,
@@ -31,12 +56,10 @@ table! {
         is_active -> Bool,
         is_staff -> Bool,
         is_superuser -> Bool,
+        last_login -> Nullable<Timestamptz>,
         last_name -> Varchar,
         password -> Varchar,
-        tags -> Array<Text>,
         username -> Varchar,
-
-        // last_login -> Nullable<Timestamptz>,
     }
 }
 
@@ -66,6 +89,31 @@ table! {
         tags -> Array<Text>,
         tags_type -> Varchar,
         user_id -> Int4,
+        comment -> Nullable<Text>,
+    }
+}
+
+table! {
+    budgets_yearbudget (id) {
+        amount -> Numeric,
+        amount_currency -> Varchar,
+        comment -> Nullable<Text>,
+        id -> Int4,
+        name -> Varchar,
+        tags -> Array<Text>,
+        tags_type -> Varchar,
+        user_id -> Int4,
+        year -> Int4,
+    }
+}
+
+table! {
+    exchange_rates (id) {
+        id -> Int4,
+        date -> Date,
+        base -> Varchar,
+        quote -> Varchar,
+        rate -> Numeric,
     }
 }
 
@@ -128,6 +176,26 @@ table! {
     }
 }
 
+table! {
+    tags (id) {
+        id -> Int4,
+        user_id -> Int4,
+        parent_id -> Nullable<Int4>,
+        name -> Text,
+        position -> Int4,
+    }
+}
+
+table! {
+    user_settings (user_id) {
+        user_id -> Int4,
+        timezone -> Varchar,
+        default_currency -> Varchar,
+        week_start -> Int2,
+        month_start_day -> Int2,
+    }
+}
+
 // joinable!(auth_group_permissions -> auth_group (group_id));
 // joinable!(auth_group_permissions -> auth_permission (permission_id));
 // joinable!(auth_permission -> django_content_type (content_type_id));
@@ -137,13 +205,24 @@ table! {
 // joinable!(auth_user_user_permissions -> auth_user (user_id));
 // joinable!(django_admin_log -> auth_user (user_id));
 // joinable!(django_admin_log -> django_content_type (content_type_id));
+joinable!(auth_personal_tokens -> auth_user (user_id));
+joinable!(auth_refresh_tokens -> auth_user (user_id));
 joinable!(budgets_budget -> auth_user (user_id));
+joinable!(budgets_yearbudget -> auth_user (user_id));
 joinable!(records_record -> auth_user (user_id));
+joinable!(tags -> auth_user (user_id));
+joinable!(user_settings -> auth_user (user_id));
 
 allow_tables_to_appear_in_same_query!(
+    auth_personal_tokens,
+    auth_refresh_tokens,
     auth_user,
     records_record,
     budgets_budget,
+    budgets_yearbudget,
+    exchange_rates,
+    tags,
+    user_settings,
     //     auth_group,
     //     auth_group_permissions,
     //     auth_permission,
@@ -1,23 +1,37 @@
 use actix_web::{middleware::Logger, App, HttpServer};
 use dotenv::dotenv;
 
-use octo_budget_api::{config, db::ConnectionPool, redis::Redis, routes::init_routes};
+use octo_budget_api::{
+    config,
+    db::{inactive_users::InactiveUsers, personal_tokens::PersonalTokens, ConnectionPool},
+    mailer,
+    redis::{token_denylist::TokenDenylist, Redis},
+    routes::init_routes,
+};
 use octo_budget_lib::auth_token::ApiJwtTokenAuthConfig;
 
 #[actix_rt::main]
 async fn main() -> std::io::Result<()> {
     dotenv().expect("Failed to parse .env file");
     env_logger::init();
+    config::check();
 
-    let redis = Redis::new().await;
+    let redis = Redis::new();
+    let mailer = mailer::from_config();
 
     HttpServer::new(move || {
+        let pool = ConnectionPool::new();
+
         App::new()
-            .data(ConnectionPool::new())
+            .data(pool.clone())
             .data(redis.clone())
-            .app_data(ApiJwtTokenAuthConfig::new(
-                config::AUTH_TOKEN_SECRET.as_bytes(),
-            ))
+            .data(mailer.clone())
+            .app_data(
+                ApiJwtTokenAuthConfig::new(config::AUTH_TOKEN_SECRET.as_bytes())
+                    .revocation(TokenDenylist::new(redis.clone()))
+                    .revocation(InactiveUsers::new(pool.clone()))
+                    .personal_tokens(PersonalTokens::new(pool)),
+            )
             .wrap(middlewares::force_https::ForceHttps::new(
                 config::is_force_https(),
             ))
@@ -2,16 +2,29 @@
 mod auth_app;
 mod budgets_app;
 pub mod frontend_app;
+mod password_app;
 mod records_app;
+mod registration_app;
+mod reports_app;
 mod tags_app;
-pub mod users_app;
+mod tokens_app;
+mod users_app;
+mod year_budgets_app;
 
 pub use auth_app::service::Service as AuthService;
 pub use budgets_app::service::Service as BudgetsService;
+pub use password_app::service::ResetService as PasswordResetService;
+pub use password_app::service::Service as PasswordService;
 pub use records_app::service::Service as RecordsService;
+pub use registration_app::service::Service as RegistrationService;
+pub use reports_app::service::Service as ReportsService;
 pub use tags_app::service::Service as TagsService;
+pub use tokens_app::service::Service as TokensService;
+pub use users_app::service::Service as UsersService;
+pub use year_budgets_app::service::Service as YearBudgetsService;
 
 pub mod forms;
 pub mod helpers;
 pub mod index_params;
 pub mod index_response;
+pub mod money_format;
@@ -1,24 +1,143 @@
 use actix_web::{
     post,
     web::{self, Json},
-    HttpResponse, Result,
+    HttpRequest, HttpResponse, Result,
 };
+use log::error;
+use octo_budget_lib::auth_token::AuthToken;
+use serde::Deserialize;
+use serde_json::json;
 
 use self::utils::generate_token;
 use super::forms::auth::{self, Form};
-use crate::db::{queries::FindUserByName, ConnectionPool};
+use crate::db::{
+    models::AuthUser,
+    queries::{
+        FindUserByName, IssueRefreshToken, RevokeRefreshTokens, RotateRefreshToken, Rotated,
+        UpdateLastLogin, UpgradePasswordHash,
+    },
+    ConnectionPool,
+};
+use crate::redis::{
+    login_throttle::LoginThrottle,
+    token_denylist::{revoke_all_tokens, revoke_token, token_generation},
+    Redis,
+};
 
 mod response_data;
-mod utils;
+pub(super) mod utils;
+
+#[derive(Deserialize)]
+struct RefreshForm {
+    refresh: String,
+}
+
+async fn authenticate(username: String, password: &str, pool: &ConnectionPool) -> Result<AuthUser> {
+    let user = pool.execute(FindUserByName::new(username)).await?;
+    Form::validate_password(&user, password)?;
+
+    Ok(user)
+}
 
 #[post("/create/")]
-async fn create(form: Json<Form>, pool: web::Data<ConnectionPool>) -> Result<HttpResponse> {
+async fn create(
+    req: HttpRequest,
+    form: Json<Form>,
+    pool: web::Data<ConnectionPool>,
+    redis: web::Data<Redis>,
+) -> Result<HttpResponse> {
     let auth::Data { username, password } = form.into_inner().validate()?;
-    let user = pool.execute(FindUserByName::new(username)).await?;
+    let throttle = LoginThrottle::new(&username, &req);
+
+    throttle.check(&redis).await?;
+
+    let user = match authenticate(username, &password, &pool).await {
+        Ok(user) => user,
+        Err(e) => {
+            throttle.failed(&redis).await;
+            return Err(e);
+        }
+    };
+    throttle.succeeded(&redis).await;
+
+    Form::validate_active(&user)?;
+
+    if UpgradePasswordHash::is_needed(&user) {
+        // the user is already authenticated, failing here would only lock them out
+        if let Err(e) = pool
+            .execute(UpgradePasswordHash::new(&user, password))
+            .await
+        {
+            error!("Failed to upgrade password hash of user {}: {}", user.id, e);
+        }
+    }
+
+    pool.execute(UpdateLastLogin::new(user.id)).await?;
+    let refresh = pool.execute(IssueRefreshToken::new(user.id)).await?;
+    let generation = token_generation(user.id.into(), &redis).await;
+
+    Ok(HttpResponse::Ok().json(generate_token(user.id, generation, refresh)))
+}
+
+#[post("/refresh/")]
+async fn renew(
+    form: Json<RefreshForm>,
+    pool: web::Data<ConnectionPool>,
+    redis: web::Data<Redis>,
+) -> Result<HttpResponse> {
+    let rotated = pool
+        .execute(RotateRefreshToken::new(form.into_inner().refresh))
+        .await?;
+
+    Ok(match rotated {
+        Some(Rotated { user_id, refresh }) => {
+            let generation = token_generation(user_id.into(), &redis).await;
+            HttpResponse::Ok().json(generate_token(user_id, generation, refresh))
+        }
+        None => HttpResponse::Unauthorized().json(json!({
+            "detail": "Token is invalid or expired",
+            "code": "token_not_valid",
+        })),
+    })
+}
+
+/// Revokes the access token of the request, and the refresh token if it is
+/// sent along.
+#[post("/logout/")]
+async fn logout(
+    token: AuthToken,
+    form: Option<Json<RefreshForm>>,
+    pool: web::Data<ConnectionPool>,
+    redis: web::Data<Redis>,
+) -> Result<HttpResponse> {
+    revoke_token(&token, &redis).await?;
+
+    if let Some(form) = form {
+        pool.execute(RevokeRefreshTokens::Family(
+            token.user_id().into(),
+            form.into_inner().refresh,
+        ))
+        .await?;
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Takes `AuthToken` rather than `UserId`, so a personal token cannot log
+/// its owner out.
+#[post("/logout/all/")]
+async fn logout_all(
+    token: AuthToken,
+    pool: web::Data<ConnectionPool>,
+    redis: web::Data<Redis>,
+) -> Result<HttpResponse> {
+    let user_id = token.user_id();
 
-    Form::validate_password(&user, &password)?;
+    revoke_all_tokens(user_id, &redis).await?;
+    pool.execute(RevokeRefreshTokens::User(user_id.into()))
+        .await?;
 
-    Ok(HttpResponse::Ok().json(generate_token(&user)))
+    Ok(HttpResponse::NoContent().finish())
 }
 
 pub mod service {
@@ -29,7 +148,10 @@ pub mod service {
 
     impl HttpServiceFactory for Service {
         fn register(self, config: &mut actix_web::dev::AppService) {
-            HttpServiceFactory::register(create, config)
+            HttpServiceFactory::register(create, config);
+            HttpServiceFactory::register(renew, config);
+            HttpServiceFactory::register(logout, config);
+            HttpServiceFactory::register(logout_all, config);
         }
     }
 }
@@ -3,15 +3,21 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Data {
     token: String,
+    refresh: String,
 }
 
 impl Data {
-    pub fn from_token(token: String) -> Self {
-        Self { token }
+    pub fn new(token: String, refresh: String) -> Self {
+        Self { token, refresh }
     }
 
     #[cfg(test)]
     pub fn token(&self) -> &str {
         &self.token
     }
+
+    #[cfg(test)]
+    pub fn refresh(&self) -> &str {
+        &self.refresh
+    }
 }
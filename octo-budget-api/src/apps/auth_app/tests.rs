@@ -1,12 +1,13 @@
 use super::response_data::Data as ResponseData;
 use super::*;
 use crate::{
+    apps::forms::personal_token::Data,
     await_test_server,
-    db::builders::UserBuilder,
-    tests::{setup_env, DbSession},
+    db::{builders::UserBuilder, queries::CreatePersonalToken},
+    tests::{setup_env, DbSession, RequestTokenAuthExt},
 };
 use actix_web::http::{header, Method, StatusCode};
-use actix_web::test::{call_service, read_response, read_response_json, TestRequest};
+use actix_web::test::{call_service, read_body, read_response, read_response_json, TestRequest};
 use bytes::Bytes;
 use serde_json::{json, Value};
 use service::Service;
@@ -141,3 +142,417 @@ async fn when_ok_auth_response_body_correct() {
 
     assert!(!data.token().is_empty());
 }
+
+fn refresh_request(refresh: &str) -> actix_http::Request {
+    TestRequest::with_uri("/refresh/")
+        .method(Method::POST)
+        .set_json(&json!({ "refresh": refresh }))
+        .to_request()
+}
+
+#[actix_rt::test]
+async fn refresh_token_can_be_exchanged_for_new_pair() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(
+        UserBuilder::default()
+            .username("ok auth user")
+            .password("dummy password"),
+    );
+
+    let mut service = await_test_server!(Service);
+    let request = login_request(json!({"username": user.username, "password": "dummy password"}));
+    let login: ResponseData = read_response_json(&mut service, request).await;
+
+    assert!(!login.refresh().is_empty());
+
+    let renewed: ResponseData =
+        read_response_json(&mut service, refresh_request(login.refresh())).await;
+
+    assert!(!renewed.token().is_empty());
+    assert_ne!(login.refresh(), renewed.refresh());
+}
+
+#[actix_rt::test]
+async fn reused_refresh_token_is_rejected() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(
+        UserBuilder::default()
+            .username("ok auth user")
+            .password("dummy password"),
+    );
+
+    let mut service = await_test_server!(Service);
+    let request = login_request(json!({"username": user.username, "password": "dummy password"}));
+    let login: ResponseData = read_response_json(&mut service, request).await;
+
+    let response = call_service(&mut service, refresh_request(login.refresh())).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let response = call_service(&mut service, refresh_request(login.refresh())).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    let body: Value = read_response_json(&mut service, refresh_request(login.refresh())).await;
+    assert_eq!(
+        json!({"detail": "Token is invalid or expired", "code": "token_not_valid"}),
+        body
+    );
+}
+
+fn logout_request(uri: &str, token: &str) -> TestRequest {
+    TestRequest::with_uri(uri)
+        .method(Method::POST)
+        .header(header::AUTHORIZATION, format!("JWT {}", token))
+}
+
+async fn login<S, B>(service: &mut S, username: &str) -> ResponseData
+where
+    S: actix_web::dev::Service<
+        Request = actix_http::Request,
+        Response = actix_web::dev::ServiceResponse<B>,
+        Error = actix_web::Error,
+    >,
+    B: actix_web::body::MessageBody,
+{
+    let request = login_request(json!({"username": username, "password": "dummy password"}));
+
+    read_response_json(service, request).await
+}
+
+#[actix_rt::test]
+async fn logout_revokes_access_and_refresh_tokens() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(
+        UserBuilder::default()
+            .username("logout user")
+            .password("dummy password"),
+    );
+
+    let mut service = await_test_server!(Service);
+    let tokens = login(&mut service, &user.username).await;
+
+    let request = logout_request("/logout/", tokens.token())
+        .set_json(&json!({ "refresh": tokens.refresh() }))
+        .to_request();
+    let response = call_service(&mut service, request).await;
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let request = logout_request("/logout/", tokens.token()).to_request();
+    let response = call_service(&mut service, request).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    let response = call_service(&mut service, refresh_request(tokens.refresh())).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+}
+
+#[actix_rt::test]
+async fn logout_does_not_affect_other_sessions() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(
+        UserBuilder::default()
+            .username("logout user")
+            .password("dummy password"),
+    );
+
+    let mut service = await_test_server!(Service);
+    let phone = login(&mut service, &user.username).await;
+    let laptop = login(&mut service, &user.username).await;
+
+    let request = logout_request("/logout/", phone.token()).to_request();
+    let response = call_service(&mut service, request).await;
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let request = logout_request("/logout/", laptop.token()).to_request();
+    let response = call_service(&mut service, request).await;
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+}
+
+#[actix_rt::test]
+async fn logout_all_revokes_every_session() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(
+        UserBuilder::default()
+            .username("logout user")
+            .password("dummy password"),
+    );
+
+    let mut service = await_test_server!(Service);
+    let phone = login(&mut service, &user.username).await;
+    let laptop = login(&mut service, &user.username).await;
+
+    let request = logout_request("/logout/all/", phone.token()).to_request();
+    let response = call_service(&mut service, request).await;
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let request = logout_request("/logout/", laptop.token()).to_request();
+    let response = call_service(&mut service, request).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    let response = call_service(&mut service, refresh_request(laptop.refresh())).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    let tablet = login(&mut service, &user.username).await;
+    let request = logout_request("/logout/", tablet.token()).to_request();
+    let response = call_service(&mut service, request).await;
+    assert_eq!(
+        StatusCode::NO_CONTENT,
+        response.status(),
+        "new logins are not affected"
+    );
+}
+
+#[actix_rt::test]
+async fn personal_token_cannot_log_out_all_sessions() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(
+        UserBuilder::default()
+            .username("scripted user")
+            .password("dummy password"),
+    );
+    let data = Data {
+        name: "script".to_string(),
+        scope: "read_write".to_string(),
+    };
+    let (_, token) = ConnectionPool::new()
+        .execute(CreatePersonalToken::new(user.id, data))
+        .await
+        .unwrap();
+
+    let mut service = await_test_server!(Service);
+    let phone = login(&mut service, &user.username).await;
+
+    let request = TestRequest::with_uri("/logout/all/")
+        .method(Method::POST)
+        .token_auth(&token)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    let response = call_service(&mut service, refresh_request(phone.refresh())).await;
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+#[actix_rt::test]
+async fn inactive_user_cannot_log_in() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(
+        UserBuilder::default()
+            .username("inactive user")
+            .password("dummy password")
+            .is_active(false),
+    );
+
+    let mut service = await_test_server!(Service);
+    let request = login_request(json!({"username": user.username, "password": "dummy password"}));
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    let request = login_request(json!({"username": user.username, "password": "dummy password"}));
+    let body: Value = read_response_json(&mut service, request).await;
+
+    assert_eq!(
+        json!({"non_field_errors": ["User account is disabled."]}),
+        body
+    );
+}
+
+#[actix_rt::test]
+async fn successful_login_updates_last_login() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(
+        UserBuilder::default()
+            .username("ok auth user")
+            .password("dummy password"),
+    );
+
+    let mut service = await_test_server!(Service);
+    login(&mut service, &user.username).await;
+
+    let user = session.find_user_by_name("ok auth user");
+    assert!(user.last_login.is_some(), "last_login is not updated");
+}
+
+#[actix_rt::test]
+async fn tokens_of_deactivated_user_are_rejected() {
+    use crate::db::schema::auth_user::dsl::*;
+    use diesel::prelude::*;
+
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(
+        UserBuilder::default()
+            .username("ok auth user")
+            .password("dummy password"),
+    );
+
+    let mut service = await_test_server!(Service);
+    let tokens = login(&mut service, &user.username).await;
+
+    diesel::update(auth_user.find(user.id))
+        .set(is_active.eq(false))
+        .execute(&crate::db::ConnectionPool::new().conn())
+        .unwrap();
+
+    let request = logout_request("/logout/", tokens.token()).to_request();
+    let response = call_service(&mut service, request).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    let response = call_service(&mut service, refresh_request(tokens.refresh())).await;
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+}
+
+#[actix_rt::test]
+async fn login_upgrades_legacy_password_hash() {
+    use crate::db::schema::auth_user::dsl::*;
+    use diesel::prelude::*;
+    use djangohashers::{make_password_with_algorithm, Algorithm};
+
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().username("legacy user"));
+    let legacy_hash = make_password_with_algorithm("dummy password", Algorithm::SHA1);
+
+    diesel::update(auth_user.find(user.id))
+        .set(password.eq(&legacy_hash))
+        .execute(&crate::db::ConnectionPool::new().conn())
+        .unwrap();
+
+    let mut service = await_test_server!(Service);
+    login(&mut service, "legacy user").await;
+
+    let user = session.find_user_by_name("legacy user");
+    assert!(user.password.starts_with("pbkdf2_sha256$"));
+    assert_eq!(
+        Ok(true),
+        djangohashers::check_password("dummy password", &user.password)
+    );
+}
+
+mod throttling {
+    use super::*;
+    use crate::config::{login_failures_redis_key, login_lock_redis_key};
+
+    async fn reset_throttling(scope: &str, id: &str) {
+        let redis = Redis::connect().await.expect("Failed to connect to redis");
+
+        let _: () = redis::cmd("del")
+            .arg(login_failures_redis_key(scope, id))
+            .arg(login_lock_redis_key(scope, id))
+            .query_async(&mut redis.connection().unwrap())
+            .await
+            .expect("Failed to reset login throttling");
+    }
+
+    fn login_from(ip: &str, username: &str, password: &str) -> actix_http::Request {
+        TestRequest::with_uri("/create/")
+            .method(Method::POST)
+            .peer_addr(format!("{}:43210", ip).parse().unwrap())
+            .set_json(&json!({"username": username, "password": password}))
+            .to_request()
+    }
+
+    #[actix_rt::test]
+    async fn username_is_throttled_after_failed_logins() {
+        setup_env();
+        let session = DbSession::new();
+        session.create_user(
+            UserBuilder::default()
+                .username("throttled")
+                .password("dummy password"),
+        );
+        reset_throttling("username", "throttled").await;
+        let mut service = await_test_server!(Service);
+
+        // allowed attempts, then the first delay
+        for _ in 0..6 {
+            let request = login_request(json!({"username": "throttled", "password": "wrong"}));
+            let response = call_service(&mut service, request).await;
+
+            assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+        }
+
+        // even the right password is not checked
+        let request = login_request(json!({"username": "Throttled", "password": "dummy password"}));
+        let response = call_service(&mut service, request).await;
+
+        assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+        assert_eq!("1", response.headers().get(header::RETRY_AFTER).unwrap());
+        assert_eq!(
+            json!({"detail": "Request was throttled. Expected available in 1 seconds."}),
+            serde_json::from_slice::<Value>(&read_body(response).await).unwrap()
+        );
+    }
+
+    #[actix_rt::test]
+    async fn successful_login_resets_username_failures() {
+        setup_env();
+        let session = DbSession::new();
+        session.create_user(
+            UserBuilder::default()
+                .username("forgetful")
+                .password("dummy password"),
+        );
+        reset_throttling("username", "forgetful").await;
+        let mut service = await_test_server!(Service);
+
+        for password in &[
+            "wrong",
+            "wrong",
+            "wrong",
+            "wrong",
+            "wrong",
+            "dummy password",
+        ] {
+            let request = login_request(json!({"username": "forgetful", "password": password}));
+            call_service(&mut service, request).await;
+        }
+
+        let request = login_request(json!({"username": "forgetful", "password": "wrong"}));
+        let response = call_service(&mut service, request).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+        let request = login_request(json!({"username": "forgetful", "password": "dummy password"}));
+        let response = call_service(&mut service, request).await;
+        assert_eq!(StatusCode::OK, response.status());
+    }
+
+    #[actix_rt::test]
+    async fn ip_is_throttled_across_usernames() {
+        setup_env();
+        let session = DbSession::new();
+        session.create_user(
+            UserBuilder::default()
+                .username("victim")
+                .password("dummy password"),
+        );
+        reset_throttling("ip", "10.11.12.13").await;
+        reset_throttling("username", "victim").await;
+        let mut service = await_test_server!(Service);
+
+        for n in 0..21 {
+            let username = format!("guess-{}", n);
+            reset_throttling("username", &username).await;
+
+            let request = login_from("10.11.12.13", &username, "wrong");
+            let response = call_service(&mut service, request).await;
+
+            assert_ne!(StatusCode::TOO_MANY_REQUESTS, response.status());
+        }
+
+        let request = login_from("10.11.12.13", "victim", "dummy password");
+        let response = call_service(&mut service, request).await;
+        assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+
+        let request = login_from("10.11.12.14", "victim", "dummy password");
+        let response = call_service(&mut service, request).await;
+        assert_eq!(StatusCode::OK, response.status());
+    }
+}
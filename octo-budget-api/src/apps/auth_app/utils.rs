@@ -2,17 +2,20 @@ use octo_budget_lib::auth_token::AuthToken;
 
 use super::response_data::Data;
 use crate::config;
-use crate::db::models::AuthUser as UserModel;
 
-pub fn generate_token(user: &UserModel) -> Data {
+/// Pairs a fresh access token with the refresh token it can be renewed with.
+pub fn generate_token(user_id: i32, generation: i64, refresh: String) -> Data {
     let secret = config::AUTH_TOKEN_SECRET.as_bytes();
-    let token = AuthToken::new(user.id).encrypt(secret);
-    Data::from_token(token)
+    let token = AuthToken::new(user_id)
+        .with_generation(generation)
+        .encrypt(secret);
+    Data::new(token, refresh)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::models::AuthUser as UserModel;
 
     fn setup() {
         dotenv::dotenv().expect("Failed to parse .env file");
@@ -23,11 +26,17 @@ mod tests {
         setup();
 
         let user = make_user_with_pass("foo");
-        let data = generate_token(&make_user_with_pass("foo"));
-        let token = AuthToken::new(user.id).encrypt(config::AUTH_TOKEN_SECRET.as_bytes());
-        let expected_data = Data::from_token(token);
-
-        assert_eq!(expected_data, data);
+        let data: serde_json::Value =
+            serde_json::to_value(generate_token(user.id, 2, "refresh".to_string())).unwrap();
+        let token = AuthToken::from(
+            data["token"].as_str().unwrap(),
+            config::AUTH_TOKEN_SECRET.as_bytes(),
+        )
+        .expect("Failed to decode token");
+
+        assert_eq!(user.id, token.user_id());
+        assert_eq!(2, token.generation());
+        assert_eq!("refresh", data["refresh"]);
     }
 
     fn make_user_with_pass(password: &'static str) -> UserModel {
@@ -43,10 +52,10 @@ mod tests {
             is_active: true,
             is_superuser: true,
             first_name: "".to_string(),
+            last_login: None,
             last_name: "".to_string(),
             is_staff: false,
             date_joined: NaiveDateTime::from_timestamp(0, 0),
-            tags: Vec::new(),
         }
     }
 }
@@ -1,14 +1,24 @@
-use actix_web::{get, web, web::Query, HttpResponse, Result};
+use actix_web::{
+    delete, get, post, put,
+    web::{self, Json, Path, Query},
+    HttpResponse, Result,
+};
 use octo_budget_lib::auth_token::UserId;
 
+use super::forms::budget::Form;
 use super::index_params::Params;
-use crate::db::{queries::GetBudgets, ConnectionPool};
+use super::money_format::MoneyFormat;
+use crate::db::{
+    queries::{CreateBudget, DeleteBudget, FindBudget, GetBudget, GetBudgets, UpdateBudget},
+    ConnectionPool,
+};
 
 #[get("/budget-detail/")]
 async fn index(
     user_id: UserId,
     params: Query<Params>,
     pool: web::Data<ConnectionPool>,
+    money_format: MoneyFormat,
 ) -> Result<HttpResponse> {
     let params = params.into_inner().validate()?;
 
@@ -20,7 +30,63 @@ async fn index(
 
     let budgets = pool.execute(query).await?;
 
-    Ok(HttpResponse::Ok().json(budgets))
+    Ok(money_format.json(&budgets))
+}
+
+#[get("/budget-detail/{id}/")]
+async fn show(
+    user_id: UserId,
+    budget_id: Path<i32>,
+    pool: web::Data<ConnectionPool>,
+    money_format: MoneyFormat,
+) -> Result<HttpResponse> {
+    let budget_id = budget_id.into_inner();
+    let budget = pool.execute(GetBudget::new(budget_id, user_id)).await?;
+
+    Ok(money_format.json(&budget))
+}
+
+#[post("/budget-detail/")]
+async fn create(
+    user_id: UserId,
+    form: Json<Form>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    use serde_json::json;
+
+    let data = form.into_inner().validate()?;
+    let id = pool.execute(CreateBudget::new(&data, user_id)).await?;
+
+    Ok(HttpResponse::Ok().json(json!({ "id": id })))
+}
+
+#[put("/budget-detail/{id}/")]
+async fn update(
+    user_id: UserId,
+    budget_id: Path<i32>,
+    form: Json<Form>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let budget_id = budget_id.into_inner();
+    let data = form.into_inner().validate()?;
+
+    let budget = pool.execute(FindBudget::new(budget_id, user_id)).await?;
+    pool.execute(UpdateBudget::new(budget.id, &data, user_id))
+        .await?;
+
+    Ok(HttpResponse::Ok().json(""))
+}
+
+#[delete("/budget-detail/{id}/")]
+async fn destroy(
+    user_id: UserId,
+    budget_id: Path<i32>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let budget_id = budget_id.into_inner();
+    pool.execute(DeleteBudget::new(budget_id, user_id)).await?;
+
+    Ok(HttpResponse::NoContent().finish())
 }
 
 pub mod service {
@@ -32,6 +98,13 @@ pub mod service {
     impl HttpServiceFactory for Service {
         fn register(self, config: &mut actix_web::dev::AppService) {
             HttpServiceFactory::register(index, config);
+            HttpServiceFactory::register(show, config);
+            HttpServiceFactory::register(create, config);
+            HttpServiceFactory::register(update, config);
+            HttpServiceFactory::register(destroy, config);
         }
     }
 }
+
+#[cfg(test)]
+mod tests;
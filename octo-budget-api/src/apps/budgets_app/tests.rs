@@ -0,0 +1,314 @@
+use super::service::Service;
+use crate::{
+    await_test_server,
+    db::builders::{BudgetBuilder, UserBuilder},
+    tests::{self, setup_env, RequestJwtAuthExt as _},
+};
+use actix_web::{
+    http::{Method, StatusCode},
+    test::{call_service, read_body, TestRequest},
+};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+
+fn budget_payload() -> Value {
+    json!({
+        "name": "Food",
+        "amount": {"amount": 500, "currency": { "code": "CAD", "name": "Canadian Dollar" }},
+        "start_date": "2020-03-01",
+        "tags": ["food"],
+        "tags_type": "INCL",
+    })
+}
+
+#[actix_rt::test]
+async fn create_requires_auth() {
+    setup_env();
+
+    let mut service = await_test_server!(Service);
+    let request = TestRequest::with_uri("/budget-detail/")
+        .method(Method::POST)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::UNAUTHORIZED,
+        response.status(),
+        "wrong status code"
+    );
+}
+
+#[actix_rt::test]
+async fn create_happy_path() {
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+
+    let request = TestRequest::with_uri("/budget-detail/")
+        .method(Method::POST)
+        .jwt_auth(user.id)
+        .set_json(&budget_payload())
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    let new_budget_id = response_body.get("id").unwrap().as_i64().unwrap() as i32;
+    let budget = session.find_budget(new_budget_id);
+
+    assert_eq!("Food", budget.name);
+    assert_eq!(BigDecimal::from(500), budget.amount);
+    assert_eq!(NaiveDate::from_ymd(2020, 3, 1), budget.start_date);
+    assert_eq!(vec!["food"], budget.tags);
+    assert_eq!("INCL", budget.tags_type);
+    assert_eq!(user.id, budget.user_id);
+}
+
+#[actix_rt::test]
+async fn create_with_invalid_data() {
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+
+    let mut payload = budget_payload();
+    payload["tags_type"] = json!("FOO");
+    payload["start_date"] = json!("yesterday");
+
+    let request = TestRequest::with_uri("/budget-detail/")
+        .method(Method::POST)
+        .jwt_auth(user.id)
+        .set_json(&payload)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::BAD_REQUEST,
+        response.status(),
+        "wrong status code"
+    );
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    assert_eq!(
+        json!({
+            "start_date": ["Date has wrong format. Use one of these formats instead: YYYY-MM-DD."],
+            "tags_type": ["\"FOO\" is not a valid choice."],
+        }),
+        response_body
+    );
+    assert_eq!(0, session.count_budgets());
+}
+
+#[actix_rt::test]
+async fn show_happy_path() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    let budget = session.create_budget(
+        BudgetBuilder::default()
+            .user_id(user.id)
+            .name("Food")
+            .finish(),
+    );
+
+    let request = TestRequest::with_uri(&format!("/budget-detail/{}/", budget.id))
+        .jwt_auth(user.id)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    assert_eq!(json!(budget.id), response_body["id"]);
+    assert_eq!(json!("Food"), response_body["name"]);
+}
+
+#[actix_rt::test]
+async fn show_budget_of_other_user() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let owner = session.create_user(UserBuilder::default().username("foo"));
+    let other_user = session.create_user(UserBuilder::default().username("bar"));
+    let budget = session.create_budget(BudgetBuilder::default().user_id(other_user.id).finish());
+
+    let request = TestRequest::with_uri(&format!("/budget-detail/{}/", budget.id))
+        .jwt_auth(owner.id)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::NOT_FOUND,
+        response.status(),
+        "wrong status code"
+    );
+}
+
+#[actix_rt::test]
+async fn update_happy_path() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    let budget = session.create_budget(BudgetBuilder::default().user_id(user.id).finish());
+
+    let request = TestRequest::with_uri(&format!("/budget-detail/{}/", budget.id))
+        .method(Method::PUT)
+        .jwt_auth(user.id)
+        .set_json(&budget_payload())
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let updated_budget = session.find_budget(budget.id);
+
+    assert_eq!("Food", updated_budget.name);
+    assert_eq!(BigDecimal::from(500), updated_budget.amount);
+    assert_eq!("INCL", updated_budget.tags_type);
+}
+
+#[actix_rt::test]
+async fn update_not_found() {
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+
+    let request = TestRequest::with_uri("/budget-detail/123/")
+        .method(Method::PUT)
+        .jwt_auth(user.id)
+        .set_json(&budget_payload())
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::NOT_FOUND,
+        response.status(),
+        "wrong status code"
+    );
+}
+
+#[actix_rt::test]
+async fn delete_happy_path() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    let budget = session.create_budget(BudgetBuilder::default().user_id(user.id).finish());
+
+    let request = TestRequest::with_uri(&format!("/budget-detail/{}/", budget.id))
+        .method(Method::DELETE)
+        .jwt_auth(user.id)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::NO_CONTENT,
+        response.status(),
+        "wrong status code"
+    );
+    assert_eq!(0, session.count_budgets());
+}
+
+#[actix_rt::test]
+async fn delete_not_found() {
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+
+    let request = TestRequest::with_uri("/budget-detail/123/")
+        .method(Method::DELETE)
+        .jwt_auth(user.id)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::NOT_FOUND,
+        response.status(),
+        "wrong status code"
+    );
+}
+
+#[actix_rt::test]
+async fn show_with_decimal_money_format() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    let budget = session.create_budget(
+        BudgetBuilder::default()
+            .user_id(user.id)
+            .name("Food")
+            .amount(100.0)
+            .currency("CAD")
+            .finish(),
+    );
+
+    let request = TestRequest::with_uri(&format!("/budget-detail/{}/", budget.id))
+        .jwt_auth(user.id)
+        .header("X-Money-Format", "decimal")
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    assert_eq!(
+        json!({"amount": "100.00", "currency": "CAD"}),
+        response_body["amount"]
+    );
+    assert_eq!(
+        json!({"amount": "0.00", "currency": "CAD"}),
+        response_body["spent"]
+    );
+    assert_eq!(
+        json!({"amount": "100.00", "currency": "CAD"}),
+        response_body["left"]
+    );
+    assert_eq!(None, response_body.get("amount_currency"));
+}
@@ -1,2 +1,14 @@
+pub mod amount;
 pub mod auth;
+pub mod budget;
+pub mod password;
+pub mod personal_token;
+pub mod profile;
 pub mod record;
+pub mod record_filters;
+pub mod record_import;
+pub mod registration;
+pub mod report;
+pub mod settings;
+pub mod tags;
+pub mod year_budget;
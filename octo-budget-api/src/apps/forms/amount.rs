@@ -0,0 +1,36 @@
+use bigdecimal::BigDecimal;
+use models::{currency, Money};
+
+/// Amounts are stored as `numeric(15, 2)`.
+const MAX_DECIMAL_PLACES: u32 = 2;
+const MAX_WHOLE_DIGITS: u32 = 13;
+
+pub fn currency_code(amount: &Money) -> Result<String, String> {
+    match currency::find(&amount.currency) {
+        Some(currency) => Ok(currency.code.to_string()),
+        None => Err(format!("\"{}\" is not a valid choice.", amount.currency)),
+    }
+}
+
+/// Rejects amounts the database would round or could not store: more decimal
+/// places than the currency has minor units, or too many whole digits.
+pub fn validate_digits(amount: &Money, errors: &mut Vec<String>) {
+    let decimal_places = currency::find(&amount.currency)
+        .map(|currency| currency.minor_units)
+        .unwrap_or(MAX_DECIMAL_PLACES)
+        .min(MAX_DECIMAL_PLACES);
+
+    if amount.amount.with_scale(i64::from(decimal_places)) != amount.amount {
+        errors.push(format!(
+            "Ensure that there are no more than {} decimal places.",
+            decimal_places
+        ));
+    }
+
+    if amount.amount.abs() >= BigDecimal::from(10u64.pow(MAX_WHOLE_DIGITS)) {
+        errors.push(format!(
+            "Ensure that there are no more than {} digits before the decimal point.",
+            MAX_WHOLE_DIGITS
+        ));
+    }
+}
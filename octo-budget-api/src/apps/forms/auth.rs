@@ -18,6 +18,14 @@ impl Form {
             _ => Err(ValidationErrors::bad_password()),
         }
     }
+
+    pub fn validate_active(user: &AuthUser) -> Result<(), ValidationErrors> {
+        if user.is_active {
+            Ok(())
+        } else {
+            Err(ValidationErrors::account_disabled())
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -66,6 +74,14 @@ impl ValidationErrors {
             password: vec![],
         }
     }
+
+    fn account_disabled() -> Self {
+        ValidationErrors {
+            non_field_errors: vec![ValidationError::AccountDisabled],
+            username: vec![],
+            password: vec![],
+        }
+    }
 }
 
 impl Form {
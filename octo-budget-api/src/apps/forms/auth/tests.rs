@@ -15,6 +15,18 @@ fn test_validate_password_err() {
     assert_eq!(ValidationErrors::bad_password(), result.unwrap_err());
 }
 
+#[test]
+fn test_validate_active() {
+    let mut user = make_user_with_pass("foo");
+    assert!(Form::validate_active(&user).is_ok());
+
+    user.is_active = false;
+    assert_eq!(
+        ValidationErrors::account_disabled(),
+        Form::validate_active(&user).unwrap_err()
+    );
+}
+
 fn make_user_with_pass(password: &'static str) -> AuthUser {
     use chrono::naive::NaiveDateTime;
 
@@ -26,10 +38,10 @@ fn make_user_with_pass(password: &'static str) -> AuthUser {
         is_active: true,
         is_superuser: true,
         first_name: "".to_string(),
+        last_login: None,
         last_name: "".to_string(),
         is_staff: false,
         date_joined: NaiveDateTime::from_timestamp(0, 0),
-        tags: Vec::new(),
     }
 }
 
@@ -0,0 +1,184 @@
+use actix_web::{error::ResponseError, HttpResponse};
+use bigdecimal::{BigDecimal, Zero};
+use chrono::NaiveDate;
+use failure::Fail;
+use models::Money;
+use serde::{Deserialize, Serialize};
+
+use super::amount::{currency_code, validate_digits};
+use crate::errors::ValidationError;
+
+const MAX_NAME_LENGTH: usize = 100;
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct Form {
+    name: Option<String>,
+    amount: Option<Money>,
+    start_date: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    tags_type: Option<String>,
+    comment: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct FormData {
+    pub name: String,
+    pub amount: BigDecimal,
+    pub amount_currency: String,
+    pub start_date: NaiveDate,
+    pub tags: Vec<String>,
+    pub tags_type: String,
+    pub comment: String,
+}
+
+#[derive(Debug, Fail, Serialize, Default)]
+pub struct ValidationErrors {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    name: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    amount: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    currency_code: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    start_date: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags_type: Vec<String>,
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl ResponseError for ValidationErrors {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().json(self)
+    }
+}
+
+impl ValidationErrors {
+    fn is_empty(&self) -> bool {
+        self.name.is_empty()
+            && self.amount.is_empty()
+            && self.currency_code.is_empty()
+            && self.start_date.is_empty()
+            && self.tags_type.is_empty()
+    }
+}
+
+impl Form {
+    pub fn validate(self) -> Result<FormData, ValidationErrors> {
+        let Self {
+            name,
+            amount,
+            start_date,
+            tags,
+            tags_type,
+            comment,
+        } = self;
+        let mut errors = ValidationErrors::default();
+
+        let name = validate_name(name, &mut errors.name);
+        let (amount_number, amount_currency) =
+            validate_amount(amount, &mut errors.amount, &mut errors.currency_code);
+
+        let start_date = match start_date {
+            None => {
+                errors
+                    .start_date
+                    .push(ValidationError::MustPresent.to_string());
+                NaiveDate::from_ymd(1970, 1, 1)
+            }
+            Some(val) => NaiveDate::parse_from_str(&val, DATE_FORMAT).unwrap_or_else(|_| {
+                errors.start_date.push(
+                    "Date has wrong format. Use one of these formats instead: YYYY-MM-DD."
+                        .to_string(),
+                );
+                NaiveDate::from_ymd(1970, 1, 1)
+            }),
+        };
+
+        let tags_type = validate_tags_type(tags_type, &mut errors.tags_type);
+
+        let comment = comment.unwrap_or_default();
+
+        if errors.is_empty() {
+            Ok(FormData {
+                name,
+                amount: amount_number,
+                amount_currency,
+                start_date,
+                tags,
+                tags_type,
+                comment,
+            })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+pub(super) fn validate_name(name: Option<String>, errors: &mut Vec<String>) -> String {
+    match name {
+        None => {
+            errors.push(ValidationError::MustPresent.to_string());
+            String::new()
+        }
+        Some(val) if val.is_empty() => {
+            errors.push(ValidationError::CannotBeBlank.to_string());
+            val
+        }
+        Some(val) if val.chars().count() > MAX_NAME_LENGTH => {
+            errors.push(format!(
+                "Ensure this field has no more than {} characters.",
+                MAX_NAME_LENGTH
+            ));
+            val
+        }
+        Some(val) => val,
+    }
+}
+
+/// The amount and the code of its currency.
+pub(super) fn validate_amount(
+    amount: Option<Money>,
+    errors: &mut Vec<String>,
+    currency_errors: &mut Vec<String>,
+) -> (BigDecimal, String) {
+    let amount = match amount {
+        None => {
+            errors.push(ValidationError::MustPresent.to_string());
+            return (BigDecimal::zero(), String::new());
+        }
+        Some(amount) => amount,
+    };
+
+    if amount.amount < BigDecimal::zero() {
+        errors.push("Ensure this value is greater than or equal to 0.".to_string());
+    }
+
+    validate_digits(&amount, errors);
+
+    let currency = currency_code(&amount).unwrap_or_else(|err| {
+        currency_errors.push(err);
+        String::new()
+    });
+
+    (amount.amount, currency)
+}
+
+pub(super) fn validate_tags_type(tags_type: Option<String>, errors: &mut Vec<String>) -> String {
+    match tags_type.as_deref() {
+        None => errors.push(ValidationError::MustPresent.to_string()),
+        Some("INCL") | Some("EXCL") | Some("ALL") => {}
+        Some(other) => errors.push(format!("\"{}\" is not a valid choice.", other)),
+    };
+
+    tags_type.unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,200 @@
+use super::*;
+use serde_json::{json, Value};
+
+fn make_form(params: Value) -> Form {
+    let mut form = json!({
+        "name": "Food",
+        "amount": {"amount": 500.5, "currency": {"code": "CAD", "name": "Canadian Dollar"}},
+        "start_date": "2020-03-01",
+        "tags": ["food"],
+        "tags_type": "INCL",
+    });
+
+    for (key, value) in params.as_object().unwrap() {
+        form[key] = value.clone();
+    }
+
+    serde_json::from_value(form).expect("Failed to deserialize form")
+}
+
+fn errors_json(form: Form) -> Value {
+    serde_json::to_value(form.validate().unwrap_err()).expect("Failed to convert to json")
+}
+
+#[test]
+fn test_validate_ok() {
+    let data = make_form(json!({}))
+        .validate()
+        .expect("is expected to be valid");
+
+    assert_eq!("Food", data.name);
+    assert_eq!(BigDecimal::from(500.5), data.amount);
+    assert_eq!("CAD", data.amount_currency);
+    assert_eq!(NaiveDate::from_ymd(2020, 3, 1), data.start_date);
+    assert_eq!(vec!["food"], data.tags);
+    assert_eq!("INCL", data.tags_type);
+    assert_eq!("", data.comment);
+}
+
+#[test]
+fn test_all_tags_types_are_valid() {
+    for tags_type in ["INCL", "EXCL", "ALL"].iter() {
+        let form = make_form(json!({ "tags_type": tags_type }));
+
+        assert!(
+            form.validate().is_ok(),
+            "{} is expected to be valid",
+            tags_type
+        );
+    }
+}
+
+#[test]
+fn test_invalid_tags_type() {
+    let form = make_form(json!({"tags_type": "FOO"}));
+
+    assert_eq!(
+        json!({"tags_type": ["\"FOO\" is not a valid choice."]}),
+        errors_json(form)
+    );
+}
+
+#[test]
+fn test_no_amount_and_tags_type() {
+    let form = make_form(json!({ "amount": null, "tags_type": null }));
+
+    assert_eq!(
+        json!({
+            "amount": ["This field is required."],
+            "tags_type": ["This field is required."],
+        }),
+        errors_json(form)
+    );
+}
+
+#[test]
+fn test_no_name() {
+    let form = make_form(json!({ "name": null }));
+
+    assert_eq!(
+        json!({"name": ["This field is required."]}),
+        errors_json(form)
+    );
+}
+
+#[test]
+fn test_blank_name() {
+    let form = make_form(json!({ "name": "" }));
+
+    assert_eq!(
+        json!({"name": ["This field may not be blank."]}),
+        errors_json(form)
+    );
+}
+
+#[test]
+fn test_too_long_name() {
+    let form = make_form(json!({ "name": "x".repeat(101) }));
+
+    assert_eq!(
+        json!({"name": ["Ensure this field has no more than 100 characters."]}),
+        errors_json(form)
+    );
+}
+
+#[test]
+fn test_negative_amount() {
+    let form = make_form(json!({
+        "amount": {"amount": -1, "currency": {"code": "CAD", "name": "Canadian Dollar"}},
+    }));
+
+    assert_eq!(
+        json!({"amount": ["Ensure this value is greater than or equal to 0."]}),
+        errors_json(form)
+    );
+}
+
+#[test]
+fn test_too_many_decimal_places() {
+    for (amount, currency, places) in [
+        ("12.345", "CAD", 2),
+        ("12.5", "JPY", 0),
+        ("1.125", "KWD", 2),
+    ]
+    .iter()
+    {
+        let form = make_form(json!({
+            "amount": {"amount": amount, "currency": currency},
+        }));
+
+        assert_eq!(
+            json!({
+                "amount": [format!("Ensure that there are no more than {} decimal places.", places)]
+            }),
+            errors_json(form)
+        );
+    }
+
+    let form = make_form(json!({"amount": {"amount": "12.340", "currency": "CAD"}}));
+    assert!(form.validate().is_ok(), "trailing zeros are fine");
+}
+
+#[test]
+fn test_too_large_amount() {
+    let form = make_form(json!({
+        "amount": {"amount": "10000000000000", "currency": "CAD"},
+    }));
+
+    assert_eq!(
+        json!({"amount": ["Ensure that there are no more than 13 digits before the decimal point."]}),
+        errors_json(form)
+    );
+
+    let form = make_form(json!({"amount": {"amount": "9999999999999.99", "currency": "CAD"}}));
+    assert!(form.validate().is_ok());
+}
+
+#[test]
+fn test_invalid_currency() {
+    let form = make_form(json!({
+        "amount": {"amount": 1, "currency": {"code": "XXX", "name": "Foo"}},
+    }));
+
+    assert_eq!(
+        json!({"currency_code": ["\"XXX\" is not a valid choice."]}),
+        errors_json(form)
+    );
+}
+
+#[test]
+fn test_no_start_date() {
+    let form = make_form(json!({ "start_date": null }));
+
+    assert_eq!(
+        json!({"start_date": ["This field is required."]}),
+        errors_json(form)
+    );
+}
+
+#[test]
+fn test_invalid_start_date() {
+    let form = make_form(json!({ "start_date": "01/03/2020" }));
+
+    assert_eq!(
+        json!({
+            "start_date": ["Date has wrong format. Use one of these formats instead: YYYY-MM-DD."]
+        }),
+        errors_json(form)
+    );
+}
+
+#[test]
+fn test_other_currency() {
+    let form = make_form(json!({
+        "amount": {"amount": 1, "currency": {"code": "USD", "name": "US Dollar"}},
+    }));
+
+    let data = form.validate().expect("is expected to be valid");
+
+    assert_eq!("USD", data.amount_currency);
+}
@@ -0,0 +1,145 @@
+use actix_web::{error::ResponseError, HttpResponse};
+use failure::Fail;
+use serde::{Deserialize, Serialize};
+
+use super::registration::{required, validate_password};
+use crate::db::models::AuthUser;
+
+#[derive(Deserialize, Debug, Default)]
+pub struct ChangeForm {
+    old_password: Option<String>,
+    new_password: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ChangeData {
+    pub old_password: String,
+    pub new_password: String,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct ResetForm {
+    email: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct ResetConfirmForm {
+    token: Option<String>,
+    new_password: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ResetConfirmData {
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Fail, Serialize, Default, PartialEq)]
+pub struct ValidationErrors {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    old_password: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    new_password: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    email: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    token: Vec<String>,
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl ResponseError for ValidationErrors {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().json(self)
+    }
+}
+
+impl ValidationErrors {
+    fn is_empty(&self) -> bool {
+        self.old_password.is_empty()
+            && self.new_password.is_empty()
+            && self.email.is_empty()
+            && self.token.is_empty()
+    }
+
+    pub fn invalid_token() -> Self {
+        Self {
+            token: vec!["Invalid or expired token.".to_string()],
+            ..Default::default()
+        }
+    }
+}
+
+impl ChangeForm {
+    pub fn validate(self) -> Result<ChangeData, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        let old_password = required(self.old_password, &mut errors.old_password);
+        let new_password = required(self.new_password, &mut errors.new_password);
+
+        match (old_password, new_password) {
+            (Some(old_password), Some(new_password)) if errors.is_empty() => Ok(ChangeData {
+                old_password,
+                new_password,
+            }),
+            _ => Err(errors),
+        }
+    }
+
+    pub fn validate_old_password(user: &AuthUser, password: &str) -> Result<(), ValidationErrors> {
+        match djangohashers::check_password(password, &user.password) {
+            Ok(true) => Ok(()),
+            _ => Err(ValidationErrors {
+                old_password: vec![
+                    "Your old password was entered incorrectly. Please enter it again.".to_string(),
+                ],
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+impl ResetForm {
+    pub fn validate(self) -> Result<String, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        required(self.email, &mut errors.email).ok_or(errors)
+    }
+}
+
+impl ResetConfirmForm {
+    pub fn validate(self) -> Result<ResetConfirmData, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        let token = required(self.token, &mut errors.token);
+        let new_password = required(self.new_password, &mut errors.new_password);
+
+        match (token, new_password) {
+            (Some(token), Some(new_password)) if errors.is_empty() => Ok(ResetConfirmData {
+                token,
+                new_password,
+            }),
+            _ => Err(errors),
+        }
+    }
+}
+
+/// Same rules as for registration.
+pub fn validate_new_password(user: &AuthUser, password: &str) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::default();
+
+    validate_password(password, Some(&user.username), &mut errors.new_password);
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests;
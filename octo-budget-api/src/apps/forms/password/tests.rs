@@ -0,0 +1,68 @@
+use super::*;
+use crate::db::builders::UserBuilder;
+use serde_json::{json, Value};
+
+fn to_json(errors: ValidationErrors) -> Value {
+    serde_json::to_value(errors).unwrap()
+}
+
+#[test]
+fn change_form_requires_both_passwords() {
+    let form = ChangeForm {
+        old_password: None,
+        new_password: Some(" ".to_string()),
+    };
+
+    assert_eq!(
+        json!({
+            "old_password": ["This field is required."],
+            "new_password": ["This field may not be blank."],
+        }),
+        to_json(form.validate().unwrap_err())
+    );
+}
+
+#[test]
+fn wrong_old_password() {
+    let mut user = UserBuilder::default().finish();
+    user.password = djangohashers::make_password("foo");
+
+    assert!(ChangeForm::validate_old_password(&user, "foo").is_ok());
+    assert_eq!(
+        json!({
+            "old_password": ["Your old password was entered incorrectly. Please enter it again."],
+        }),
+        to_json(ChangeForm::validate_old_password(&user, "bar").unwrap_err())
+    );
+}
+
+#[test]
+fn reset_form_requires_email() {
+    assert_eq!(
+        json!({ "email": ["This field is required."] }),
+        to_json(ResetForm::default().validate().unwrap_err())
+    );
+}
+
+#[test]
+fn reset_confirm_form() {
+    let form = ResetConfirmForm {
+        token: Some("token".to_string()),
+        new_password: Some("correct horse".to_string()),
+    };
+    let data = form.validate().expect("is expected to be valid");
+
+    assert_eq!("token", data.token);
+    assert_eq!("correct horse", data.new_password);
+}
+
+#[test]
+fn new_password_is_validated_against_username() {
+    let user = UserBuilder::default().username("johndoe").finish();
+
+    assert!(validate_new_password(&user, "correct horse").is_ok());
+    assert_eq!(
+        json!({ "new_password": ["The password is too similar to the username."] }),
+        to_json(validate_new_password(&user, "johndoe2020").unwrap_err())
+    );
+}
@@ -0,0 +1,83 @@
+use actix_web::{error::ResponseError, HttpResponse};
+use failure::Fail;
+use serde::{Deserialize, Serialize};
+
+use super::registration::required;
+use crate::db::personal_tokens::{self, READ};
+
+const MAX_NAME_LENGTH: usize = 100;
+
+#[derive(Deserialize, Debug, Default)]
+pub struct Form {
+    name: Option<String>,
+    scope: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Data {
+    pub name: String,
+    pub scope: String,
+}
+
+#[derive(Debug, Fail, Serialize, Default, PartialEq)]
+pub struct ValidationErrors {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    name: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    scope: Vec<String>,
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl ResponseError for ValidationErrors {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().json(self)
+    }
+}
+
+impl ValidationErrors {
+    fn is_empty(&self) -> bool {
+        self.name.is_empty() && self.scope.is_empty()
+    }
+}
+
+impl Form {
+    /// Tokens are read-only unless asked otherwise.
+    pub fn validate(self) -> Result<Data, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        let name = required(self.name, &mut errors.name)
+            .map(|name| name.trim().to_string())
+            .unwrap_or_default();
+        if name.chars().count() > MAX_NAME_LENGTH {
+            errors.name.push(format!(
+                "Ensure this field has no more than {} characters.",
+                MAX_NAME_LENGTH
+            ));
+        }
+
+        let scope = match self.scope.as_deref() {
+            None | Some("") => READ.to_string(),
+            Some(val) if personal_tokens::scope(val).is_some() => val.to_string(),
+            Some(other) => {
+                errors
+                    .scope
+                    .push(format!("\"{}\" is not a valid choice.", other));
+                String::new()
+            }
+        };
+
+        if errors.is_empty() {
+            Ok(Data { name, scope })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,57 @@
+use super::*;
+use serde_json::{json, Value};
+
+fn make_form(params: Value) -> Form {
+    serde_json::from_value(params).expect("Failed to deserialize form")
+}
+
+#[test]
+fn read_only_by_default() {
+    let data = make_form(json!({"name": " import script "}))
+        .validate()
+        .unwrap();
+
+    assert_eq!(
+        Data {
+            name: "import script".to_string(),
+            scope: "read".to_string(),
+        },
+        data
+    );
+}
+
+#[test]
+fn read_write_scope() {
+    let data = make_form(json!({"name": "import", "scope": "read_write"}))
+        .validate()
+        .unwrap();
+
+    assert_eq!("read_write", data.scope);
+}
+
+#[test]
+fn invalid_form() {
+    let errors = make_form(json!({"name": " ", "scope": "admin"}))
+        .validate()
+        .unwrap_err();
+
+    assert_eq!(
+        json!({
+            "name": ["This field may not be blank."],
+            "scope": ["\"admin\" is not a valid choice."],
+        }),
+        serde_json::to_value(errors).unwrap()
+    );
+}
+
+#[test]
+fn too_long_name() {
+    let errors = make_form(json!({"name": "a".repeat(101)}))
+        .validate()
+        .unwrap_err();
+
+    assert_eq!(
+        json!({"name": ["Ensure this field has no more than 100 characters."]}),
+        serde_json::to_value(errors).unwrap()
+    );
+}
@@ -0,0 +1,101 @@
+use actix_web::{error::ResponseError, HttpResponse};
+use failure::Fail;
+use serde::{Deserialize, Serialize};
+
+use super::registration::validate_email;
+use crate::errors::ValidationError;
+
+const MAX_NAME_LENGTH: usize = 150;
+
+/// Partial update, fields which are not sent are left as they are.
+#[derive(Deserialize, Debug, Default)]
+pub struct Form {
+    email: Option<String>,
+    first_name: Option<String>,
+    last_name: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Data {
+    pub email: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+}
+
+#[derive(Debug, Fail, Serialize, Default, PartialEq)]
+pub struct ValidationErrors {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    email: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    first_name: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    last_name: Vec<String>,
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl ResponseError for ValidationErrors {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().json(self)
+    }
+}
+
+impl ValidationErrors {
+    fn is_empty(&self) -> bool {
+        self.email.is_empty() && self.first_name.is_empty() && self.last_name.is_empty()
+    }
+}
+
+impl Form {
+    pub fn validate(self) -> Result<Data, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        let email = self.email.map(|email| email.trim().to_string());
+        if let Some(email) = &email {
+            if email.is_empty() {
+                errors
+                    .email
+                    .push(ValidationError::CannotBeBlank.to_string());
+            } else {
+                validate_email(email, &mut errors.email);
+            }
+        }
+
+        let first_name = self.first_name.map(|name| name.trim().to_string());
+        if let Some(name) = &first_name {
+            validate_name(name, &mut errors.first_name);
+        }
+
+        let last_name = self.last_name.map(|name| name.trim().to_string());
+        if let Some(name) = &last_name {
+            validate_name(name, &mut errors.last_name);
+        }
+
+        if errors.is_empty() {
+            Ok(Data {
+                email,
+                first_name,
+                last_name,
+            })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+// names are optional in Django, so they can be blank
+fn validate_name(name: &str, errors: &mut Vec<String>) {
+    if name.chars().count() > MAX_NAME_LENGTH {
+        errors.push(format!(
+            "Ensure this field has no more than {} characters.",
+            MAX_NAME_LENGTH
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,61 @@
+use super::*;
+use serde_json::{json, Value};
+
+fn make_form(params: Value) -> Form {
+    serde_json::from_value(params).expect("Failed to deserialize form")
+}
+
+#[test]
+fn empty_form_changes_nothing() {
+    let data = make_form(json!({})).validate().unwrap();
+
+    assert_eq!(Data::default(), data);
+}
+
+#[test]
+fn valid_form() {
+    let data = make_form(json!({
+        "email": " john@example.com ",
+        "first_name": "John",
+        "last_name": "",
+    }))
+    .validate()
+    .unwrap();
+
+    assert_eq!(
+        Data {
+            email: Some("john@example.com".to_string()),
+            first_name: Some("John".to_string()),
+            last_name: Some("".to_string()),
+        },
+        data
+    );
+}
+
+#[test]
+fn invalid_form() {
+    let errors = make_form(json!({
+        "email": "john",
+        "first_name": "x".repeat(151),
+    }))
+    .validate()
+    .unwrap_err();
+
+    assert_eq!(
+        json!({
+            "email": ["Enter a valid email address."],
+            "first_name": ["Ensure this field has no more than 150 characters."],
+        }),
+        serde_json::to_value(errors).unwrap()
+    );
+}
+
+#[test]
+fn blank_email() {
+    let errors = make_form(json!({ "email": " " })).validate().unwrap_err();
+
+    assert_eq!(
+        json!({ "email": ["This field may not be blank."] }),
+        serde_json::to_value(errors).unwrap()
+    );
+}
@@ -1,29 +1,20 @@
 use actix_web::{error::ResponseError, HttpResponse};
-use bigdecimal::{BigDecimal, FromPrimitive, Zero};
+use bigdecimal::BigDecimal;
 use failure::Fail;
+use models::Money;
 use serde::{Deserialize, Serialize};
 
+use super::amount::{currency_code, validate_digits};
+
 #[derive(Deserialize, Debug, Default, Clone)]
 pub struct Form {
     tags: Vec<String>,
     transaction_type: String,
-    amount: Amount,
+    amount: Money,
     comment: Option<String>,
 }
 
-#[derive(Deserialize, Debug, Default, Clone)]
-pub struct Amount {
-    amount: f64,
-    currency: Currency,
-}
-
-#[derive(Deserialize, Debug, Default, Clone)]
-pub struct Currency {
-    code: String,
-    name: String,
-}
-
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Serialize)]
 pub struct FormData {
     pub transaction_type: String,
     pub tags: Vec<String>,
@@ -61,6 +52,20 @@ impl ValidationErrors {
 }
 
 impl Form {
+    pub fn new(
+        transaction_type: String,
+        tags: Vec<String>,
+        amount: Money,
+        comment: Option<String>,
+    ) -> Self {
+        Self {
+            tags,
+            transaction_type,
+            amount,
+            comment,
+        }
+    }
+
     // TODO: validate comment field
     pub fn validate(self) -> Result<FormData, ValidationErrors> {
         let Self {
@@ -78,22 +83,12 @@ impl Form {
                 .push(format!("\"{}\" is not a valid choice.", other)),
         };
 
-        let amount_number = match BigDecimal::from_f64(amount.amount) {
-            Some(n) => n,
-            None => {
-                errors
-                    .amount
-                    .push(format!("Cannot parse a number from {}", amount.amount));
-                BigDecimal::zero()
-            }
-        };
+        validate_digits(&amount, &mut errors.amount);
 
-        match amount.currency.code.as_str() {
-            "CAD" => {}
-            other => errors
-                .currency_code
-                .push(format!("\"{}\" is not a valid choice.", other)),
-        };
+        let amount_currency = currency_code(&amount).unwrap_or_else(|err| {
+            errors.currency_code.push(err);
+            String::new()
+        });
 
         let comment = comment.unwrap_or_default();
 
@@ -102,11 +97,14 @@ impl Form {
                 transaction_type,
                 tags,
                 comment,
-                amount: amount_number,
-                amount_currency: amount.currency.code,
+                amount: amount.amount,
+                amount_currency,
             })
         } else {
             Err(errors)
         }
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,81 @@
+use super::*;
+use serde_json::{json, Value};
+
+fn make_form(params: Value) -> Form {
+    let mut form = json!({
+        "tags": ["food"],
+        "transaction_type": "EXP",
+        "amount": {"amount": "12.5", "currency": "CAD"},
+        "comment": "lunch",
+    });
+
+    for (key, value) in params.as_object().unwrap() {
+        form[key] = value.clone();
+    }
+
+    serde_json::from_value(form).expect("Failed to deserialize form")
+}
+
+fn errors_json(form: Form) -> Value {
+    serde_json::to_value(form.validate().unwrap_err()).expect("Failed to convert to json")
+}
+
+#[test]
+fn test_validate_ok() {
+    let data = make_form(json!({}))
+        .validate()
+        .expect("is expected to be valid");
+
+    assert_eq!("EXP", data.transaction_type);
+    assert_eq!(vec!["food"], data.tags);
+    assert_eq!(BigDecimal::from(12.5), data.amount);
+    assert_eq!("CAD", data.amount_currency);
+    assert_eq!("lunch", data.comment);
+}
+
+#[test]
+fn test_invalid_transaction_type_and_currency() {
+    let form = make_form(json!({
+        "transaction_type": "FOO",
+        "amount": {"amount": "1", "currency": "XYZ"},
+    }));
+
+    assert_eq!(
+        json!({
+            "transaction_type": ["\"FOO\" is not a valid choice."],
+            "currency_code": ["\"XYZ\" is not a valid choice."],
+        }),
+        errors_json(form)
+    );
+}
+
+#[test]
+fn test_too_many_decimal_places() {
+    for (amount, currency, places) in [("12.345", "CAD", 2), ("12.5", "JPY", 0)].iter() {
+        let form = make_form(json!({
+            "amount": {"amount": amount, "currency": currency},
+        }));
+
+        assert_eq!(
+            json!({
+                "amount": [format!("Ensure that there are no more than {} decimal places.", places)]
+            }),
+            errors_json(form)
+        );
+    }
+}
+
+#[test]
+fn test_too_large_amount() {
+    let form = make_form(json!({
+        "amount": {"amount": "-10000000000000", "currency": "CAD"},
+    }));
+
+    assert_eq!(
+        json!({"amount": ["Ensure that there are no more than 13 digits before the decimal point."]}),
+        errors_json(form)
+    );
+
+    let form = make_form(json!({"amount": {"amount": "9999999999999.99", "currency": "CAD"}}));
+    assert!(form.validate().is_ok());
+}
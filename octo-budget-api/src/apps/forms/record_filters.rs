@@ -0,0 +1,185 @@
+use actix_web::{error::ResponseError, HttpResponse};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use failure::Fail;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Filters of the records index and export, read from the same query string
+/// as the pagination.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct Params {
+    date_from: Option<String>,
+    date_to: Option<String>,
+    transaction_type: Option<String>,
+    /// comma separated list of tags
+    tags: Option<String>,
+    /// `any` (default) or `all`
+    tags_match: Option<String>,
+    amount_min: Option<String>,
+    amount_max: Option<String>,
+    search: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum TagsMatch {
+    #[default]
+    Any,
+    All,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Filters {
+    pub date_from: Option<NaiveDate>,
+    pub date_to: Option<NaiveDate>,
+    pub transaction_type: Option<String>,
+    pub tags: Vec<String>,
+    pub tags_match: TagsMatch,
+    pub amount_min: Option<BigDecimal>,
+    pub amount_max: Option<BigDecimal>,
+    pub search: Option<String>,
+}
+
+/// Errors are keyed by the parameter name, a field per filter would make the
+/// error larger than the result.
+#[derive(Debug, Fail, Serialize, Default)]
+#[serde(transparent)]
+pub struct ValidationErrors(BTreeMap<&'static str, Vec<String>>);
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl ResponseError for ValidationErrors {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().json(self)
+    }
+}
+
+impl ValidationErrors {
+    fn add(&mut self, field: &'static str, message: String) {
+        self.0.entry(field).or_default().push(message);
+    }
+}
+
+impl Params {
+    pub fn validate(&self) -> Result<Filters, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        let date_from = parse_date("date_from", &self.date_from, &mut errors);
+        let date_to = parse_date("date_to", &self.date_to, &mut errors);
+
+        if let (Some(from), Some(to)) = (date_from, date_to) {
+            if from > to {
+                errors.add("date_to", "Must not be earlier than date_from.".to_string());
+            }
+        }
+
+        let transaction_type = match self.transaction_type.as_deref() {
+            None | Some("") => None,
+            Some(val @ "EXP") | Some(val @ "INC") => Some(val.to_string()),
+            Some(other) => {
+                errors.add(
+                    "transaction_type",
+                    format!("\"{}\" is not a valid choice.", other),
+                );
+                None
+            }
+        };
+
+        let tags = self
+            .tags
+            .as_deref()
+            .unwrap_or("")
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(String::from)
+            .collect();
+
+        let tags_match = match self.tags_match.as_deref() {
+            None | Some("") | Some("any") => TagsMatch::Any,
+            Some("all") => TagsMatch::All,
+            Some(other) => {
+                errors.add(
+                    "tags_match",
+                    format!("\"{}\" is not a valid choice.", other),
+                );
+                TagsMatch::Any
+            }
+        };
+
+        let amount_min = parse_amount("amount_min", &self.amount_min, &mut errors);
+        let amount_max = parse_amount("amount_max", &self.amount_max, &mut errors);
+
+        if let (Some(min), Some(max)) = (&amount_min, &amount_max) {
+            if min > max {
+                errors.add(
+                    "amount_max",
+                    "Must not be less than amount_min.".to_string(),
+                );
+            }
+        }
+
+        let search = self
+            .search
+            .as_ref()
+            .map(|val| val.trim().to_string())
+            .filter(|val| !val.is_empty());
+
+        if !errors.0.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(Filters {
+            date_from,
+            date_to,
+            transaction_type,
+            tags,
+            tags_match,
+            amount_min,
+            amount_max,
+            search,
+        })
+    }
+}
+
+fn parse_date(
+    field: &'static str,
+    value: &Option<String>,
+    errors: &mut ValidationErrors,
+) -> Option<NaiveDate> {
+    match value.as_deref() {
+        None | Some("") => None,
+        Some(val) => NaiveDate::parse_from_str(val, DATE_FORMAT)
+            .map_err(|_| {
+                errors.add(
+                    field,
+                    "Date has wrong format. Use one of these formats instead: YYYY-MM-DD."
+                        .to_string(),
+                )
+            })
+            .ok(),
+    }
+}
+
+fn parse_amount(
+    field: &'static str,
+    value: &Option<String>,
+    errors: &mut ValidationErrors,
+) -> Option<BigDecimal> {
+    match value.as_deref() {
+        None | Some("") => None,
+        Some(val) => BigDecimal::from_str(val)
+            .map_err(|_| errors.add(field, "A valid number is required.".to_string()))
+            .ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests;
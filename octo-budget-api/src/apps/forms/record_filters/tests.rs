@@ -0,0 +1,93 @@
+use super::*;
+use serde_json::{json, Value};
+
+fn make_params(params: Value) -> Params {
+    serde_json::from_value(params).expect("Failed to deserialize params")
+}
+
+fn errors_value(params: Params) -> Value {
+    serde_json::to_value(params.validate().unwrap_err()).expect("Failed to convert to json")
+}
+
+#[test]
+fn filters_are_empty_by_default() {
+    let filters = make_params(json!({}))
+        .validate()
+        .expect("is expected to be valid");
+
+    assert_eq!(Filters::default(), filters);
+}
+
+#[test]
+fn pagination_is_ignored() {
+    let filters = make_params(json!({ "page": 3, "per_page": 10 }))
+        .validate()
+        .expect("is expected to be valid");
+
+    assert_eq!(Filters::default(), filters);
+}
+
+#[test]
+fn filters_are_parsed_when_valid() {
+    let params = make_params(json!({
+        "date_from": "2020-03-01",
+        "date_to": "2020-03-31",
+        "transaction_type": "EXP",
+        "tags": "food, groceries,,",
+        "tags_match": "all",
+        "amount_min": "1.5",
+        "amount_max": "100",
+        "search": " Milk ",
+    }));
+    let filters = params.validate().expect("is expected to be valid");
+
+    assert_eq!(Some(NaiveDate::from_ymd(2020, 3, 1)), filters.date_from);
+    assert_eq!(Some(NaiveDate::from_ymd(2020, 3, 31)), filters.date_to);
+    assert_eq!(Some("EXP".to_string()), filters.transaction_type);
+    assert_eq!(vec!["food", "groceries"], filters.tags);
+    assert_eq!(TagsMatch::All, filters.tags_match);
+    assert_eq!(
+        Some(BigDecimal::from_str("1.5").unwrap()),
+        filters.amount_min
+    );
+    assert_eq!(Some(BigDecimal::from(100)), filters.amount_max);
+    assert_eq!(Some("Milk".to_string()), filters.search);
+}
+
+#[test]
+fn invalid_filters() {
+    let params = make_params(json!({
+        "date_from": "yesterday",
+        "transaction_type": "FOO",
+        "tags_match": "some",
+        "amount_min": "ten",
+    }));
+
+    assert_eq!(
+        json!({
+            "date_from": ["Date has wrong format. Use one of these formats instead: YYYY-MM-DD."],
+            "transaction_type": ["\"FOO\" is not a valid choice."],
+            "tags_match": ["\"some\" is not a valid choice."],
+            "amount_min": ["A valid number is required."],
+        }),
+        errors_value(params)
+    );
+}
+
+#[test]
+fn invalid_ranges() {
+    let params = make_params(json!({
+        "date_from": "2020-03-31",
+        "date_to": "2020-03-01",
+        "amount_min": "10",
+        "amount_max": "1",
+    }));
+
+    assert_eq!(
+        json!({
+            "date_to": ["Must not be earlier than date_from."],
+            "amount_max": ["Must not be less than amount_min."],
+        }),
+        errors_value(params)
+    );
+}
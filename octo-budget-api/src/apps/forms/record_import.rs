@@ -0,0 +1,308 @@
+use std::str::FromStr;
+
+use actix_web::{error::ResponseError, HttpResponse};
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{NaiveDate, NaiveDateTime};
+use failure::Fail;
+use models::Money;
+use serde::{Deserialize, Serialize};
+
+use super::record::{self, FormData};
+use crate::db::calendar::Calendar;
+use crate::errors::ValidationError;
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+const DEFAULT_CURRENCY: &str = "CAD";
+
+/// Column mapping sent along with the CSV file as multipart text fields.
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct Mapping {
+    date_column: Option<String>,
+    date_format: Option<String>,
+    amount_column: Option<String>,
+    /// `negative_expense` (default) or `positive_expense`
+    amount_sign: Option<String>,
+    description_column: Option<String>,
+    /// comma separated list of tags added to every record
+    tags: Option<String>,
+    currency: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct Settings {
+    pub date_column: String,
+    pub date_format: String,
+    pub amount_column: String,
+    pub negative_expense: bool,
+    pub description_column: Option<String>,
+    pub tags: Vec<String>,
+    pub currency: String,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct ImportParams {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Fail, Serialize, Default)]
+pub struct ValidationErrors {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub file: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    date_column: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    amount_column: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    amount_sign: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    description_column: Vec<String>,
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl ResponseError for ValidationErrors {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().json(self)
+    }
+}
+
+impl ValidationErrors {
+    pub fn file(message: &str) -> Self {
+        Self {
+            file: vec![message.to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.file.is_empty()
+            && self.date_column.is_empty()
+            && self.amount_column.is_empty()
+            && self.amount_sign.is_empty()
+            && self.description_column.is_empty()
+    }
+}
+
+/// Errors of a single CSV row.
+#[derive(Debug, Serialize, Default)]
+pub struct RowErrors {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    date: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    amount: Vec<String>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    record: Option<record::ValidationErrors>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RowError {
+    pub line: u64,
+    pub errors: RowErrors,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Row {
+    pub line: u64,
+    /// UTC, dates of the file are in the user's timezone
+    pub created_at: NaiveDateTime,
+    #[serde(flatten)]
+    pub data: FormData,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct ParsedRows {
+    pub rows: Vec<Row>,
+    pub errors: Vec<RowError>,
+}
+
+impl Mapping {
+    pub fn validate(self) -> Result<Settings, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        let date_column = required(self.date_column, &mut errors.date_column);
+        let amount_column = required(self.amount_column, &mut errors.amount_column);
+
+        let negative_expense = match self.amount_sign.as_deref() {
+            None | Some("") | Some("negative_expense") => true,
+            Some("positive_expense") => false,
+            Some(other) => {
+                errors
+                    .amount_sign
+                    .push(format!("\"{}\" is not a valid choice.", other));
+                true
+            }
+        };
+
+        let tags = self
+            .tags
+            .as_deref()
+            .unwrap_or("")
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(String::from)
+            .collect();
+
+        if errors.is_empty() {
+            Ok(Settings {
+                date_column,
+                date_format: non_blank(self.date_format)
+                    .unwrap_or_else(|| DEFAULT_DATE_FORMAT.to_string()),
+                amount_column,
+                negative_expense,
+                description_column: non_blank(self.description_column),
+                tags,
+                currency: non_blank(self.currency).unwrap_or_else(|| DEFAULT_CURRENCY.to_string()),
+            })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn non_blank(value: Option<String>) -> Option<String> {
+    value.filter(|val| !val.trim().is_empty())
+}
+
+fn required(value: Option<String>, errors: &mut Vec<String>) -> String {
+    match non_blank(value) {
+        Some(val) => val,
+        None => {
+            errors.push(ValidationError::MustPresent.to_string());
+            String::new()
+        }
+    }
+}
+
+impl Settings {
+    /// Parses CSV `content` (with a header row) into records. Every row goes
+    /// through `record::Form::validate`, invalid rows are collected as errors.
+    /// Dates without time are the start of the day in the user's timezone.
+    pub fn parse(
+        &self,
+        content: &[u8],
+        calendar: &Calendar,
+    ) -> Result<ParsedRows, ValidationErrors> {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(content);
+
+        let headers = reader
+            .headers()
+            .map_err(|err| ValidationErrors::file(&err.to_string()))?
+            .clone();
+
+        let mut errors = ValidationErrors::default();
+        let date_idx = column_index(&headers, &self.date_column, &mut errors.date_column);
+        let amount_idx = column_index(&headers, &self.amount_column, &mut errors.amount_column);
+        let description_idx = self
+            .description_column
+            .as_ref()
+            .map(|column| column_index(&headers, column, &mut errors.description_column));
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mut result = ParsedRows::default();
+
+        for record in reader.records() {
+            let record = record.map_err(|err| ValidationErrors::file(&err.to_string()))?;
+            let line = record.position().map(|pos| pos.line()).unwrap_or_default();
+
+            let get = |idx: usize| record.get(idx).unwrap_or("");
+            let description = description_idx
+                .map(|idx| get(idx).to_string())
+                .filter(|val| !val.is_empty());
+
+            match self.parse_row(get(date_idx), get(amount_idx), description, calendar) {
+                Ok((created_at, data)) => result.rows.push(Row {
+                    line,
+                    created_at,
+                    data,
+                }),
+                Err(errors) => result.errors.push(RowError { line, errors }),
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn parse_row(
+        &self,
+        date: &str,
+        amount: &str,
+        description: Option<String>,
+        calendar: &Calendar,
+    ) -> Result<(NaiveDateTime, FormData), RowErrors> {
+        let mut errors = RowErrors::default();
+
+        let created_at = NaiveDateTime::parse_from_str(date, &self.date_format)
+            .map(|local| calendar.to_utc(local))
+            .or_else(|_| {
+                NaiveDate::parse_from_str(date, &self.date_format)
+                    .map(|day| calendar.start_of_day(day))
+            })
+            .unwrap_or_else(|_| {
+                errors.date.push(format!(
+                    "Date \"{}\" does not match format \"{}\".",
+                    date, self.date_format
+                ));
+                NaiveDateTime::from_timestamp(0, 0)
+            });
+
+        let amount = BigDecimal::from_str(amount).unwrap_or_else(|_| {
+            errors
+                .amount
+                .push("A valid number is required.".to_string());
+            BigDecimal::zero()
+        });
+
+        let transaction_type = if (amount < BigDecimal::zero()) == self.negative_expense {
+            "EXP"
+        } else {
+            "INC"
+        };
+
+        let form = record::Form::new(
+            transaction_type.to_string(),
+            self.tags.clone(),
+            Money {
+                amount: amount.abs(),
+                currency: self.currency.clone(),
+            },
+            description,
+        );
+
+        let data = form
+            .validate()
+            .map_err(|err| errors.record = Some(err))
+            .ok();
+
+        match data {
+            Some(data) if errors.date.is_empty() && errors.amount.is_empty() => {
+                Ok((created_at, data))
+            }
+            _ => Err(errors),
+        }
+    }
+}
+
+fn column_index(headers: &csv::StringRecord, column: &str, errors: &mut Vec<String>) -> usize {
+    headers
+        .iter()
+        .position(|header| header == column)
+        .unwrap_or_else(|| {
+            errors.push(format!("Column \"{}\" is not found in the file.", column));
+            0
+        })
+}
+
+#[cfg(test)]
+mod tests;
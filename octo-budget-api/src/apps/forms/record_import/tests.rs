@@ -0,0 +1,156 @@
+use super::*;
+use crate::db::models::UserSettings;
+use bigdecimal::BigDecimal;
+use serde_json::{json, Value};
+
+const STATEMENT: &str = "\
+Date,Description,Amount
+2020-03-01,Grocery store,-45.10
+2020-03-02,Salary,1000
+yesterday,Coffee,-3
+2020-03-04,Bus,abc
+2020-03-05,Rent,-1200.505
+";
+
+fn settings(mapping: Value) -> Settings {
+    let mut params = json!({
+        "date_column": "Date",
+        "amount_column": "Amount",
+        "description_column": "Description",
+        "tags": "imported, bank",
+    });
+
+    for (key, value) in mapping.as_object().unwrap() {
+        params[key] = value.clone();
+    }
+
+    serde_json::from_value::<Mapping>(params)
+        .expect("Failed to deserialize mapping")
+        .validate()
+        .expect("Mapping is expected to be valid")
+}
+
+fn calendar(timezone: &str) -> Calendar {
+    Calendar::new(&UserSettings {
+        timezone: timezone.to_string(),
+        ..UserSettings::defaults(1)
+    })
+}
+
+#[test]
+fn mapping_requires_columns() {
+    let errors = Mapping::default().validate().unwrap_err();
+
+    assert_eq!(
+        json!({
+            "date_column": ["This field is required."],
+            "amount_column": ["This field is required."],
+        }),
+        serde_json::to_value(errors).unwrap()
+    );
+}
+
+#[test]
+fn mapping_defaults() {
+    let settings = settings(json!({ "tags": null, "description_column": null }));
+
+    assert_eq!("%Y-%m-%d", settings.date_format);
+    assert_eq!("CAD", settings.currency);
+    assert!(settings.negative_expense);
+    assert!(settings.tags.is_empty());
+    assert_eq!(None, settings.description_column);
+}
+
+#[test]
+fn parse_valid_and_invalid_rows() {
+    let parsed = settings(json!({}))
+        .parse(STATEMENT.as_bytes(), &calendar("UTC"))
+        .expect("Failed to parse");
+
+    assert_eq!(2, parsed.rows.len());
+
+    let groceries = &parsed.rows[0];
+    assert_eq!(2, groceries.line);
+    assert_eq!(
+        NaiveDate::from_ymd(2020, 3, 1).and_hms(0, 0, 0),
+        groceries.created_at
+    );
+    assert_eq!("EXP", groceries.data.transaction_type);
+    assert_eq!(BigDecimal::from(45.1), groceries.data.amount);
+    assert_eq!("Grocery store", groceries.data.comment);
+    assert_eq!(vec!["imported", "bank"], groceries.data.tags);
+
+    let salary = &parsed.rows[1];
+    assert_eq!("INC", salary.data.transaction_type);
+    assert_eq!(BigDecimal::from(1000), salary.data.amount);
+
+    assert_eq!(
+        json!([
+            {"line": 4, "errors": {"date": ["Date \"yesterday\" does not match format \"%Y-%m-%d\"."]}},
+            {"line": 5, "errors": {"amount": ["A valid number is required."]}},
+            {"line": 6, "errors": {"amount": ["Ensure that there are no more than 2 decimal places."]}},
+        ]),
+        serde_json::to_value(&parsed.errors).unwrap()
+    );
+}
+
+#[test]
+fn parse_with_positive_expenses_and_custom_date_format() {
+    let content = "When,Sum\n01/03/2020,12.5\n02/03/2020,-7\n";
+    let settings = settings(json!({
+        "date_column": "When",
+        "amount_column": "Sum",
+        "description_column": null,
+        "date_format": "%d/%m/%Y",
+        "amount_sign": "positive_expense",
+    }));
+
+    let parsed = settings
+        .parse(content.as_bytes(), &calendar("UTC"))
+        .expect("Failed to parse");
+
+    assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+    assert_eq!("EXP", parsed.rows[0].data.transaction_type);
+    assert_eq!(
+        NaiveDate::from_ymd(2020, 3, 1).and_hms(0, 0, 0),
+        parsed.rows[0].created_at
+    );
+    assert_eq!("INC", parsed.rows[1].data.transaction_type);
+}
+
+#[test]
+fn parse_with_unknown_column() {
+    let errors = settings(json!({ "amount_column": "Sum" }))
+        .parse(STATEMENT.as_bytes(), &calendar("UTC"))
+        .unwrap_err();
+
+    assert_eq!(
+        json!({"amount_column": ["Column \"Sum\" is not found in the file."]}),
+        serde_json::to_value(errors).unwrap()
+    );
+}
+
+#[test]
+fn parse_dates_in_user_timezone() {
+    let vancouver = calendar("America/Vancouver");
+    let created_at = |content: &str, date_format: &str| {
+        let parsed = settings(json!({
+            "description_column": null,
+            "date_format": date_format,
+        }))
+        .parse(content.as_bytes(), &vancouver)
+        .expect("Failed to parse");
+
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        parsed.rows[0].created_at
+    };
+
+    assert_eq!(
+        NaiveDate::from_ymd(2020, 3, 1).and_hms(8, 0, 0),
+        created_at("Date,Amount\n2020-03-01,-1\n", "%Y-%m-%d")
+    );
+    assert_eq!(
+        NaiveDate::from_ymd(2020, 3, 2).and_hms(1, 30, 0),
+        created_at("Date,Amount\n2020-03-01 17:30,-1\n", "%Y-%m-%d %H:%M")
+    );
+}
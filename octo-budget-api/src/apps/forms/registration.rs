@@ -0,0 +1,159 @@
+use actix_web::{error::ResponseError, HttpResponse};
+use failure::Fail;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::ValidationError;
+
+const MAX_USERNAME_LENGTH: usize = 150;
+const MAX_EMAIL_LENGTH: usize = 254;
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+#[derive(Deserialize, Debug, Default)]
+pub struct Form {
+    username: Option<String>,
+    email: Option<String>,
+    password: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct Data {
+    pub username: String,
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Debug, Fail, Serialize, Default, PartialEq)]
+pub struct ValidationErrors {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    username: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    email: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    password: Vec<String>,
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl ResponseError for ValidationErrors {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().json(self)
+    }
+}
+
+impl ValidationErrors {
+    fn is_empty(&self) -> bool {
+        self.username.is_empty() && self.email.is_empty() && self.password.is_empty()
+    }
+
+    pub fn username_taken() -> Self {
+        Self {
+            username: vec!["A user with that username already exists.".to_string()],
+            ..Default::default()
+        }
+    }
+}
+
+impl Form {
+    pub fn validate(self) -> Result<Data, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        let username = required(self.username, &mut errors.username);
+        let email = required(self.email, &mut errors.email);
+        let password = required(self.password, &mut errors.password);
+
+        if let Some(username) = &username {
+            validate_username(username, &mut errors.username);
+        }
+        if let Some(email) = &email {
+            validate_email(email, &mut errors.email);
+        }
+        if let Some(password) = &password {
+            validate_password(password, username.as_deref(), &mut errors.password);
+        }
+
+        match (username, email, password) {
+            (Some(username), Some(email), Some(password)) if errors.is_empty() => Ok(Data {
+                username,
+                email,
+                password,
+            }),
+            _ => Err(errors),
+        }
+    }
+}
+
+pub(super) fn required(value: Option<String>, errors: &mut Vec<String>) -> Option<String> {
+    match value {
+        None => {
+            errors.push(ValidationError::MustPresent.to_string());
+            None
+        }
+        Some(val) if val.trim().is_empty() => {
+            errors.push(ValidationError::CannotBeBlank.to_string());
+            None
+        }
+        Some(val) => Some(val),
+    }
+}
+
+fn validate_username(username: &str, errors: &mut Vec<String>) {
+    if username.chars().count() > MAX_USERNAME_LENGTH {
+        errors.push(format!(
+            "Ensure this field has no more than {} characters.",
+            MAX_USERNAME_LENGTH
+        ));
+    }
+
+    let is_allowed = |c: char| c.is_alphanumeric() || "@.+-_".contains(c);
+    if !username.chars().all(is_allowed) {
+        errors.push(
+            "Enter a valid username. This value may contain only letters, numbers, and @/./+/-/_ characters."
+                .to_string(),
+        );
+    }
+}
+
+pub fn validate_email(email: &str, errors: &mut Vec<String>) {
+    let is_valid = match email.rsplitn(2, '@').collect::<Vec<_>>().as_slice() {
+        [domain, local] => {
+            !local.is_empty()
+                && !email.chars().any(char::is_whitespace)
+                && domain.contains('.')
+                && domain.split('.').all(|label| !label.is_empty())
+        }
+        _ => false,
+    };
+
+    if !is_valid || email.len() > MAX_EMAIL_LENGTH {
+        errors.push("Enter a valid email address.".to_string());
+    }
+}
+
+pub fn validate_password(password: &str, username: Option<&str>, errors: &mut Vec<String>) {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        errors.push(format!(
+            "This password is too short. It must contain at least {} characters.",
+            MIN_PASSWORD_LENGTH
+        ));
+    }
+
+    if password.chars().all(|c| c.is_ascii_digit()) {
+        errors.push("This password is entirely numeric.".to_string());
+    }
+
+    if let Some(username) = username {
+        let password = password.to_lowercase();
+        let username = username.to_lowercase();
+
+        if password.contains(&username) || username.contains(&password) {
+            errors.push("The password is too similar to the username.".to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,94 @@
+use super::*;
+use serde_json::{json, Value};
+
+fn make_form(params: Value) -> Form {
+    serde_json::from_value(params).expect("Failed to deserialize form")
+}
+
+#[test]
+fn valid_form() {
+    let data = make_form(json!({
+        "username": "john.doe",
+        "email": "john@example.com",
+        "password": "correct horse",
+    }))
+    .validate()
+    .expect("is expected to be valid");
+
+    assert_eq!("john.doe", data.username);
+    assert_eq!("john@example.com", data.email);
+    assert_eq!("correct horse", data.password);
+}
+
+#[test]
+fn missing_fields() {
+    let errors = make_form(json!({ "username": " " }))
+        .validate()
+        .unwrap_err();
+
+    assert_eq!(
+        json!({
+            "username": ["This field may not be blank."],
+            "email": ["This field is required."],
+            "password": ["This field is required."],
+        }),
+        serde_json::to_value(errors).unwrap()
+    );
+}
+
+#[test]
+fn invalid_username_and_email() {
+    let errors = make_form(json!({
+        "username": "john doe",
+        "email": "john@localhost",
+        "password": "correct horse",
+    }))
+    .validate()
+    .unwrap_err();
+
+    assert_eq!(
+        json!({
+            "username": ["Enter a valid username. This value may contain only letters, numbers, and @/./+/-/_ characters."],
+            "email": ["Enter a valid email address."],
+        }),
+        serde_json::to_value(errors).unwrap()
+    );
+}
+
+#[test]
+fn email_format() {
+    let is_valid = |email: &str| {
+        let mut errors = vec![];
+        validate_email(email, &mut errors);
+        errors.is_empty()
+    };
+
+    assert!(is_valid("john@example.com"));
+    assert!(is_valid("john+budget@mail.example.com"));
+    assert!(!is_valid("john"));
+    assert!(!is_valid("@example.com"));
+    assert!(!is_valid("john@example."));
+    assert!(!is_valid("john doe@example.com"));
+}
+
+#[test]
+fn weak_passwords() {
+    let password_errors = |password: &str| {
+        let mut errors = vec![];
+        validate_password(password, Some("johndoe"), &mut errors);
+        errors
+    };
+
+    assert_eq!(
+        vec![
+            "This password is too short. It must contain at least 8 characters.",
+            "This password is entirely numeric.",
+        ],
+        password_errors("1234")
+    );
+    assert_eq!(
+        vec!["The password is too similar to the username."],
+        password_errors("JohnDoe2020")
+    );
+    assert!(password_errors("correct horse").is_empty());
+}
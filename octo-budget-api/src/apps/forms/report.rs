@@ -0,0 +1,196 @@
+use actix_web::{error::ResponseError, HttpResponse};
+use chrono::{Datelike, NaiveDate};
+use failure::Fail;
+use models::currency;
+use serde::{Deserialize, Serialize};
+
+use crate::db::{calendar::Calendar, models::UserSettings};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DEFAULT_MONTHS: u32 = 12;
+/// every month is a bucket of the report, so the span is limited
+const MAX_MONTHS: i32 = 10 * 12;
+const MIN_YEAR: i32 = 1900;
+const MAX_YEAR: i32 = 2100;
+
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct Params {
+    from: Option<String>,
+    to: Option<String>,
+    transaction_type: Option<String>,
+    currency: Option<String>,
+    level: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct Data {
+    /// first day of the first month of the report
+    pub from: NaiveDate,
+    /// last day of the report, inclusive
+    pub to: NaiveDate,
+    pub transaction_type: String,
+    pub currency: String,
+    /// nested tags deeper than this are rolled up into their ancestors
+    pub level: Option<i32>,
+}
+
+#[derive(Debug, Fail, Serialize, Default)]
+pub struct ValidationErrors {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    from: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    to: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    transaction_type: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    currency: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    level: Vec<String>,
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl ResponseError for ValidationErrors {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().json(self)
+    }
+}
+
+impl ValidationErrors {
+    fn is_empty(&self) -> bool {
+        self.from.is_empty()
+            && self.to.is_empty()
+            && self.transaction_type.is_empty()
+            && self.currency.is_empty()
+            && self.level.is_empty()
+    }
+}
+
+impl Params {
+    /// By default the report covers the last 12 months, including the current one,
+    /// sums are converted into the user's default currency and nested tags are
+    /// not rolled up.
+    pub fn validate(self, settings: &UserSettings) -> Result<Data, ValidationErrors> {
+        let today = Calendar::new(settings).today();
+
+        self.validate_at(today, &settings.default_currency)
+    }
+
+    fn validate_at(
+        self,
+        today: NaiveDate,
+        default_currency: &str,
+    ) -> Result<Data, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        let to = parse_date(self.to, &mut errors.to).unwrap_or(today);
+        let from = parse_date(self.from, &mut errors.from)
+            .unwrap_or_else(|| months_before(to, DEFAULT_MONTHS - 1));
+
+        if from > to {
+            errors.to.push("Must not be earlier than from.".to_string());
+        } else if months_between(from, to) >= MAX_MONTHS {
+            errors.to.push(format!(
+                "Ensure the report covers no more than {} months.",
+                MAX_MONTHS
+            ));
+        }
+
+        let transaction_type = match self.transaction_type.as_deref() {
+            None | Some("") => "EXP".to_string(),
+            Some(val @ "EXP") | Some(val @ "INC") => val.to_string(),
+            Some(other) => {
+                errors
+                    .transaction_type
+                    .push(format!("\"{}\" is not a valid choice.", other));
+                String::new()
+            }
+        };
+
+        let currency = match self.currency.as_deref() {
+            None | Some("") => default_currency.to_string(),
+            Some(val) => match currency::find(val) {
+                Some(currency) => currency.code.to_string(),
+                None => {
+                    errors
+                        .currency
+                        .push(format!("\"{}\" is not a valid choice.", val));
+                    String::new()
+                }
+            },
+        };
+
+        let level = match self.level.as_deref() {
+            None | Some("") => None,
+            Some(val) => match val.parse::<i32>() {
+                Ok(level) if level >= 1 => Some(level),
+                Ok(_) => {
+                    errors
+                        .level
+                        .push("Ensure this value is greater than or equal to 1.".to_string());
+                    None
+                }
+                Err(_) => {
+                    errors
+                        .level
+                        .push("A valid integer is required.".to_string());
+                    None
+                }
+            },
+        };
+
+        if errors.is_empty() {
+            Ok(Data {
+                from: from.with_day(1).unwrap(),
+                to,
+                transaction_type,
+                currency,
+                level,
+            })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn parse_date(value: Option<String>, errors: &mut Vec<String>) -> Option<NaiveDate> {
+    match value.as_deref() {
+        None | Some("") => None,
+        Some(val) => match NaiveDate::parse_from_str(val, DATE_FORMAT) {
+            Ok(date) if (MIN_YEAR..=MAX_YEAR).contains(&date.year()) => Some(date),
+            Ok(_) => {
+                errors.push(format!(
+                    "Ensure the year is between {} and {}.",
+                    MIN_YEAR, MAX_YEAR
+                ));
+                None
+            }
+            Err(_) => {
+                errors.push(
+                    "Date has wrong format. Use one of these formats instead: YYYY-MM-DD."
+                        .to_string(),
+                );
+                None
+            }
+        },
+    }
+}
+
+/// Number of months from the month of `from` to the month of `to`.
+fn months_between(from: NaiveDate, to: NaiveDate) -> i32 {
+    (to.year() - from.year()) * 12 + to.month0() as i32 - from.month0() as i32
+}
+
+/// The first day of the month that is `months` months before the month of `date`.
+fn months_before(date: NaiveDate, months: u32) -> NaiveDate {
+    let total = date.year() * 12 + date.month0() as i32 - months as i32;
+
+    NaiveDate::from_ymd(total.div_euclid(12), total.rem_euclid(12) as u32 + 1, 1)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,132 @@
+use super::*;
+use serde_json::{json, Value};
+
+fn make_params(params: Value) -> Params {
+    serde_json::from_value(params).expect("Failed to deserialize params")
+}
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd(2020, 3, 15)
+}
+
+#[test]
+fn defaults_to_the_last_twelve_months() {
+    let data = make_params(json!({}))
+        .validate_at(today(), "CAD")
+        .expect("is expected to be valid");
+
+    assert_eq!(NaiveDate::from_ymd(2019, 4, 1), data.from);
+    assert_eq!(today(), data.to);
+    assert_eq!("EXP", data.transaction_type);
+    assert_eq!("CAD", data.currency);
+    assert_eq!(None, data.level);
+}
+
+#[test]
+fn from_is_aligned_to_the_beginning_of_the_month() {
+    let data = make_params(json!({
+        "from": "2020-01-20",
+        "to": "2020-02-10",
+        "transaction_type": "INC",
+        "currency": "EUR",
+        "level": "2",
+    }))
+    .validate_at(today(), "CAD")
+    .expect("is expected to be valid");
+
+    assert_eq!(NaiveDate::from_ymd(2020, 1, 1), data.from);
+    assert_eq!(NaiveDate::from_ymd(2020, 2, 10), data.to);
+    assert_eq!("INC", data.transaction_type);
+    assert_eq!("EUR", data.currency);
+    assert_eq!(Some(2), data.level);
+}
+
+#[test]
+fn invalid_params() {
+    let errors = make_params(json!({
+        "from": "last year",
+        "transaction_type": "FOO",
+        "currency": "XYZ",
+        "level": "top",
+    }))
+    .validate_at(today(), "CAD")
+    .unwrap_err();
+
+    assert_eq!(
+        json!({
+            "from": ["Date has wrong format. Use one of these formats instead: YYYY-MM-DD."],
+            "transaction_type": ["\"FOO\" is not a valid choice."],
+            "currency": ["\"XYZ\" is not a valid choice."],
+            "level": ["A valid integer is required."],
+        }),
+        serde_json::to_value(errors).unwrap()
+    );
+}
+
+#[test]
+fn level_starts_from_one() {
+    let errors = make_params(json!({ "level": "0" }))
+        .validate_at(today(), "CAD")
+        .unwrap_err();
+
+    assert_eq!(
+        json!({"level": ["Ensure this value is greater than or equal to 1."]}),
+        serde_json::to_value(errors).unwrap()
+    );
+}
+
+#[test]
+fn span_is_limited_to_ten_years() {
+    let data = make_params(json!({ "from": "2010-04-01", "to": "2020-03-15" }))
+        .validate_at(today(), "CAD")
+        .expect("is expected to be valid");
+    assert_eq!(NaiveDate::from_ymd(2010, 4, 1), data.from);
+
+    let errors = make_params(json!({ "from": "2010-03-31", "to": "2020-03-15" }))
+        .validate_at(today(), "CAD")
+        .unwrap_err();
+
+    assert_eq!(
+        json!({"to": ["Ensure the report covers no more than 120 months."]}),
+        serde_json::to_value(errors).unwrap()
+    );
+}
+
+#[test]
+fn dates_out_of_range() {
+    let errors = make_params(json!({ "from": "1899-12-31", "to": "2101-01-01" }))
+        .validate_at(today(), "CAD")
+        .unwrap_err();
+
+    assert_eq!(
+        json!({
+            "from": ["Ensure the year is between 1900 and 2100."],
+            "to": ["Ensure the year is between 1900 and 2100."],
+        }),
+        serde_json::to_value(errors).unwrap()
+    );
+}
+
+#[test]
+fn from_after_to() {
+    let errors = make_params(json!({ "from": "2020-03-01", "to": "2020-02-01" }))
+        .validate_at(today(), "CAD")
+        .unwrap_err();
+
+    assert_eq!(
+        json!({"to": ["Must not be earlier than from."]}),
+        serde_json::to_value(errors).unwrap()
+    );
+}
+
+#[test]
+fn months_before_crosses_years() {
+    assert_eq!(
+        NaiveDate::from_ymd(2019, 12, 1),
+        months_before(NaiveDate::from_ymd(2020, 2, 29), 2)
+    );
+    assert_eq!(
+        NaiveDate::from_ymd(2020, 2, 1),
+        months_before(NaiveDate::from_ymd(2020, 2, 29), 0)
+    );
+}
@@ -0,0 +1,109 @@
+use actix_web::{error::ResponseError, HttpResponse};
+use chrono_tz::Tz;
+use failure::Fail;
+use models::currency;
+use serde::{Deserialize, Serialize};
+
+use crate::db::models::UserSettings;
+
+/// Partial update, fields which are not sent are left as they are.
+#[derive(Deserialize, Debug, Default)]
+pub struct Form {
+    timezone: Option<String>,
+    default_currency: Option<String>,
+    week_start: Option<i16>,
+    month_start_day: Option<i16>,
+}
+
+#[derive(Debug, Fail, Serialize, Default, PartialEq)]
+pub struct ValidationErrors {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    timezone: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    default_currency: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    week_start: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    month_start_day: Vec<String>,
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl ResponseError for ValidationErrors {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().json(self)
+    }
+}
+
+impl ValidationErrors {
+    fn is_empty(&self) -> bool {
+        self.timezone.is_empty()
+            && self.default_currency.is_empty()
+            && self.week_start.is_empty()
+            && self.month_start_day.is_empty()
+    }
+}
+
+impl Form {
+    /// `current` settings with the sent fields applied.
+    pub fn validate(self, current: UserSettings) -> Result<UserSettings, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        let mut settings = current;
+
+        if let Some(timezone) = self.timezone {
+            match timezone.parse::<Tz>() {
+                Ok(tz) => settings.timezone = tz.name().to_string(),
+                Err(_) => errors.timezone.push(not_a_choice(&timezone)),
+            }
+        }
+
+        if let Some(code) = self.default_currency {
+            match currency::find(&code) {
+                Some(currency) => settings.default_currency = currency.code.to_string(),
+                None => errors.default_currency.push(not_a_choice(&code)),
+            }
+        }
+
+        if let Some(day) = self.week_start {
+            validate_range(day, 1, 7, &mut errors.week_start);
+            settings.week_start = day;
+        }
+
+        // every month has the 28th, so the period always starts on the same day
+        if let Some(day) = self.month_start_day {
+            validate_range(day, 1, 28, &mut errors.month_start_day);
+            settings.month_start_day = day;
+        }
+
+        if errors.is_empty() {
+            Ok(settings)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn not_a_choice(value: &str) -> String {
+    format!("\"{}\" is not a valid choice.", value)
+}
+
+fn validate_range(value: i16, min: i16, max: i16, errors: &mut Vec<String>) {
+    if value < min {
+        errors.push(format!(
+            "Ensure this value is greater than or equal to {}.",
+            min
+        ));
+    } else if value > max {
+        errors.push(format!(
+            "Ensure this value is less than or equal to {}.",
+            max
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests;
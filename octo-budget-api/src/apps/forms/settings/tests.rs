@@ -0,0 +1,60 @@
+use super::*;
+use serde_json::{json, Value};
+
+fn make_form(params: Value) -> Form {
+    serde_json::from_value(params).expect("Failed to deserialize form")
+}
+
+#[test]
+fn empty_form_changes_nothing() {
+    let settings = make_form(json!({}))
+        .validate(UserSettings::defaults(1))
+        .unwrap();
+
+    assert_eq!(UserSettings::defaults(1), settings);
+}
+
+#[test]
+fn valid_form() {
+    let settings = make_form(json!({
+        "timezone": "America/Vancouver",
+        "default_currency": "USD",
+        "week_start": 7,
+        "month_start_day": 15,
+    }))
+    .validate(UserSettings::defaults(1))
+    .unwrap();
+
+    assert_eq!(
+        UserSettings {
+            user_id: 1,
+            timezone: "America/Vancouver".to_string(),
+            default_currency: "USD".to_string(),
+            week_start: 7,
+            month_start_day: 15,
+        },
+        settings
+    );
+}
+
+#[test]
+fn invalid_form() {
+    let errors = make_form(json!({
+        "timezone": "Mars/Olympus",
+        "default_currency": "XXX",
+        "week_start": 0,
+        "month_start_day": 31,
+    }))
+    .validate(UserSettings::defaults(1))
+    .unwrap_err();
+
+    assert_eq!(
+        json!({
+            "timezone": ["\"Mars/Olympus\" is not a valid choice."],
+            "default_currency": ["\"XXX\" is not a valid choice."],
+            "week_start": ["Ensure this value is greater than or equal to 1."],
+            "month_start_day": ["Ensure this value is less than or equal to 28."],
+        }),
+        serde_json::to_value(errors).unwrap()
+    );
+}
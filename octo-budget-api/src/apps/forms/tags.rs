@@ -0,0 +1,161 @@
+use actix_web::{error::ResponseError, HttpResponse};
+use failure::Fail;
+use serde::{Deserialize, Serialize};
+
+use super::registration::required;
+use crate::db::tags::is_descendant;
+use crate::errors::ValidationError;
+
+const INTO_DESCENDANT: &str = "A tag cannot be moved into its own descendant.";
+
+#[derive(Deserialize, Debug, Default)]
+pub struct RenameForm {
+    from: Option<String>,
+    to: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct MergeForm {
+    #[serde(default)]
+    tags: Vec<String>,
+    into: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct RebuildForm {
+    user_id: Option<i32>,
+    half_life_days: Option<f64>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct SuggestParams {
+    #[serde(default)]
+    pub prefix: String,
+    /// comment of the record being entered
+    #[serde(default)]
+    pub comment: String,
+}
+
+/// Tags of records, budgets and the user's own list that are replaced by
+/// `into`.
+#[derive(Debug, PartialEq)]
+pub struct MergeData {
+    pub tags: Vec<String>,
+    pub into: String,
+}
+
+#[derive(Debug, Fail, Serialize, Default, PartialEq)]
+pub struct ValidationErrors {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    from: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    to: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    into: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    half_life_days: Vec<String>,
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl ResponseError for ValidationErrors {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().json(self)
+    }
+}
+
+impl ValidationErrors {
+    fn is_empty(&self) -> bool {
+        self.from.is_empty()
+            && self.to.is_empty()
+            && self.tags.is_empty()
+            && self.into.is_empty()
+            && self.half_life_days.is_empty()
+    }
+}
+
+impl RenameForm {
+    /// Renaming is merging of a single tag.
+    pub fn validate(self) -> Result<MergeData, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        let from = required(self.from, &mut errors.from);
+        let to = required(self.to, &mut errors.to);
+
+        if from.is_some() && from == to {
+            errors
+                .to
+                .push("The new name should differ from the old one.".to_string());
+        } else if let (Some(from), Some(to)) = (&from, &to) {
+            if is_descendant(to, from) {
+                errors.to.push(INTO_DESCENDANT.to_string());
+            }
+        }
+
+        match (from, to) {
+            (Some(from), Some(to)) if errors.is_empty() => Ok(MergeData {
+                tags: vec![from],
+                into: to,
+            }),
+            _ => Err(errors),
+        }
+    }
+}
+
+impl MergeForm {
+    pub fn validate(self) -> Result<MergeData, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        let into = required(self.into, &mut errors.into);
+
+        if self.tags.is_empty() {
+            errors.tags.push(ValidationError::MustPresent.to_string());
+        } else if self.tags.iter().any(|tag| tag.trim().is_empty()) {
+            errors.tags.push(ValidationError::CannotBeBlank.to_string());
+        }
+
+        if let Some(into) = &into {
+            if self
+                .tags
+                .iter()
+                .any(|tag| tag != into && is_descendant(into, tag))
+            {
+                errors.into.push(INTO_DESCENDANT.to_string());
+            }
+        }
+
+        // merging a tag into itself changes nothing
+        let tags = self
+            .tags
+            .into_iter()
+            .filter(|tag| Some(tag) != into.as_ref())
+            .collect();
+
+        match into {
+            Some(into) if errors.is_empty() => Ok(MergeData { tags, into }),
+            _ => Err(errors),
+        }
+    }
+}
+
+impl RebuildForm {
+    /// Users and the half-life of a use in days to rebuild scores with.
+    pub fn validate(self) -> Result<(Option<i32>, Option<f64>), ValidationErrors> {
+        match self.half_life_days {
+            Some(days) if days <= 0.0 => Err(ValidationErrors {
+                half_life_days: vec!["Ensure this value is greater than 0.".to_string()],
+                ..Default::default()
+            }),
+            _ => Ok((self.user_id, self.half_life_days)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
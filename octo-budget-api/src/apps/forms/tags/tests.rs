@@ -0,0 +1,140 @@
+use super::*;
+use crate::tags_vec;
+use serde_json::{json, Value};
+
+fn to_json(errors: ValidationErrors) -> Value {
+    serde_json::to_value(errors).unwrap()
+}
+
+#[test]
+fn rename_form_requires_both_names() {
+    let form = RenameForm {
+        from: None,
+        to: Some(" ".to_string()),
+    };
+
+    assert_eq!(
+        json!({
+            "from": ["This field is required."],
+            "to": ["This field may not be blank."],
+        }),
+        to_json(form.validate().unwrap_err())
+    );
+}
+
+#[test]
+fn rename_to_the_same_name() {
+    let form = RenameForm {
+        from: Some("food".to_string()),
+        to: Some("food".to_string()),
+    };
+
+    assert_eq!(
+        json!({ "to": ["The new name should differ from the old one."] }),
+        to_json(form.validate().unwrap_err())
+    );
+}
+
+#[test]
+fn rename_into_own_descendant() {
+    let form = RenameForm {
+        from: Some("food".to_string()),
+        to: Some("food/groceries".to_string()),
+    };
+
+    assert_eq!(
+        json!({ "to": ["A tag cannot be moved into its own descendant."] }),
+        to_json(form.validate().unwrap_err())
+    );
+}
+
+#[test]
+fn merge_into_descendant_of_merged_tag() {
+    let form = MergeForm {
+        tags: tags_vec!["car", "food"],
+        into: Some("food/groceries".to_string()),
+    };
+
+    assert_eq!(
+        json!({ "into": ["A tag cannot be moved into its own descendant."] }),
+        to_json(form.validate().unwrap_err())
+    );
+}
+
+#[test]
+fn rename_is_merge_of_one_tag() {
+    let form = RenameForm {
+        from: Some("food".to_string()),
+        to: Some("groceries".to_string()),
+    };
+
+    assert_eq!(
+        MergeData {
+            tags: tags_vec!["food"],
+            into: "groceries".to_string(),
+        },
+        form.validate().unwrap()
+    );
+}
+
+#[test]
+fn merge_form_requires_tags() {
+    let form = MergeForm {
+        tags: vec![],
+        into: None,
+    };
+
+    assert_eq!(
+        json!({
+            "tags": ["This field is required."],
+            "into": ["This field is required."],
+        }),
+        to_json(form.validate().unwrap_err())
+    );
+
+    let form = MergeForm {
+        tags: tags_vec!["food", ""],
+        into: Some("groceries".to_string()),
+    };
+
+    assert_eq!(
+        json!({ "tags": ["This field may not be blank."] }),
+        to_json(form.validate().unwrap_err())
+    );
+}
+
+#[test]
+fn merge_skips_the_target_tag() {
+    let form = MergeForm {
+        tags: tags_vec!["food", "groceries", "restaurants"],
+        into: Some("groceries".to_string()),
+    };
+
+    assert_eq!(
+        MergeData {
+            tags: tags_vec!["food", "restaurants"],
+            into: "groceries".to_string(),
+        },
+        form.validate().unwrap()
+    );
+}
+
+#[test]
+fn rebuild_form_half_life_must_be_positive() {
+    let form = RebuildForm {
+        user_id: None,
+        half_life_days: Some(0.0),
+    };
+
+    assert_eq!(
+        json!({ "half_life_days": ["Ensure this value is greater than 0."] }),
+        to_json(form.validate().unwrap_err())
+    );
+
+    let form = RebuildForm {
+        user_id: Some(1),
+        half_life_days: Some(30.0),
+    };
+
+    assert_eq!((Some(1), Some(30.0)), form.validate().unwrap());
+}
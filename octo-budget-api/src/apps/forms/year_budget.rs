@@ -0,0 +1,123 @@
+use actix_web::{error::ResponseError, HttpResponse};
+use bigdecimal::BigDecimal;
+use failure::Fail;
+use models::Money;
+use serde::{Deserialize, Serialize};
+
+use super::budget::{validate_amount, validate_name, validate_tags_type};
+use crate::errors::ValidationError;
+
+const MIN_YEAR: i32 = 1900;
+const MAX_YEAR: i32 = 2100;
+
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct Form {
+    name: Option<String>,
+    amount: Option<Money>,
+    year: Option<i32>,
+    #[serde(default)]
+    tags: Vec<String>,
+    tags_type: Option<String>,
+    comment: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct FormData {
+    pub name: String,
+    pub amount: BigDecimal,
+    pub amount_currency: String,
+    pub year: i32,
+    pub tags: Vec<String>,
+    pub tags_type: String,
+    pub comment: String,
+}
+
+#[derive(Debug, Fail, Serialize, Default)]
+pub struct ValidationErrors {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    name: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    amount: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    currency_code: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    year: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags_type: Vec<String>,
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl ResponseError for ValidationErrors {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().json(self)
+    }
+}
+
+impl ValidationErrors {
+    fn is_empty(&self) -> bool {
+        self.name.is_empty()
+            && self.amount.is_empty()
+            && self.currency_code.is_empty()
+            && self.year.is_empty()
+            && self.tags_type.is_empty()
+    }
+}
+
+impl Form {
+    pub fn validate(self) -> Result<FormData, ValidationErrors> {
+        let Self {
+            name,
+            amount,
+            year,
+            tags,
+            tags_type,
+            comment,
+        } = self;
+        let mut errors = ValidationErrors::default();
+
+        let name = validate_name(name, &mut errors.name);
+        let (amount_number, amount_currency) =
+            validate_amount(amount, &mut errors.amount, &mut errors.currency_code);
+
+        let year = match year {
+            None => {
+                errors.year.push(ValidationError::MustPresent.to_string());
+                0
+            }
+            Some(val) if !(MIN_YEAR..=MAX_YEAR).contains(&val) => {
+                errors.year.push(format!(
+                    "Ensure this value is between {} and {}.",
+                    MIN_YEAR, MAX_YEAR
+                ));
+                val
+            }
+            Some(val) => val,
+        };
+
+        let tags_type = validate_tags_type(tags_type, &mut errors.tags_type);
+
+        let comment = comment.unwrap_or_default();
+
+        if errors.is_empty() {
+            Ok(FormData {
+                name,
+                amount: amount_number,
+                amount_currency,
+                year,
+                tags,
+                tags_type,
+                comment,
+            })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,69 @@
+use super::*;
+use serde_json::{json, Value};
+
+fn make_form(params: Value) -> Form {
+    let mut form = json!({
+        "name": "Travel",
+        "amount": {"amount": 5000, "currency": {"code": "CAD", "name": "Canadian Dollar"}},
+        "year": 2020,
+        "tags": ["travel"],
+        "tags_type": "INCL",
+    });
+
+    for (key, value) in params.as_object().unwrap() {
+        form[key] = value.clone();
+    }
+
+    serde_json::from_value(form).expect("Failed to deserialize form")
+}
+
+fn errors_json(form: Form) -> Value {
+    serde_json::to_value(form.validate().unwrap_err()).expect("Failed to convert to json")
+}
+
+#[test]
+fn test_validate_ok() {
+    let data = make_form(json!({}))
+        .validate()
+        .expect("is expected to be valid");
+
+    assert_eq!("Travel", data.name);
+    assert_eq!(BigDecimal::from(5000), data.amount);
+    assert_eq!("CAD", data.amount_currency);
+    assert_eq!(2020, data.year);
+    assert_eq!(vec!["travel"], data.tags);
+    assert_eq!("INCL", data.tags_type);
+}
+
+#[test]
+fn test_no_year() {
+    let form = make_form(json!({ "year": null }));
+
+    assert_eq!(
+        json!({"year": ["This field is required."]}),
+        errors_json(form)
+    );
+}
+
+#[test]
+fn test_year_out_of_range() {
+    let form = make_form(json!({ "year": 20 }));
+
+    assert_eq!(
+        json!({"year": ["Ensure this value is between 1900 and 2100."]}),
+        errors_json(form)
+    );
+}
+
+#[test]
+fn test_invalid_fields() {
+    let form = make_form(json!({ "name": "", "tags_type": "FOO" }));
+
+    assert_eq!(
+        json!({
+            "name": ["This field may not be blank."],
+            "tags_type": ["\"FOO\" is not a valid choice."],
+        }),
+        errors_json(form)
+    );
+}
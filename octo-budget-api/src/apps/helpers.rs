@@ -1,3 +1,23 @@
+use actix_web::{
+    http::header::{HeaderName, HeaderValue},
+    HttpResponse,
+};
+
+/// Set on responses served while Redis is unavailable: tags may be in a less
+/// accurate order and their counters are updated once Redis is back.
+pub const DEGRADED_MODE_HEADER: &str = "x-degraded-mode";
+
+pub fn with_degraded_mode(mut response: HttpResponse, degraded: bool) -> HttpResponse {
+    if degraded {
+        response.headers_mut().insert(
+            HeaderName::from_static(DEGRADED_MODE_HEADER),
+            HeaderValue::from_static("redis"),
+        );
+    }
+
+    response
+}
+
 pub fn sort_tags(redis_tags: &[String], user_tags: &[String]) -> Vec<String> {
     let mut result = Vec::with_capacity(user_tags.len());
 
@@ -27,3 +27,15 @@ fn sorting_tags_with_order_defined_by_redis_tags() {
 
     assert_eq!(tags_vec!["buz", "foo", "bar"], sorted);
 }
+
+#[test]
+fn degraded_mode_header() {
+    let response = with_degraded_mode(HttpResponse::Ok().finish(), false);
+    assert!(response.headers().get(DEGRADED_MODE_HEADER).is_none());
+
+    let response = with_degraded_mode(HttpResponse::Ok().finish(), true);
+    assert_eq!(
+        "redis",
+        response.headers().get(DEGRADED_MODE_HEADER).unwrap()
+    );
+}
@@ -1,3 +1,5 @@
+use models::{Legacy, LegacySerialize};
+use serde::ser::{SerializeStruct, Serializer};
 use serde::Serialize;
 
 #[derive(Serialize, Debug)]
@@ -7,3 +9,18 @@ pub struct Data<M: Serialize> {
     pub next: bool,
     pub previous: bool,
 }
+
+impl<M: Serialize + LegacySerialize> LegacySerialize for Data<M> {
+    fn serialize_legacy<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Data", 4)?;
+
+        state.serialize_field("total", &self.total)?;
+        state.serialize_field("results", &Legacy(&self.results))?;
+        state.serialize_field("next", &self.next)?;
+        state.serialize_field("previous", &self.previous)?;
+        state.end()
+    }
+}
@@ -0,0 +1,38 @@
+use actix_web::{dev::Payload, FromRequest, HttpRequest, HttpResponse};
+use futures::future::{ok, Ready};
+use models::{Legacy, LegacySerialize};
+use serde::Serialize;
+
+/// Client opts into `models::Money` in responses with this header, otherwise
+/// amounts are serialized as floats, as the React app expects.
+pub const HEADER: &str = "X-Money-Format";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoneyFormat {
+    Float,
+    Decimal,
+}
+
+impl MoneyFormat {
+    pub fn json<T: Serialize + LegacySerialize>(self, value: &T) -> HttpResponse {
+        match self {
+            MoneyFormat::Decimal => HttpResponse::Ok().json(value),
+            MoneyFormat::Float => HttpResponse::Ok().json(Legacy(value)),
+        }
+    }
+}
+
+impl FromRequest for MoneyFormat {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let format = match req.headers().get(HEADER).map(|value| value.to_str()) {
+            Some(Ok(value)) if value.eq_ignore_ascii_case("decimal") => MoneyFormat::Decimal,
+            _ => MoneyFormat::Float,
+        };
+
+        ok(format)
+    }
+}
@@ -0,0 +1,155 @@
+use actix_web::{
+    post,
+    web::{self, Json},
+    HttpResponse, Result,
+};
+use log::error;
+use octo_budget_lib::auth_token::UserId;
+use std::sync::Arc;
+
+use super::forms::password::{
+    validate_new_password, ChangeData, ChangeForm, ResetConfirmData, ResetConfirmForm, ResetForm,
+    ValidationErrors,
+};
+use crate::db::{
+    models::AuthUser,
+    queries::{FindActiveUsersByEmail, FindUser, RevokeRefreshTokens, SetPassword},
+    ConnectionPool,
+};
+use crate::errors::Error;
+use crate::mailer::{self, Email, Mailer};
+use crate::redis::{
+    login_throttle::LoginThrottle,
+    password_reset::{consume_reset_token, find_reset_token, issue_reset_token},
+    token_denylist::revoke_all_tokens,
+    Redis,
+};
+
+#[post("/password")]
+async fn change(
+    user_id: UserId,
+    form: Json<ChangeForm>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let ChangeData {
+        old_password,
+        new_password,
+    } = form.into_inner().validate()?;
+    let user = pool.execute(FindUser::new(user_id)).await?;
+
+    ChangeForm::validate_old_password(&user, &old_password)?;
+    validate_new_password(&user, &new_password)?;
+
+    pool.execute(SetPassword::new(user.id, new_password))
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Emails a reset token to every active account with the email. Responds the
+/// same way whether there are such accounts or not, so it cannot be used to
+/// find out who is registered. That is also why failures to send are only
+/// logged.
+#[post("/password/reset")]
+async fn reset(
+    form: Json<ResetForm>,
+    pool: web::Data<ConnectionPool>,
+    redis: web::Data<Redis>,
+    mailer: web::Data<Arc<dyn Mailer>>,
+) -> Result<HttpResponse> {
+    let email = form.into_inner().validate()?;
+    let throttle = LoginThrottle::password_reset(&email);
+
+    throttle.check(&redis).await?;
+    throttle.failed(&redis).await;
+
+    let users = pool.execute(FindActiveUsersByEmail::new(email)).await?;
+
+    for user in users {
+        let user_id = user.id;
+
+        if let Err(e) = send_reset_token(user, &redis, mailer.get_ref().clone()).await {
+            error!("Failed to send password reset to user {}: {}", user_id, e);
+        }
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+async fn send_reset_token(
+    user: AuthUser,
+    redis: &Redis,
+    mailer: Arc<dyn Mailer>,
+) -> Result<(), Error> {
+    let token = issue_reset_token(user.id, redis).await?;
+    let email = Email {
+        to: user.email,
+        subject: "Password reset".to_string(),
+        body: format!(
+            "Hi {},\n\nUse this token to reset your password: {}\n\nIt expires in an hour. If you did not ask for a password reset, just ignore this email.\n",
+            user.username, token
+        ),
+    };
+
+    mailer::send(mailer, email).await
+}
+
+/// Sets the new password and logs the user out everywhere.
+#[post("/password/reset/confirm")]
+async fn reset_confirm(
+    form: Json<ResetConfirmForm>,
+    pool: web::Data<ConnectionPool>,
+    redis: web::Data<Redis>,
+) -> Result<HttpResponse> {
+    let ResetConfirmData {
+        token,
+        new_password,
+    } = form.into_inner().validate()?;
+
+    let user_id = find_reset_token(&token, &redis)
+        .await?
+        .ok_or_else(ValidationErrors::invalid_token)?;
+    let user = pool.execute(FindUser::new(user_id)).await?;
+
+    if !user.is_active {
+        return Err(ValidationErrors::invalid_token().into());
+    }
+
+    validate_new_password(&user, &new_password)?;
+
+    if !consume_reset_token(&token, &redis).await? {
+        return Err(ValidationErrors::invalid_token().into());
+    }
+
+    pool.execute(SetPassword::new(user.id, new_password))
+        .await?;
+    revoke_all_tokens(user.id.into(), &redis).await?;
+    pool.execute(RevokeRefreshTokens::User(user.id)).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub mod service {
+    use super::*;
+    use actix_web::dev::HttpServiceFactory;
+
+    pub struct Service;
+
+    impl HttpServiceFactory for Service {
+        fn register(self, config: &mut actix_web::dev::AppService) {
+            HttpServiceFactory::register(change, config)
+        }
+    }
+
+    pub struct ResetService;
+
+    impl HttpServiceFactory for ResetService {
+        fn register(self, config: &mut actix_web::dev::AppService) {
+            HttpServiceFactory::register(reset, config);
+            HttpServiceFactory::register(reset_confirm, config);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
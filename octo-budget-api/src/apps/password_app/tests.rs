@@ -0,0 +1,249 @@
+use super::service::{ResetService, Service};
+use crate::{
+    await_test_server,
+    config::{login_failures_redis_key, login_lock_redis_key},
+    db::builders::UserBuilder,
+    redis::Redis,
+    tests::{outbox, setup_env, DbSession, RequestJwtAuthExt},
+};
+use actix_web::{
+    http::{Method, StatusCode},
+    test::{call_service, read_body, TestRequest},
+};
+use serde_json::{json, Value};
+
+fn post(uri: &str, body: Value) -> TestRequest {
+    TestRequest::with_uri(uri)
+        .method(Method::POST)
+        .set_json(&body)
+}
+
+async fn body_json(response: actix_web::dev::ServiceResponse) -> Value {
+    let body = read_body(response).await;
+
+    serde_json::from_slice(&body).unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", body))
+}
+
+/// The token from the last reset email sent to `email`.
+fn reset_token(email: &str) -> String {
+    let emails = outbox().emails_to(email);
+    let email = emails.last().expect("No email is sent");
+
+    email
+        .split("reset your password: ")
+        .nth(1)
+        .and_then(|rest| rest.split_whitespace().next())
+        .expect("No token in email")
+        .to_string()
+}
+
+/// Forgets earlier reset requests for `email`, e.g. of previous test runs.
+async fn reset_throttling(email: &str) {
+    let redis = Redis::connect().await.expect("Failed to connect to redis");
+
+    let _: () = redis::cmd("del")
+        .arg(login_failures_redis_key("reset_email", email))
+        .arg(login_lock_redis_key("reset_email", email))
+        .query_async(&mut redis.connection().unwrap())
+        .await
+        .expect("Failed to reset throttling");
+}
+
+#[actix_rt::test]
+async fn change_password_happy_path() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(
+        UserBuilder::default()
+            .username("john")
+            .password("old password"),
+    );
+    let mut service = await_test_server!(Service);
+
+    let request = post(
+        "/password",
+        json!({"old_password": "old password", "new_password": "correct horse"}),
+    )
+    .jwt_auth(user.id)
+    .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let user = session.find_user_by_name("john");
+    assert_eq!(
+        Ok(true),
+        djangohashers::check_password("correct horse", &user.password)
+    );
+}
+
+#[actix_rt::test]
+async fn change_password_with_wrong_old_password() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(
+        UserBuilder::default()
+            .username("john")
+            .password("old password"),
+    );
+    let mut service = await_test_server!(Service);
+
+    let request = post(
+        "/password",
+        json!({"old_password": "wrong", "new_password": "correct horse"}),
+    )
+    .jwt_auth(user.id)
+    .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(
+        json!({"old_password": ["Your old password was entered incorrectly. Please enter it again."]}),
+        body_json(response).await
+    );
+}
+
+#[actix_rt::test]
+async fn change_password_requires_auth() {
+    setup_env();
+    let mut service = await_test_server!(Service);
+
+    let request = post(
+        "/password",
+        json!({"old_password": "old password", "new_password": "correct horse"}),
+    )
+    .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+}
+
+#[actix_rt::test]
+async fn reset_password_happy_path() {
+    setup_env();
+    let session = DbSession::new();
+    session.create_user(
+        UserBuilder::default()
+            .username("john")
+            .email("reset-happy-path@example.com")
+            .password("forgotten"),
+    );
+    reset_throttling("reset-happy-path@example.com").await;
+    let mut service = await_test_server!(ResetService);
+
+    let request = post(
+        "/password/reset",
+        json!({"email": "reset-happy-path@example.com"}),
+    )
+    .to_request();
+    let response = call_service(&mut service, request).await;
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let token = reset_token("reset-happy-path@example.com");
+    let request = post(
+        "/password/reset/confirm",
+        json!({"token": token, "new_password": "correct horse"}),
+    )
+    .to_request();
+    let response = call_service(&mut service, request).await;
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let user = session.find_user_by_name("john");
+    assert_eq!(
+        Ok(true),
+        djangohashers::check_password("correct horse", &user.password)
+    );
+
+    let request = post(
+        "/password/reset/confirm",
+        json!({"token": token, "new_password": "another password"}),
+    )
+    .to_request();
+    let response = call_service(&mut service, request).await;
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(
+        json!({"token": ["Invalid or expired token."]}),
+        body_json(response).await,
+        "token is expected to be single-use"
+    );
+}
+
+#[actix_rt::test]
+async fn reset_password_with_unknown_email() {
+    setup_env();
+    let _session = DbSession::new();
+    reset_throttling("nobody@example.com").await;
+    let mut service = await_test_server!(ResetService);
+
+    let request = post("/password/reset", json!({"email": "nobody@example.com"})).to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+    assert!(outbox().emails_to("nobody@example.com").is_empty());
+}
+
+#[actix_rt::test]
+async fn reset_token_survives_weak_password() {
+    setup_env();
+    let session = DbSession::new();
+    session.create_user(
+        UserBuilder::default()
+            .username("john")
+            .email("reset-weak-password@example.com"),
+    );
+    reset_throttling("reset-weak-password@example.com").await;
+    let mut service = await_test_server!(ResetService);
+
+    let request = post(
+        "/password/reset",
+        json!({"email": "reset-weak-password@example.com"}),
+    )
+    .to_request();
+    call_service(&mut service, request).await;
+    let token = reset_token("reset-weak-password@example.com");
+
+    let request = post(
+        "/password/reset/confirm",
+        json!({"token": token, "new_password": "123"}),
+    )
+    .to_request();
+    let response = call_service(&mut service, request).await;
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let request = post(
+        "/password/reset/confirm",
+        json!({"token": token, "new_password": "correct horse"}),
+    )
+    .to_request();
+    let response = call_service(&mut service, request).await;
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+}
+
+#[actix_rt::test]
+async fn reset_password_is_throttled_per_email() {
+    setup_env();
+    let _session = DbSession::new();
+    reset_throttling("reset-throttled@example.com").await;
+    let mut service = await_test_server!(ResetService);
+
+    // allowed attempts, then the first delay
+    for _ in 0..4 {
+        let request = post(
+            "/password/reset",
+            json!({"email": "reset-throttled@example.com"}),
+        )
+        .to_request();
+        let response = call_service(&mut service, request).await;
+
+        assert_eq!(StatusCode::NO_CONTENT, response.status());
+    }
+
+    let request = post(
+        "/password/reset",
+        json!({"email": "Reset-Throttled@example.com"}),
+    )
+    .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+}
@@ -1,14 +1,21 @@
+use actix_multipart::Multipart;
 use actix_web::{
-    get, post, put,
+    delete, get, post, put,
     web::{self, Json, Path, Query},
     HttpResponse, Result,
 };
 use octo_budget_lib::auth_token::UserId;
 
-use super::forms::record::Form;
+use super::forms::{record::Form, record_filters, record_import::ImportParams};
+use super::helpers::with_degraded_mode;
 use super::index_params::Params;
+use super::money_format::MoneyFormat;
 use crate::db::{
-    queries::{CreateRecord, FindRecord, GetRecords, UpdateRecord},
+    calendar::Calendar,
+    queries::{
+        CreateRecord, DeleteRecord, FindRecord, GetRecords, GetUserSettings, ImportRecords,
+        UpdateRecord,
+    },
     ConnectionPool,
 };
 use crate::redis::{
@@ -20,19 +27,42 @@ use crate::redis::{
 async fn index(
     user_id: UserId,
     params: Query<Params>,
+    filters: Query<record_filters::Params>,
     pool: web::Data<ConnectionPool>,
+    money_format: MoneyFormat,
 ) -> Result<HttpResponse> {
     let params = params.into_inner().validate()?;
+    let filters = filters.into_inner().validate()?;
 
     let message = GetRecords {
         page: params.page,
         per_page: params.per_page,
         user_id: user_id.into(),
+        filters,
     };
 
     let records = pool.execute(message).await?;
 
-    Ok(HttpResponse::Ok().json(records))
+    Ok(money_format.json(&records))
+}
+
+#[get("/export.csv")]
+async fn export(
+    user_id: UserId,
+    filters: Query<record_filters::Params>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let filters = filters.into_inner().validate()?;
+    let settings = pool.execute(GetUserSettings::new(user_id)).await?;
+    let body = csv_export::records_csv(pool, user_id.into(), filters, Calendar::new(&settings));
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .header(
+            "Content-Disposition",
+            "attachment; filename=\"records.csv\"",
+        )
+        .streaming(body))
 }
 
 #[post("/record-detail/")]
@@ -47,9 +77,50 @@ async fn create(
     let data = form.into_inner().validate()?;
     let id = pool.execute(CreateRecord::new(&data, user_id)).await?;
 
-    increment_tags(user_id, data.tags, &redis).await?;
+    // the record is saved anyway, counters are updated once redis is back
+    let degraded = increment_tags(user_id, data.tags, &redis).await.is_err();
+    let response = HttpResponse::Ok().json(json!({ "id": id }));
+
+    Ok(with_degraded_mode(response, degraded))
+}
+
+#[post("/import")]
+async fn import(
+    user_id: UserId,
+    params: Query<ImportParams>,
+    payload: Multipart,
+    pool: web::Data<ConnectionPool>,
+    redis: web::Data<Redis>,
+) -> Result<HttpResponse> {
+    use serde_json::json;
+
+    let (mapping, file) = csv_import::read_upload(payload).await?;
+    let settings = pool.execute(GetUserSettings::new(user_id)).await?;
+    let parsed = mapping
+        .validate()?
+        .parse(&file, &Calendar::new(&settings))?;
+
+    if params.dry_run {
+        return Ok(HttpResponse::Ok().json(parsed));
+    }
+
+    if !parsed.errors.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(parsed));
+    }
+
+    let created = pool
+        .execute(ImportRecords::new(&parsed.rows, user_id))
+        .await?;
+
+    let tags = parsed
+        .rows
+        .into_iter()
+        .flat_map(|row| row.data.tags)
+        .collect();
+    let degraded = increment_tags(user_id, tags, &redis).await.is_err();
+    let response = HttpResponse::Ok().json(json!({ "created": created }));
 
-    Ok(HttpResponse::Ok().json(json!({ "id": id })))
+    Ok(with_degraded_mode(response, degraded))
 }
 
 #[put("/record-detail/{id}/")]
@@ -67,10 +138,29 @@ async fn update(
     pool.execute(UpdateRecord::new(record.id, &data, user_id))
         .await?;
 
-    decrement_tags(user_id, record.tags, &redis).await?;
-    increment_tags(user_id, data.tags, &redis).await?;
+    let decremented = decrement_tags(user_id, record.tags, &redis).await.is_ok();
+    let incremented = increment_tags(user_id, data.tags, &redis).await.is_ok();
+    let response = HttpResponse::Ok().json("");
+
+    Ok(with_degraded_mode(response, !(decremented && incremented)))
+}
+
+#[delete("/record-detail/{id}/")]
+async fn destroy(
+    user_id: UserId,
+    record_id: Path<i32>,
+    pool: web::Data<ConnectionPool>,
+    redis: web::Data<Redis>,
+) -> Result<HttpResponse> {
+    let record_id = record_id.into_inner();
+    let record = pool.execute(DeleteRecord::new(record_id, user_id)).await?;
 
-    Ok(HttpResponse::Ok().json(""))
+    let degraded = decrement_tags(user_id, record.tags, &redis).await.is_err();
+
+    Ok(with_degraded_mode(
+        HttpResponse::NoContent().finish(),
+        degraded,
+    ))
 }
 
 pub mod service {
@@ -82,11 +172,17 @@ pub mod service {
     impl HttpServiceFactory for Service {
         fn register(self, config: &mut actix_web::dev::AppService) {
             HttpServiceFactory::register(index, config);
+            HttpServiceFactory::register(export, config);
             HttpServiceFactory::register(create, config);
+            HttpServiceFactory::register(import, config);
             HttpServiceFactory::register(update, config);
+            HttpServiceFactory::register(destroy, config);
         }
     }
 }
 
+mod csv_export;
+mod csv_import;
+
 #[cfg(test)]
 mod tests;
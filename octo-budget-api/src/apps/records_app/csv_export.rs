@@ -0,0 +1,123 @@
+use actix_web::{web, web::Bytes, Error};
+use futures::stream::{self, Stream};
+
+use crate::apps::forms::record_filters::Filters;
+use crate::db::{calendar::Calendar, models::Record, queries::GetRecordsBatch, ConnectionPool};
+use crate::errors::Error as AppError;
+
+const BATCH_SIZE: i64 = 500;
+const HEADER: [&str; 7] = [
+    "id",
+    "created_at",
+    "transaction_type",
+    "amount",
+    "currency",
+    "tags",
+    "comment",
+];
+/// `created_at` is written in the user's timezone, with the UTC offset
+const DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f%:z";
+/// spreadsheets evaluate cells starting with these as formulas
+const FORMULA_PREFIXES: [char; 4] = ['=', '+', '-', '@'];
+
+struct Cursor {
+    pool: web::Data<ConnectionPool>,
+    user_id: i32,
+    filters: Filters,
+    calendar: Calendar,
+    after_id: Option<i32>,
+}
+
+/// CSV body with all records of the user matching `filters`, loaded from the
+/// database batch by batch while the response is being sent.
+pub(super) fn records_csv(
+    pool: web::Data<ConnectionPool>,
+    user_id: i32,
+    filters: Filters,
+    calendar: Calendar,
+) -> impl Stream<Item = Result<Bytes, Error>> {
+    let cursor = Cursor {
+        pool,
+        user_id,
+        filters,
+        calendar,
+        after_id: None,
+    };
+
+    stream::try_unfold(Some(cursor), |cursor| async move {
+        let mut cursor = match cursor {
+            Some(cursor) => cursor,
+            None => return Ok(None),
+        };
+
+        let query = GetRecordsBatch {
+            user_id: cursor.user_id,
+            filters: cursor.filters.clone(),
+            after_id: cursor.after_id,
+            limit: BATCH_SIZE,
+        };
+        let records = cursor.pool.execute(query).await?;
+        let chunk = write_csv(&records, &cursor.calendar, cursor.after_id.is_none())?;
+
+        let next = if (records.len() as i64) < BATCH_SIZE {
+            None
+        } else {
+            cursor.after_id = records.last().map(|record| record.id);
+            Some(cursor)
+        };
+
+        Ok::<_, Error>(Some((chunk, next)))
+    })
+}
+
+fn write_csv(
+    records: &[Record],
+    calendar: &Calendar,
+    with_header: bool,
+) -> Result<Bytes, AppError> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+
+    if with_header {
+        writer.write_record(HEADER).map_err(into_app_error)?;
+    }
+
+    for record in records {
+        writer
+            .write_record(&[
+                record.id.to_string(),
+                calendar
+                    .local(record.created_at)
+                    .format(DATETIME_FORMAT)
+                    .to_string(),
+                record.transaction_type.clone(),
+                record.amount.to_string(),
+                record.amount_currency.clone(),
+                escape_formula(record.tags.join(",")),
+                escape_formula(record.comment.clone().unwrap_or_default()),
+            ])
+            .map_err(into_app_error)?;
+    }
+
+    let data = writer
+        .into_inner()
+        .map_err(|err| AppError::Unknown(failure::err_msg(err.to_string())))?;
+
+    Ok(Bytes::from(data))
+}
+
+/// Free text typed by the user is written as text, so opening the export in a
+/// spreadsheet does not run it as a formula.
+fn escape_formula(cell: String) -> String {
+    if cell.starts_with(&FORMULA_PREFIXES[..]) {
+        format!("'{}", cell)
+    } else {
+        cell
+    }
+}
+
+fn into_app_error(err: csv::Error) -> AppError {
+    AppError::Unknown(err.into())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,66 @@
+use super::*;
+use crate::db::{builders::RecordBuilder, models::UserSettings};
+
+fn record(tags: Vec<&str>, comment: &str) -> Record {
+    RecordBuilder {
+        id: 1,
+        amount: bigdecimal::BigDecimal::from(12),
+        ..Default::default()
+    }
+    .transaction_type("EXP")
+    .currency("CAD")
+    .tags(tags)
+    .comment(comment)
+    .created_at(chrono::NaiveDate::from_ymd(2020, 3, 1).and_hms(10, 30, 0))
+    .finish()
+}
+
+fn calendar(timezone: &str) -> Calendar {
+    Calendar::new(&UserSettings {
+        timezone: timezone.to_string(),
+        ..UserSettings::defaults(1)
+    })
+}
+
+fn body(records: &[Record]) -> String {
+    let data = write_csv(records, &calendar("UTC"), false).expect("Failed to write csv");
+
+    String::from_utf8_lossy(&data).to_string()
+}
+
+#[test]
+fn formulas_in_free_text_are_escaped() {
+    let records = [
+        record(vec!["=cmd"], "+1 refund"),
+        record(vec!["-food"], "@SUM(A1:A2)"),
+    ];
+
+    assert_eq!(
+        "1,2020-03-01T10:30:00+00:00,EXP,12,CAD,'=cmd,'+1 refund\n\
+         1,2020-03-01T10:30:00+00:00,EXP,12,CAD,'-food,'@SUM(A1:A2)\n",
+        body(&records)
+    );
+}
+
+#[test]
+fn plain_text_is_written_as_is() {
+    let records = [record(vec!["food", "lunch"], "pizza = 2 slices")];
+
+    assert_eq!(
+        "1,2020-03-01T10:30:00+00:00,EXP,12,CAD,\"food,lunch\",pizza = 2 slices\n",
+        body(&records)
+    );
+}
+
+#[test]
+fn created_at_is_in_user_timezone() {
+    let records = [record(vec![], "")];
+    let data =
+        write_csv(&records, &calendar("America/Vancouver"), true).expect("Failed to write csv");
+
+    assert_eq!(
+        "id,created_at,transaction_type,amount,currency,tags,comment\n\
+         1,2020-03-01T02:30:00-08:00,EXP,12,CAD,,\n",
+        String::from_utf8_lossy(&data)
+    );
+}
@@ -0,0 +1,50 @@
+use actix_multipart::Multipart;
+use actix_web::Error;
+use futures::StreamExt;
+use serde_json::{Map, Value};
+
+use crate::apps::forms::record_import::{Mapping, ValidationErrors};
+
+/// of the whole upload, the file and all other fields together
+const MAX_UPLOAD_SIZE: usize = 5 * 1024 * 1024;
+
+/// Reads the uploaded CSV from the `file` field, all other fields are treated
+/// as the column mapping.
+pub(super) async fn read_upload(mut payload: Multipart) -> Result<(Mapping, Vec<u8>), Error> {
+    let mut file = None;
+    let mut fields = Map::new();
+    let mut size = 0;
+
+    while let Some(field) = payload.next().await {
+        let mut field = field?;
+        let name = field
+            .content_disposition()
+            .and_then(|disposition| disposition.get_name().map(String::from))
+            .unwrap_or_default();
+
+        let mut data = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk?;
+            size += chunk.len();
+
+            if size > MAX_UPLOAD_SIZE {
+                return Err(ValidationErrors::file("The file is too large.").into());
+            }
+
+            data.extend_from_slice(&chunk);
+        }
+
+        if name == "file" {
+            file = Some(data);
+        } else {
+            let value = String::from_utf8_lossy(&data).into_owned();
+            fields.insert(name, Value::String(value));
+        }
+    }
+
+    let file = file.ok_or_else(|| ValidationErrors::file("No file was submitted."))?;
+    let mapping = serde_json::from_value(Value::Object(fields))
+        .map_err(|err| ValidationErrors::file(&err.to_string()))?;
+
+    Ok((mapping, file))
+}
@@ -1,7 +1,8 @@
 use super::service::Service;
 use crate::{
     await_test_server,
-    db::builders::UserBuilder,
+    db::builders::{RecordBuilder, UserBuilder},
+    tags_vec,
     tests::{self, setup_env, RequestJwtAuthExt as _},
 };
 use actix_web::{
@@ -124,6 +125,44 @@ async fn create_happy_path() {
     assert_eq!(vec!["foo", "bar"], updated_record.tags);
 }
 
+#[actix_rt::test]
+async fn create_when_tag_counters_cannot_be_updated() {
+    use crate::{config::user_tags_redis_key, redis::Redis};
+
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+    let redis = Redis::connect().await.expect("Failed to connect to redis");
+
+    let user = session.create_user(UserBuilder::default());
+
+    // counters cannot be incremented in a key of the wrong type
+    let _: () = redis::cmd("set")
+        .arg(user_tags_redis_key(user.id))
+        .arg("foo")
+        .query_async(&mut redis.connection().unwrap())
+        .await
+        .unwrap();
+
+    let payload = json!({
+        "amount": {"amount": 10, "currency": { "code": "CAD", "name": "Canadian Dollar" }},
+        "transaction_type": "EXP",
+        "tags": ["foo"],
+    });
+    let request = TestRequest::with_uri("/record-detail/")
+        .method(Method::POST)
+        .jwt_auth(user.id)
+        .set_json(&payload)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+    assert_eq!("redis", response.headers().get("x-degraded-mode").unwrap());
+    assert_eq!(1, session.count_records());
+}
+
 #[actix_rt::test]
 async fn update_happy_path() {
     setup_env();
@@ -163,3 +202,483 @@ async fn update_happy_path() {
     assert_eq!("INC", updated_record.transaction_type);
     assert_eq!(vec!["foo"], updated_record.tags);
 }
+
+#[actix_rt::test]
+async fn delete_requires_auth() {
+    setup_env();
+
+    let mut service = await_test_server!(Service);
+    let request = TestRequest::with_uri("/record-detail/123/")
+        .method(Method::DELETE)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::UNAUTHORIZED,
+        response.status(),
+        "wrong status code"
+    );
+}
+
+#[actix_rt::test]
+async fn delete_not_found() {
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+
+    let request = TestRequest::with_uri("/record-detail/123/")
+        .method(Method::DELETE)
+        .jwt_auth(user.id)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::NOT_FOUND,
+        response.status(),
+        "wrong status code"
+    );
+}
+
+#[actix_rt::test]
+async fn delete_happy_path() {
+    use crate::redis::{
+        helpers::{increment_tags, read_redis_tags},
+        Redis,
+    };
+
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+    let redis = Redis::connect().await.expect("Failed to connect to redis");
+
+    let user = session.create_user(UserBuilder::default());
+    let record = session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .transaction_type("EXP")
+            .tags(vec!["foo", "bar"])
+            .finish(),
+    );
+
+    // "foo" was used twice and "bar" only once, by the record being deleted
+    increment_tags(user.id.into(), tags_vec!["foo", "bar"], &redis)
+        .await
+        .expect("failed to increment tags");
+    increment_tags(user.id.into(), tags_vec!["foo"], &redis)
+        .await
+        .expect("failed to increment tags");
+
+    let request = TestRequest::with_uri(&format!("/record-detail/{}/", record.id))
+        .method(Method::DELETE)
+        .jwt_auth(user.id)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::NO_CONTENT,
+        response.status(),
+        "wrong status code"
+    );
+    assert_eq!(0, session.count_records());
+
+    let redis_tags = read_redis_tags(user.id.into(), &redis)
+        .await
+        .expect("failed to read tags");
+
+    assert_eq!(tags_vec!["foo"], redis_tags);
+}
+
+#[actix_rt::test]
+async fn export_requires_auth() {
+    setup_env();
+
+    let mut service = await_test_server!(Service);
+    let request = TestRequest::with_uri("/export.csv").to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::UNAUTHORIZED,
+        response.status(),
+        "wrong status code"
+    );
+}
+
+#[actix_rt::test]
+async fn export_happy_path() {
+    use chrono::NaiveDate;
+
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    let record = RecordBuilder {
+        amount_currency: "CAD".to_string(),
+        ..Default::default()
+    }
+    .user_id(user.id)
+    .amount(12.5)
+    .created_at(NaiveDate::from_ymd(2020, 3, 1).and_hms(10, 30, 0));
+
+    let food = session.create_record(
+        record
+            .clone()
+            .transaction_type("EXP")
+            .tags(vec!["food", "lunch"])
+            .comment("pizza, large")
+            .finish(),
+    );
+    let salary = session.create_record(record.transaction_type("INC").finish());
+
+    let request = TestRequest::with_uri("/export.csv")
+        .jwt_auth(user.id)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+    assert_eq!(
+        "text/csv; charset=utf-8",
+        response.headers().get("content-type").unwrap()
+    );
+
+    let response_body = read_body(response).await;
+
+    assert_eq!(
+        format!(
+            "id,created_at,transaction_type,amount,currency,tags,comment\n\
+             {},2020-03-01T10:30:00+00:00,EXP,12.50,CAD,\"food,lunch\",\"pizza, large\"\n\
+             {},2020-03-01T10:30:00+00:00,INC,12.50,CAD,,\n",
+            food.id, salary.id
+        ),
+        String::from_utf8_lossy(&response_body)
+    );
+}
+
+#[actix_rt::test]
+async fn export_with_filters() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    let record = RecordBuilder::default().user_id(user.id).amount(1.0);
+
+    session.create_record(record.clone().transaction_type("EXP").finish());
+    let salary = session.create_record(record.transaction_type("INC").finish());
+
+    let request = TestRequest::with_uri("/export.csv?transaction_type=INC")
+        .jwt_auth(user.id)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let response_body = read_body(response).await;
+    let lines: Vec<String> = String::from_utf8_lossy(&response_body)
+        .lines()
+        .map(String::from)
+        .collect();
+
+    assert_eq!(2, lines.len());
+    assert!(lines[1].starts_with(&format!("{},", salary.id)));
+}
+
+fn multipart_import(uri: &str, user_id: i32, file: &str) -> actix_http::Request {
+    let fields = [
+        ("date_column", "Date"),
+        ("amount_column", "Amount"),
+        ("description_column", "Description"),
+        ("tags", "bank"),
+    ];
+
+    multipart_import_with_fields(uri, user_id, &fields, file)
+}
+
+fn multipart_import_with_fields(
+    uri: &str,
+    user_id: i32,
+    fields: &[(&str, &str)],
+    file: &str,
+) -> actix_http::Request {
+    let boundary = "----octo-budget-boundary";
+    let mut body = String::new();
+
+    for (name, value) in fields {
+        body.push_str(&format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+            boundary, name, value
+        ));
+    }
+
+    body.push_str(&format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"statement.csv\"\r\n\
+         Content-Type: text/csv\r\n\r\n{}\r\n--{}--\r\n",
+        boundary, file, boundary
+    ));
+
+    TestRequest::with_uri(uri)
+        .method(Method::POST)
+        .jwt_auth(user_id)
+        .header(
+            "content-type",
+            format!("multipart/form-data; boundary={}", boundary),
+        )
+        .set_payload(body)
+        .to_request()
+}
+
+#[actix_rt::test]
+async fn import_requires_auth() {
+    setup_env();
+
+    let mut service = await_test_server!(Service);
+    let request = TestRequest::with_uri("/import")
+        .method(Method::POST)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::UNAUTHORIZED,
+        response.status(),
+        "wrong status code"
+    );
+}
+
+#[actix_rt::test]
+async fn import_dry_run() {
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    let file = "Date,Description,Amount\n2020-03-01,Groceries,-45.10\n2020-03-02,Coffee,abc\n";
+
+    let request = multipart_import("/import?dry_run=true", user.id, file);
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    assert_eq!(
+        json!(1),
+        json!(response_body["rows"].as_array().unwrap().len())
+    );
+    assert_eq!(json!("Groceries"), response_body["rows"][0]["comment"]);
+    assert_eq!(json!("EXP"), response_body["rows"][0]["transaction_type"]);
+    assert_eq!(
+        json!([{"line": 3, "errors": {"amount": ["A valid number is required."]}}]),
+        response_body["errors"]
+    );
+    assert_eq!(0, session.count_records());
+}
+
+#[actix_rt::test]
+async fn import_with_invalid_rows() {
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    let file = "Date,Description,Amount\n2020-03-01,Groceries,-45.10\n2020-03-02,Coffee,abc\n";
+
+    let request = multipart_import("/import", user.id, file);
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::BAD_REQUEST,
+        response.status(),
+        "wrong status code"
+    );
+    assert_eq!(0, session.count_records());
+}
+
+#[actix_rt::test]
+async fn import_limits_size_of_whole_upload() {
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    // every field alone is within the limit
+    let padding = "x".repeat(3 * 1024 * 1024);
+    let fields = [
+        ("date_column", "Date"),
+        ("amount_column", "Amount"),
+        ("padding_1", padding.as_str()),
+        ("padding_2", padding.as_str()),
+    ];
+    let file = "Date,Description,Amount\n2020-03-01,Groceries,-45.10\n";
+
+    let request = multipart_import_with_fields("/import", user.id, &fields, file);
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::BAD_REQUEST,
+        response.status(),
+        "wrong status code"
+    );
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    assert_eq!(json!({"file": ["The file is too large."]}), response_body);
+    assert_eq!(0, session.count_records());
+}
+
+#[actix_rt::test]
+async fn import_happy_path() {
+    use crate::redis::{helpers::read_redis_tags, Redis};
+
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+    let redis = Redis::connect().await.expect("Failed to connect to redis");
+
+    let user = session.create_user(UserBuilder::default());
+    let file = "Date,Description,Amount\n2020-03-01,Groceries,-45.10\n2020-03-02,Salary,1000\n";
+
+    let request = multipart_import("/import", user.id, file);
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    assert_eq!(json!({"created": 2}), response_body);
+    assert_eq!(2, session.count_records());
+
+    let tags = read_redis_tags(user.id.into(), &redis)
+        .await
+        .expect("Failed to read tags");
+
+    assert_eq!(tags_vec!["bank"], tags);
+}
+
+#[actix_rt::test]
+async fn index_serializes_record_currency() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    session.create_record(
+        RecordBuilder {
+            amount_currency: "EUR".to_string(),
+            ..Default::default()
+        }
+        .user_id(user.id)
+        .transaction_type("EXP")
+        .amount(10.0)
+        .finish(),
+    );
+
+    let request = TestRequest::with_uri("/record-detail/")
+        .jwt_auth(user.id)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    assert_eq!(
+        json!({"code": "EUR", "name": "Euro"}),
+        response_body["results"][0]["amount"]["currency"]
+    );
+}
+
+#[actix_rt::test]
+async fn index_with_decimal_money_format() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .transaction_type("EXP")
+            .amount(10.1)
+            .currency("CAD")
+            .finish(),
+    );
+
+    let request = TestRequest::with_uri("/record-detail/")
+        .jwt_auth(user.id)
+        .header("X-Money-Format", "decimal")
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    assert_eq!(
+        json!({"amount": "10.10", "currency": "CAD"}),
+        response_body["results"][0]["amount"]
+    );
+}
+
+#[actix_rt::test]
+async fn create_with_decimal_amount() {
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+
+    let payload = json!({
+        "amount": {"amount": "0.30", "currency": "CAD"},
+        "transaction_type": "EXP",
+        "tags": [],
+    });
+
+    let request = TestRequest::with_uri("/record-detail/")
+        .method(Method::POST)
+        .jwt_auth(user.id)
+        .set_json(&payload)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    let new_record_id = response_body["id"].as_i64().unwrap() as i32;
+    let record = session.find_record(new_record_id);
+
+    assert_eq!("0.30", record.amount.to_string());
+    assert_eq!("CAD", record.amount_currency);
+}
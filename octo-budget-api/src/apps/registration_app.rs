@@ -0,0 +1,53 @@
+use actix_web::{
+    post,
+    web::{self, Json},
+    HttpResponse, Result,
+};
+use serde_json::json;
+
+use super::auth_app::utils::generate_token;
+use super::forms::registration::{Form, ValidationErrors};
+use crate::config;
+use crate::db::{
+    queries::{CreateUser, IssueRefreshToken},
+    ConnectionPool,
+};
+use crate::redis::{token_denylist::token_generation, Redis};
+
+#[post("/register")]
+async fn register(
+    form: Json<Form>,
+    pool: web::Data<ConnectionPool>,
+    redis: web::Data<Redis>,
+) -> Result<HttpResponse> {
+    if !config::is_registration_enabled() {
+        return Ok(HttpResponse::Forbidden().json(json!({ "detail": "Registration is disabled." })));
+    }
+
+    let data = form.into_inner().validate()?;
+    let user = pool
+        .execute(CreateUser::new(&data))
+        .await?
+        .ok_or_else(ValidationErrors::username_taken)?;
+
+    let refresh = pool.execute(IssueRefreshToken::new(user.id)).await?;
+    let generation = token_generation(user.id.into(), &redis).await;
+
+    Ok(HttpResponse::Ok().json(generate_token(user.id, generation, refresh)))
+}
+
+pub mod service {
+    use super::*;
+    use actix_web::dev::HttpServiceFactory;
+
+    pub struct Service;
+
+    impl HttpServiceFactory for Service {
+        fn register(self, config: &mut actix_web::dev::AppService) {
+            HttpServiceFactory::register(register, config)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
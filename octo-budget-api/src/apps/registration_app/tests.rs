@@ -0,0 +1,163 @@
+use super::service::Service;
+use crate::{
+    await_test_server,
+    db::builders::UserBuilder,
+    tests::{setup_env, DbSession},
+};
+use actix_web::{
+    http::{Method, StatusCode},
+    test::{call_service, read_body, TestRequest},
+};
+use serde_json::{json, Value};
+
+const REGISTRATION_ENABLED: &str = "REGISTRATION_ENABLED";
+
+fn register_request(body: Value) -> actix_http::Request {
+    TestRequest::with_uri("/register")
+        .method(Method::POST)
+        .set_json(&body)
+        .to_request()
+}
+
+fn valid_payload() -> Value {
+    json!({
+        "username": "john",
+        "email": "john@example.com",
+        "password": "correct horse",
+    })
+}
+
+#[actix_rt::test]
+async fn forbidden_when_registration_is_disabled() {
+    setup_env();
+    std::env::remove_var(REGISTRATION_ENABLED);
+
+    let session = DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let response = call_service(&mut service, register_request(valid_payload())).await;
+
+    assert_eq!(
+        StatusCode::FORBIDDEN,
+        response.status(),
+        "wrong status code"
+    );
+    assert_eq!(0, session.count_users());
+}
+
+#[actix_rt::test]
+async fn forbidden_when_registration_is_set_to_false() {
+    setup_env();
+    std::env::set_var(REGISTRATION_ENABLED, "false");
+
+    let session = DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let response = call_service(&mut service, register_request(valid_payload())).await;
+
+    assert_eq!(
+        StatusCode::FORBIDDEN,
+        response.status(),
+        "wrong status code"
+    );
+    assert_eq!(0, session.count_users());
+
+    std::env::remove_var(REGISTRATION_ENABLED);
+}
+
+#[actix_rt::test]
+async fn register_happy_path() {
+    setup_env();
+    std::env::set_var(REGISTRATION_ENABLED, "true");
+
+    let session = DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let response = call_service(&mut service, register_request(valid_payload())).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    assert!(response_body["token"].is_string(), "no token in response");
+
+    let user = session.find_user_by_name("john");
+    assert!(!user.is_superuser);
+    assert_eq!(
+        Ok(true),
+        djangohashers::check_password("correct horse", &user.password)
+    );
+
+    std::env::remove_var(REGISTRATION_ENABLED);
+}
+
+#[actix_rt::test]
+async fn register_with_invalid_data() {
+    setup_env();
+    std::env::set_var(REGISTRATION_ENABLED, "true");
+
+    let _session = DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let request = register_request(json!({
+        "username": "john",
+        "email": "john",
+        "password": "123",
+    }));
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::BAD_REQUEST,
+        response.status(),
+        "wrong status code"
+    );
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    assert_eq!(
+        json!({
+            "email": ["Enter a valid email address."],
+            "password": [
+                "This password is too short. It must contain at least 8 characters.",
+                "This password is entirely numeric.",
+            ],
+        }),
+        response_body
+    );
+
+    std::env::remove_var(REGISTRATION_ENABLED);
+}
+
+#[actix_rt::test]
+async fn register_with_taken_username() {
+    setup_env();
+    std::env::set_var(REGISTRATION_ENABLED, "true");
+
+    let session = DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    session.create_user(UserBuilder::default().username("john"));
+
+    let response = call_service(&mut service, register_request(valid_payload())).await;
+
+    assert_eq!(
+        StatusCode::BAD_REQUEST,
+        response.status(),
+        "wrong status code"
+    );
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    assert_eq!(
+        json!({ "username": ["A user with that username already exists."] }),
+        response_body
+    );
+
+    std::env::remove_var(REGISTRATION_ENABLED);
+}
@@ -0,0 +1,52 @@
+use actix_web::{
+    get,
+    web::{self, Query},
+    HttpResponse, Result,
+};
+use octo_budget_lib::auth_token::UserId;
+
+use super::{forms::report::Params, money_format::MoneyFormat};
+use crate::db::{
+    queries::{GetTagReport, GetUserSettings},
+    ConnectionPool,
+};
+
+#[get("/by-tag")]
+async fn by_tag(
+    user_id: UserId,
+    params: Query<Params>,
+    money_format: MoneyFormat,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let settings = pool.execute(GetUserSettings::new(user_id)).await?;
+    let params = params.into_inner().validate(&settings)?;
+
+    let query = GetTagReport {
+        user_id: user_id.into(),
+        transaction_type: params.transaction_type,
+        currency: params.currency,
+        from: params.from,
+        to: params.to,
+        level: params.level,
+    };
+
+    let report = pool.execute(query).await?;
+
+    Ok(money_format.json(&report))
+}
+
+pub mod service {
+    use super::*;
+    use actix_web::dev::HttpServiceFactory;
+
+    pub struct Service;
+
+    impl HttpServiceFactory for Service {
+        fn register(self, config: &mut actix_web::dev::AppService) {
+            HttpServiceFactory::register(by_tag, config);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
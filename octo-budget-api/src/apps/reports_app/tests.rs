@@ -0,0 +1,147 @@
+use super::service::Service;
+use crate::{
+    await_test_server,
+    db::builders::{RecordBuilder, UserBuilder},
+    tests::{self, setup_env, RequestJwtAuthExt as _},
+};
+use actix_web::{
+    http::StatusCode,
+    test::{call_service, read_body, TestRequest},
+};
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+
+#[actix_rt::test]
+async fn by_tag_requires_auth() {
+    setup_env();
+
+    let mut service = await_test_server!(Service);
+    let request = TestRequest::with_uri("/by-tag").to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::UNAUTHORIZED,
+        response.status(),
+        "wrong status code"
+    );
+}
+
+#[actix_rt::test]
+async fn by_tag_with_invalid_params() {
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    let request = TestRequest::with_uri("/by-tag?transaction_type=FOO")
+        .jwt_auth(user.id)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::BAD_REQUEST,
+        response.status(),
+        "wrong status code"
+    );
+}
+
+#[actix_rt::test]
+async fn by_tag_happy_path() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    let record = RecordBuilder::default()
+        .user_id(user.id)
+        .transaction_type("EXP")
+        .tags(vec!["food"]);
+
+    session.create_record(
+        record
+            .clone()
+            .amount(20.0)
+            .currency("CAD")
+            .created_at(NaiveDate::from_ymd(2020, 2, 10).and_hms(9, 0, 0))
+            .finish(),
+    );
+    session.create_record(
+        record
+            .clone()
+            .amount(10.0)
+            .currency("EUR")
+            .created_at(NaiveDate::from_ymd(2020, 1, 10).and_hms(9, 0, 0))
+            .finish(),
+    );
+    session.create_record(
+        record
+            .amount(10.0)
+            .currency("USD")
+            .created_at(NaiveDate::from_ymd(2020, 1, 10).and_hms(9, 0, 0))
+            .finish(),
+    );
+    session.create_exchange_rate(NaiveDate::from_ymd(2020, 1, 10), "EUR", "CAD", 1.5);
+
+    let request = TestRequest::with_uri("/by-tag?from=2020-01-01&to=2020-02-29")
+        .jwt_auth(user.id)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    assert_eq!(
+        json!({
+            "currency": "CAD",
+            "months": ["2020-01", "2020-02"],
+            "series": [{"tag": "food", "data": [15.0, 20.0]}],
+            "missing_rates": [{"currency": "USD", "date": "2020-01-10"}],
+        }),
+        response_body
+    );
+}
+
+#[actix_rt::test]
+async fn by_tag_with_decimal_money_format() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .transaction_type("EXP")
+            .tags(vec!["food"])
+            .amount(10.1)
+            .currency("CAD")
+            .created_at(NaiveDate::from_ymd(2020, 1, 10).and_hms(9, 0, 0))
+            .finish(),
+    );
+
+    let request = TestRequest::with_uri("/by-tag?from=2020-01-01&to=2020-02-29")
+        .jwt_auth(user.id)
+        .header("X-Money-Format", "decimal")
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    assert_eq!(
+        json!([{"tag": "food", "data": ["10.10", "0.00"]}]),
+        response_body["series"]
+    );
+}
@@ -1,12 +1,23 @@
-use actix_web::{get, put, web, web::Json, HttpResponse, Result};
+use actix_web::{
+    delete, get, post, put,
+    web::{self, Json, Path, Query},
+    HttpResponse, Result,
+};
+use log::error;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 
-use super::helpers::sort_tags;
+use super::forms::tags::{MergeData, MergeForm, RebuildForm, RenameForm, SuggestParams};
+use super::helpers::{sort_tags, with_degraded_mode};
+use crate::config::{is_tags_frecency, TAGS_HALF_LIFE_DAYS};
 use crate::db::{
-    queries::{GetUserTags, SetUserTags},
+    queries::{FindUser, GetTagScores, GetUserTags, ReplaceTags, SetUserTags, SuggestTags},
     ConnectionPool,
 };
-use crate::redis::{helpers::read_redis_tags, Redis};
+use crate::redis::{
+    helpers::{move_tags, read_redis_tags},
+    tag_scores, Redis,
+};
 use octo_budget_lib::auth_token::UserId;
 
 #[derive(Serialize, Deserialize, Default, Debug)]
@@ -19,16 +30,75 @@ fn ordered_tags(user_tags: &[String], redis_tags: &[String]) -> Data {
     Data { tags }
 }
 
+/// Tags in the order of use, `true` if Redis is unavailable and they had to be
+/// counted from records instead.
+///
+/// With frecency uses are always counted from records, as their weight
+/// changes over time.
+async fn used_tags(
+    user_id: UserId,
+    redis: &Redis,
+    pool: &ConnectionPool,
+) -> Result<(Vec<String>, bool)> {
+    if is_tags_frecency() {
+        let tags = counted_tags(user_id, Some(*TAGS_HALF_LIFE_DAYS), pool).await?;
+        return Ok((tags, false));
+    }
+
+    match read_redis_tags(user_id, redis).await {
+        Ok(tags) => Ok((tags, false)),
+        Err(e) => {
+            error!("Failed to read tags, counting them from records: {}", e);
+            Ok((counted_tags(user_id, None, pool).await?, true))
+        }
+    }
+}
+
+async fn counted_tags(
+    user_id: UserId,
+    half_life_days: Option<f64>,
+    pool: &ConnectionPool,
+) -> Result<Vec<String>> {
+    let query = GetTagScores {
+        user_id: Some(user_id.into()),
+        half_life_days,
+    };
+    let tags = pool
+        .execute(query)
+        .await?
+        .into_iter()
+        .flat_map(|user| user.scores)
+        .map(|(tag, _score)| tag)
+        .collect();
+
+    Ok(tags)
+}
+
 #[get("/")]
 async fn index(
     user_id: UserId,
     redis: web::Data<Redis>,
     pool: web::Data<ConnectionPool>,
 ) -> Result<HttpResponse> {
-    let redis_tags = read_redis_tags(user_id, &redis).await?;
+    let (redis_tags, degraded) = used_tags(user_id, &redis, &pool).await?;
     let user_tags = pool.execute(GetUserTags::new(user_id)).await?;
+    let response = HttpResponse::Ok().json(ordered_tags(&user_tags, &redis_tags));
 
-    Ok(HttpResponse::Ok().json(ordered_tags(&user_tags, &redis_tags)))
+    Ok(with_degraded_mode(response, degraded))
+}
+
+/// The most likely tags for a new record, for the quick entry form.
+#[get("/suggest")]
+async fn suggest(
+    user_id: UserId,
+    params: Query<SuggestParams>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let SuggestParams { prefix, comment } = params.into_inner();
+    let query = SuggestTags::new(user_id, &prefix, &comment, *TAGS_HALF_LIFE_DAYS);
+    let tags = pool.execute(query).await?;
+
+    Ok(HttpResponse::Ok().json(Data { tags }))
 }
 
 #[put("/")]
@@ -39,10 +109,97 @@ async fn update(
     pool: web::Data<ConnectionPool>,
 ) -> Result<HttpResponse> {
     let tags = data.into_inner().tags;
-    let redis_tags = read_redis_tags(user_id, &redis).await?;
+    let (redis_tags, degraded) = used_tags(user_id, &redis, &pool).await?;
     let user_tags = pool.execute(SetUserTags::new(user_id, tags)).await?;
+    let response = HttpResponse::Ok().json(ordered_tags(&*user_tags, &redis_tags));
+
+    Ok(with_degraded_mode(response, degraded))
+}
+
+async fn merge_tags(
+    user_id: UserId,
+    data: MergeData,
+    redis: &Redis,
+    pool: &ConnectionPool,
+) -> Result<HttpResponse> {
+    let MergeData { tags, into } = data;
+    let user_tags = pool
+        .execute(ReplaceTags::merge(user_id, tags.clone(), into.clone()))
+        .await?;
+
+    let moved = move_tags(user_id, &tags, Some(&into), redis).await.is_ok();
+    let (redis_tags, degraded) = used_tags(user_id, redis, pool).await?;
+    let response = HttpResponse::Ok().json(ordered_tags(&user_tags, &redis_tags));
+
+    Ok(with_degraded_mode(response, degraded || !moved))
+}
+
+#[post("/rename/")]
+async fn rename(
+    user_id: UserId,
+    form: Json<RenameForm>,
+    redis: web::Data<Redis>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let data = form.into_inner().validate()?;
 
-    Ok(HttpResponse::Ok().json(ordered_tags(&*user_tags, &redis_tags)))
+    merge_tags(user_id, data, &redis, &pool).await
+}
+
+#[post("/merge/")]
+async fn merge(
+    user_id: UserId,
+    form: Json<MergeForm>,
+    redis: web::Data<Redis>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let data = form.into_inner().validate()?;
+
+    merge_tags(user_id, data, &redis, &pool).await
+}
+
+/// Deletes the tag with all its descendants. A nested tag is deleted by its
+/// path, e.g. `/food/groceries/`.
+#[delete("/{name:.+}/")]
+async fn destroy(
+    user_id: UserId,
+    name: Path<String>,
+    redis: web::Data<Redis>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let name = name.into_inner();
+    let user_tags = pool
+        .execute(ReplaceTags::delete(user_id, name.clone()))
+        .await?;
+
+    let moved = move_tags(user_id, &[name], None, &redis).await.is_ok();
+    let (redis_tags, degraded) = used_tags(user_id, &redis, &pool).await?;
+    let response = HttpResponse::Ok().json(ordered_tags(&user_tags, &redis_tags));
+
+    Ok(with_degraded_mode(response, degraded || !moved))
+}
+
+/// Recomputes the order of tags from records, in case Redis lost it or drifted
+/// away. Only staff can do it, for a single user or for everybody.
+#[post("/rebuild/")]
+async fn rebuild(
+    user_id: UserId,
+    form: Json<RebuildForm>,
+    redis: web::Data<Redis>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let current_user = pool.execute(FindUser::new(user_id)).await?;
+
+    if !current_user.is_staff {
+        return Ok(HttpResponse::Forbidden().json(json!({
+            "detail": "You do not have permission to perform this action."
+        })));
+    }
+
+    let (for_user_id, half_life_days) = form.into_inner().validate()?;
+    let users = tag_scores::rebuild(for_user_id, half_life_days, &pool, &redis).await?;
+
+    Ok(HttpResponse::Ok().json(json!({ "users": users })))
 }
 
 pub mod service {
@@ -54,7 +211,15 @@ pub mod service {
     impl HttpServiceFactory for Service {
         fn register(self, config: &mut actix_web::dev::AppService) {
             HttpServiceFactory::register(index, config);
+            HttpServiceFactory::register(suggest, config);
             HttpServiceFactory::register(update, config);
+            HttpServiceFactory::register(rebuild, config);
+            HttpServiceFactory::register(rename, config);
+            HttpServiceFactory::register(merge, config);
+            HttpServiceFactory::register(destroy, config);
         }
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,425 @@
+use super::service::Service;
+use crate::{
+    await_test_server,
+    db::builders::{RecordBuilder, UserBuilder},
+    redis::{
+        helpers::{decrement_tags, increment_tags, read_redis_tags},
+        Redis,
+    },
+    tags_vec,
+    tests::{self, setup_env, RequestJwtAuthExt as _},
+};
+use actix_web::{
+    http::{Method, StatusCode},
+    test::{call_service, read_body, TestRequest},
+};
+use serde_json::{json, Value};
+
+#[actix_rt::test]
+async fn rename_requires_auth() {
+    setup_env();
+
+    let mut service = await_test_server!(Service);
+    let request = TestRequest::with_uri("/rename/")
+        .method(Method::POST)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+}
+
+#[actix_rt::test]
+async fn rename_validation() {
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+    let user = session.create_user(UserBuilder::default());
+
+    let request = TestRequest::with_uri("/rename/")
+        .method(Method::POST)
+        .jwt_auth(user.id)
+        .set_json(&json!({"from": "food"}))
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(
+        json!({"to": ["This field is required."]}),
+        serde_json::from_slice::<Value>(&read_body(response).await).unwrap()
+    );
+}
+
+#[actix_rt::test]
+async fn rename_happy_path() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+    let redis = Redis::connect().await.expect("Failed to connect to redis");
+
+    let user = session.create_user(UserBuilder::default().tags(vec!["food", "car"]));
+    let record = session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["food"])
+            .finish(),
+    );
+    increment_tags(user.id.into(), tags_vec!["food", "food", "car"], &redis)
+        .await
+        .expect("failed to increment tags");
+
+    let request = TestRequest::with_uri("/rename/")
+        .method(Method::POST)
+        .jwt_auth(user.id)
+        .set_json(&json!({"from": "food", "to": "groceries"}))
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        json!({"tags": ["groceries", "car"]}),
+        serde_json::from_slice::<Value>(&read_body(response).await).unwrap()
+    );
+    assert_eq!(tags_vec!["groceries"], session.find_record(record.id).tags);
+
+    let redis_tags = read_redis_tags(user.id.into(), &redis)
+        .await
+        .expect("failed to read tags");
+    assert_eq!(tags_vec!["groceries", "car"], redis_tags);
+}
+
+#[actix_rt::test]
+async fn merge_happy_path() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+    let redis = Redis::connect().await.expect("Failed to connect to redis");
+
+    let user = session.create_user(UserBuilder::default().tags(vec!["cafe", "car", "bar"]));
+    let record = session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["bar", "cafe"])
+            .finish(),
+    );
+    increment_tags(
+        user.id.into(),
+        tags_vec!["car", "car", "cafe", "bar"],
+        &redis,
+    )
+    .await
+    .expect("failed to increment tags");
+
+    let request = TestRequest::with_uri("/merge/")
+        .method(Method::POST)
+        .jwt_auth(user.id)
+        .set_json(&json!({"tags": ["cafe", "bar"], "into": "eating out"}))
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        json!({"tags": ["eating out", "car"]}),
+        serde_json::from_slice::<Value>(&read_body(response).await).unwrap()
+    );
+    assert_eq!(tags_vec!["eating out"], session.find_record(record.id).tags);
+}
+
+#[actix_rt::test]
+async fn delete_happy_path() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+    let redis = Redis::connect().await.expect("Failed to connect to redis");
+
+    let user = session.create_user(UserBuilder::default().tags(vec!["eating out", "car"]));
+    let record = session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["eating out", "car"])
+            .finish(),
+    );
+    increment_tags(user.id.into(), tags_vec!["eating out", "car"], &redis)
+        .await
+        .expect("failed to increment tags");
+
+    let request = TestRequest::with_uri("/eating%20out/")
+        .method(Method::DELETE)
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        json!({"tags": ["car"]}),
+        serde_json::from_slice::<Value>(&read_body(response).await).unwrap()
+    );
+    assert_eq!(tags_vec!["car"], session.find_record(record.id).tags);
+
+    let redis_tags = read_redis_tags(user.id.into(), &redis)
+        .await
+        .expect("failed to read tags");
+    assert_eq!(tags_vec!["car"], redis_tags);
+}
+
+#[actix_rt::test]
+async fn delete_nested_tag() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default().tags(vec![
+        "food",
+        "food/groceries",
+        "food/groceries/fruit",
+        "food/cafe",
+    ]));
+    let record = session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["food/groceries/fruit", "food/cafe"])
+            .finish(),
+    );
+
+    let request = TestRequest::with_uri("/food/groceries/")
+        .method(Method::DELETE)
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        json!({"tags": ["food", "food/cafe"]}),
+        serde_json::from_slice::<Value>(&read_body(response).await).unwrap()
+    );
+    assert_eq!(tags_vec!["food/cafe"], session.find_record(record.id).tags);
+}
+
+#[actix_rt::test]
+async fn rebuild_is_for_staff_only() {
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+    let user = session.create_user(UserBuilder::default());
+
+    let request = TestRequest::with_uri("/rebuild/")
+        .method(Method::POST)
+        .jwt_auth(user.id)
+        .set_json(&json!({}))
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+}
+
+#[actix_rt::test]
+async fn rebuild_happy_path() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+    let redis = Redis::connect().await.expect("Failed to connect to redis");
+
+    let admin = session.create_user(UserBuilder::default().username("admin").is_staff(true));
+    let user = session.create_user(UserBuilder::default().username("user"));
+    for tags in [vec!["car"], vec!["food"], vec!["food"]] {
+        session.create_record(
+            RecordBuilder::default()
+                .user_id(user.id)
+                .tags(tags)
+                .finish(),
+        );
+    }
+
+    // scores in redis drifted away from records
+    increment_tags(user.id.into(), tags_vec!["car", "car", "gone"], &redis)
+        .await
+        .expect("failed to increment tags");
+
+    let request = TestRequest::with_uri("/rebuild/")
+        .method(Method::POST)
+        .jwt_auth(admin.id)
+        .set_json(&json!({"user_id": user.id}))
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        json!({"users": 1}),
+        serde_json::from_slice::<Value>(&read_body(response).await).unwrap()
+    );
+
+    let redis_tags = read_redis_tags(user.id.into(), &redis)
+        .await
+        .expect("failed to read tags");
+    assert_eq!(tags_vec!["food", "car"], redis_tags);
+}
+
+#[actix_rt::test]
+async fn rebuild_with_half_life_mixes_with_counted_uses() {
+    use chrono::{Duration, Utc};
+
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+    let redis = Redis::connect().await.expect("Failed to connect to redis");
+
+    let admin = session.create_user(UserBuilder::default().username("admin").is_staff(true));
+    let user = session.create_user(UserBuilder::default().username("user"));
+    let two_months_ago = Utc::now().naive_utc() - Duration::days(60);
+    for (tags, created_at) in [
+        (vec!["car"], two_months_ago),
+        (vec!["car"], two_months_ago),
+        (vec!["food"], Utc::now().naive_utc()),
+    ] {
+        session.create_record(
+            RecordBuilder::default()
+                .user_id(user.id)
+                .tags(tags)
+                .created_at(created_at)
+                .finish(),
+        );
+    }
+
+    let request = TestRequest::with_uri("/rebuild/")
+        .method(Method::POST)
+        .jwt_auth(admin.id)
+        .set_json(&json!({"user_id": user.id, "half_life_days": 30}))
+        .to_request();
+    let response = call_service(&mut service, request).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    // two uses of car are a quarter each, food was used just now
+    let redis_tags = read_redis_tags(user.id.into(), &redis)
+        .await
+        .expect("failed to read tags");
+    assert_eq!(tags_vec!["food", "car"], redis_tags);
+
+    // a new use counts 1, the weight of a use made now
+    increment_tags(user.id.into(), tags_vec!["car"], &redis)
+        .await
+        .expect("failed to increment tags");
+    let redis_tags = read_redis_tags(user.id.into(), &redis)
+        .await
+        .expect("failed to read tags");
+    assert_eq!(tags_vec!["car", "food"], redis_tags);
+
+    // removing uses takes car below zero, it is removed then
+    decrement_tags(user.id.into(), tags_vec!["car"], &redis)
+        .await
+        .expect("failed to decrement tags");
+    decrement_tags(user.id.into(), tags_vec!["car"], &redis)
+        .await
+        .expect("failed to decrement tags");
+    let redis_tags = read_redis_tags(user.id.into(), &redis)
+        .await
+        .expect("failed to read tags");
+    assert_eq!(tags_vec!["food"], redis_tags);
+}
+
+#[actix_rt::test]
+async fn index_falls_back_to_records_without_redis() {
+    use crate::config::user_tags_redis_key;
+
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+    let redis = Redis::connect().await.expect("Failed to connect to redis");
+
+    let user = session.create_user(UserBuilder::default().tags(vec!["car", "food", "unused"]));
+    for tags in [vec!["car"], vec!["food"], vec!["food"]] {
+        session.create_record(
+            RecordBuilder::default()
+                .user_id(user.id)
+                .tags(tags)
+                .finish(),
+        );
+    }
+
+    // tags cannot be read from a key of the wrong type
+    let _: () = redis::cmd("set")
+        .arg(user_tags_redis_key(user.id))
+        .arg("foo")
+        .query_async(&mut redis.connection().unwrap())
+        .await
+        .unwrap();
+
+    let request = TestRequest::with_uri("/").jwt_auth(user.id).to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!("redis", response.headers().get("x-degraded-mode").unwrap());
+    assert_eq!(
+        json!({"tags": ["food", "car", "unused"]}),
+        serde_json::from_slice::<Value>(&read_body(response).await).unwrap()
+    );
+}
+
+#[actix_rt::test]
+async fn index_starts_without_redis() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    // nothing listens on the port
+    let mut service = await_test_server!(Service, Redis::with_url("redis://127.0.0.1:1/"));
+
+    let user = session.create_user(UserBuilder::default().tags(vec!["car", "food"]));
+    session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["food"])
+            .finish(),
+    );
+
+    let request = TestRequest::with_uri("/").jwt_auth(user.id).to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!("redis", response.headers().get("x-degraded-mode").unwrap());
+    assert_eq!(
+        json!({"tags": ["food", "car"]}),
+        serde_json::from_slice::<Value>(&read_body(response).await).unwrap()
+    );
+}
+
+#[actix_rt::test]
+async fn suggest_happy_path() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default().tags(vec!["food", "fun", "coffee"]));
+    session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["food"])
+            .comment("groceries")
+            .finish(),
+    );
+    session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["fun"])
+            .comment("cinema")
+            .finish(),
+    );
+
+    let request = TestRequest::with_uri("/suggest?prefix=f&comment=Cinema%20tickets")
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        json!({"tags": ["fun", "food"]}),
+        serde_json::from_slice::<Value>(&read_body(response).await).unwrap()
+    );
+}
@@ -0,0 +1,84 @@
+use actix_web::{
+    delete, get, post,
+    web::{self, Json, Path},
+    HttpResponse, Result,
+};
+use octo_budget_lib::auth_token::AuthToken;
+use serde::Serialize;
+
+use super::forms::personal_token::Form;
+use crate::db::{
+    models::PersonalToken,
+    queries::{CreatePersonalToken, DeletePersonalToken, GetPersonalTokens},
+    ConnectionPool,
+};
+
+/// The only response with the token itself, it is not possible to see it again.
+#[derive(Serialize)]
+struct Created {
+    #[serde(flatten)]
+    personal_token: PersonalToken,
+    token: String,
+}
+
+// Tokens are managed with `AuthToken` rather than `UserId`, so a personal
+// token cannot be used to create more tokens.
+
+#[get("/tokens/")]
+async fn index(auth: AuthToken, pool: web::Data<ConnectionPool>) -> Result<HttpResponse> {
+    let tokens = pool
+        .execute(GetPersonalTokens::new(auth.user_id().into()))
+        .await?;
+
+    Ok(HttpResponse::Ok().json(tokens))
+}
+
+#[post("/tokens/")]
+async fn create(
+    auth: AuthToken,
+    form: Json<Form>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let data = form.into_inner().validate()?;
+    let (personal_token, token) = pool
+        .execute(CreatePersonalToken::new(auth.user_id().into(), data))
+        .await?;
+
+    Ok(HttpResponse::Created().json(Created {
+        personal_token,
+        token,
+    }))
+}
+
+#[delete("/tokens/{id}/")]
+async fn destroy(
+    auth: AuthToken,
+    id: Path<i32>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    pool.execute(DeletePersonalToken::new(
+        id.into_inner(),
+        auth.user_id().into(),
+    ))
+    .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub mod service {
+    use super::*;
+    use actix_web::dev::HttpServiceFactory;
+
+    pub struct Service;
+
+    impl HttpServiceFactory for Service {
+        fn register(self, config: &mut actix_web::dev::AppService) {
+            HttpServiceFactory::register(index, config);
+            HttpServiceFactory::register(create, config);
+            HttpServiceFactory::register(destroy, config);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
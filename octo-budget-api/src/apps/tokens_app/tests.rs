@@ -0,0 +1,142 @@
+use super::service::Service;
+use crate::{
+    await_test_server,
+    db::builders::UserBuilder,
+    tests::{setup_env, DbSession, RequestJwtAuthExt, RequestTokenAuthExt},
+};
+use actix_web::{
+    http::{Method, StatusCode},
+    test::{call_service, read_body, TestRequest},
+};
+use serde_json::{json, Value};
+
+async fn body_json(response: actix_web::dev::ServiceResponse) -> Value {
+    let body = read_body(response).await;
+
+    serde_json::from_slice(&body).unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", body))
+}
+
+fn create_request(body: Value) -> TestRequest {
+    TestRequest::with_uri("/tokens/")
+        .method(Method::POST)
+        .set_json(&body)
+}
+
+#[actix_rt::test]
+async fn create_list_and_revoke() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+    let mut service = await_test_server!(Service);
+
+    let request = create_request(json!({"name": "import", "scope": "read_write"}))
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::CREATED, response.status());
+    let created = body_json(response).await;
+    assert_eq!(json!("import"), created["name"]);
+    assert_eq!(json!("read_write"), created["scope"]);
+    assert_eq!(64, created["token"].as_str().unwrap().len());
+
+    let request = TestRequest::with_uri("/tokens/")
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    let tokens = body_json(response).await;
+    assert_eq!(1, tokens.as_array().unwrap().len());
+    assert_eq!(created["id"], tokens[0]["id"]);
+    assert_eq!(json!(null), tokens[0]["last_used_at"]);
+    assert!(tokens[0].get("token").is_none());
+    assert!(tokens[0].get("token_hash").is_none());
+
+    let request = TestRequest::with_uri(&format!("/tokens/{}/", created["id"]))
+        .method(Method::DELETE)
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let request = TestRequest::with_uri("/tokens/")
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(json!([]), body_json(response).await);
+}
+
+#[actix_rt::test]
+async fn create_with_invalid_data() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+    let mut service = await_test_server!(Service);
+
+    let request = create_request(json!({"scope": "admin"}))
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(
+        json!({
+            "name": ["This field is required."],
+            "scope": ["\"admin\" is not a valid choice."],
+        }),
+        body_json(response).await
+    );
+}
+
+#[actix_rt::test]
+async fn revoke_token_of_other_user() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().username("john"));
+    let other = session.create_user(UserBuilder::default().username("jane"));
+    let mut service = await_test_server!(Service);
+
+    let request = create_request(json!({"name": "import"}))
+        .jwt_auth(other.id)
+        .to_request();
+    let created = body_json(call_service(&mut service, request).await).await;
+
+    let request = TestRequest::with_uri(&format!("/tokens/{}/", created["id"]))
+        .method(Method::DELETE)
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[actix_rt::test]
+async fn personal_token_cannot_manage_tokens() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+    let mut service = await_test_server!(Service);
+
+    let request = create_request(json!({"name": "import", "scope": "read_write"}))
+        .jwt_auth(user.id)
+        .to_request();
+    let created = body_json(call_service(&mut service, request).await).await;
+    let token = created["token"].as_str().unwrap();
+
+    let request = create_request(json!({"name": "another"}))
+        .token_auth(token)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+    let request = TestRequest::with_uri("/tokens/")
+        .token_auth(token)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+}
@@ -1,9 +1,140 @@
-use actix_web::{get, HttpResponse, Responder, Result as WebResult};
+use actix_web::{
+    get, patch,
+    web::{self, Json},
+    HttpResponse, Result,
+};
+use chrono::NaiveDateTime;
+use serde::Serialize;
 use serde_json::json;
 
-use octo_budget_lib::auth_token::UserId;
+use octo_budget_lib::auth_token::{AuthToken, UserId};
 
-#[get("/{user_id}/")]
-pub async fn show(_current_user_id: UserId) -> WebResult<impl Responder> {
-    Ok(HttpResponse::Ok().json(json!({})))
+use super::forms::{profile::Form, settings::Form as SettingsForm};
+use crate::db::{
+    models::AuthUser,
+    queries::{FindUser, GetUserSettings, GetUserTags, UpdateProfile, UpdateUserSettings},
+    ConnectionPool,
+};
+
+#[derive(Serialize, Debug)]
+struct Preferences {
+    tags: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+struct Profile {
+    id: i32,
+    username: String,
+    email: String,
+    first_name: String,
+    last_name: String,
+    date_joined: NaiveDateTime,
+    preferences: Preferences,
+}
+
+impl Profile {
+    fn new(user: AuthUser, tags: Vec<String>) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            first_name: user.first_name,
+            last_name: user.last_name,
+            date_joined: user.date_joined,
+            preferences: Preferences { tags },
+        }
+    }
+}
+
+async fn profile(user: AuthUser, pool: &ConnectionPool) -> Result<Profile> {
+    let tags = pool.execute(GetUserTags::new(user.id.into())).await?;
+
+    Ok(Profile::new(user, tags))
 }
+
+#[get("/me/")]
+async fn me(user_id: UserId, pool: web::Data<ConnectionPool>) -> Result<HttpResponse> {
+    let user = pool.execute(FindUser::new(user_id)).await?;
+
+    Ok(HttpResponse::Ok().json(profile(user, &pool).await?))
+}
+
+// The profile is updated with `AuthToken` rather than `UserId`, so a personal
+// token cannot change the email and then reset the password.
+#[patch("/me/")]
+async fn update(
+    auth: AuthToken,
+    form: Json<Form>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let data = form.into_inner().validate()?;
+    let user = pool
+        .execute(UpdateProfile::new(auth.user_id().into(), data))
+        .await?;
+
+    Ok(HttpResponse::Ok().json(profile(user, &pool).await?))
+}
+
+#[get("/me/settings/")]
+async fn show_settings(user_id: UserId, pool: web::Data<ConnectionPool>) -> Result<HttpResponse> {
+    let settings = pool.execute(GetUserSettings::new(user_id)).await?;
+
+    Ok(HttpResponse::Ok().json(settings))
+}
+
+#[patch("/me/settings/")]
+async fn update_settings(
+    user_id: UserId,
+    form: Json<SettingsForm>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let current = pool.execute(GetUserSettings::new(user_id)).await?;
+    let settings = form.into_inner().validate(current)?;
+    let settings = pool.execute(UpdateUserSettings::new(settings)).await?;
+
+    Ok(HttpResponse::Ok().json(settings))
+}
+
+/// Only staff can see profiles of other users.
+#[get("/{id:\\d+}/")]
+async fn show(
+    id: web::Path<i32>,
+    user_id: UserId,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let id = id.into_inner();
+
+    if id != user_id {
+        let current_user = pool.execute(FindUser::new(user_id)).await?;
+
+        if !current_user.is_staff {
+            return Ok(HttpResponse::Forbidden().json(json!({
+                "detail": "You do not have permission to perform this action."
+            })));
+        }
+    }
+
+    let user = pool.execute(FindUser::new(id)).await?;
+
+    Ok(HttpResponse::Ok().json(profile(user, &pool).await?))
+}
+
+pub mod service {
+    use super::*;
+    use actix_web::dev::HttpServiceFactory;
+
+    pub struct Service;
+
+    impl HttpServiceFactory for Service {
+        fn register(self, config: &mut actix_web::dev::AppService) {
+            HttpServiceFactory::register(me, config);
+            HttpServiceFactory::register(update, config);
+            HttpServiceFactory::register(show_settings, config);
+            HttpServiceFactory::register(update_settings, config);
+            HttpServiceFactory::register(show, config);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
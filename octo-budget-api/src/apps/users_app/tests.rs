@@ -0,0 +1,317 @@
+use super::service::Service;
+use crate::{
+    apps::forms::personal_token::Data,
+    await_test_server,
+    db::{builders::UserBuilder, queries::CreatePersonalToken, ConnectionPool},
+    tests::{setup_env, DbSession, RequestJwtAuthExt, RequestTokenAuthExt},
+};
+use actix_web::{
+    http::{Method, StatusCode},
+    test::{call_service, read_body, TestRequest},
+};
+use serde_json::{json, Value};
+
+async fn body_json(response: actix_web::dev::ServiceResponse) -> Value {
+    let body = read_body(response).await;
+
+    serde_json::from_slice(&body).unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", body))
+}
+
+fn patch(body: Value) -> TestRequest {
+    TestRequest::with_uri("/me/")
+        .method(Method::PATCH)
+        .set_json(&body)
+}
+
+fn patch_settings(body: Value) -> TestRequest {
+    TestRequest::with_uri("/me/settings/")
+        .method(Method::PATCH)
+        .set_json(&body)
+}
+
+#[actix_rt::test]
+async fn show_current_user() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(
+        UserBuilder::default()
+            .username("john")
+            .email("john@example.com")
+            .tags(vec!["food"]),
+    );
+    let mut service = await_test_server!(Service);
+
+    let request = TestRequest::with_uri("/me/").jwt_auth(user.id).to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = body_json(response).await;
+    assert_eq!(json!(user.id), body["id"]);
+    assert_eq!(json!("john"), body["username"]);
+    assert_eq!(json!("john@example.com"), body["email"]);
+    assert_eq!(json!(""), body["first_name"]);
+    assert_eq!(json!(""), body["last_name"]);
+    assert_eq!(json!({"tags": ["food"]}), body["preferences"]);
+    assert!(body["date_joined"].is_string());
+    assert!(body.get("password").is_none());
+}
+
+#[actix_rt::test]
+async fn show_requires_auth() {
+    setup_env();
+    let mut service = await_test_server!(Service);
+
+    let request = TestRequest::with_uri("/me/").to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+}
+
+#[actix_rt::test]
+async fn update_profile() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().username("john"));
+    let mut service = await_test_server!(Service);
+
+    let request = patch(json!({"email": " john@example.com ", "first_name": "John"}))
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = body_json(response).await;
+    assert_eq!(json!("john@example.com"), body["email"]);
+    assert_eq!(json!("John"), body["first_name"]);
+
+    let user = session.find_user_by_name("john");
+    assert_eq!("john@example.com", user.email);
+    assert_eq!("John", user.first_name);
+    assert_eq!("", user.last_name);
+}
+
+#[actix_rt::test]
+async fn update_profile_with_invalid_data() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(
+        UserBuilder::default()
+            .username("john")
+            .email("john@example.com"),
+    );
+    let mut service = await_test_server!(Service);
+
+    let request = patch(json!({"email": "not an email"}))
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(
+        json!({"email": ["Enter a valid email address."]}),
+        body_json(response).await
+    );
+    assert_eq!("john@example.com", session.find_user_by_name("john").email);
+}
+
+#[actix_rt::test]
+async fn show_self_by_id() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().username("john"));
+    let mut service = await_test_server!(Service);
+
+    let request = TestRequest::with_uri(&format!("/{}/", user.id))
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(json!("john"), body_json(response).await["username"]);
+}
+
+#[actix_rt::test]
+async fn show_other_user_is_forbidden() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().username("john"));
+    let other = session.create_user(UserBuilder::default().username("jane"));
+    let mut service = await_test_server!(Service);
+
+    let request = TestRequest::with_uri(&format!("/{}/", other.id))
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+}
+
+#[actix_rt::test]
+async fn staff_can_see_other_users() {
+    setup_env();
+    let session = DbSession::new();
+    let staff = session.create_user(UserBuilder::default().username("admin").is_staff(true));
+    let other = session.create_user(UserBuilder::default().username("jane"));
+    let mut service = await_test_server!(Service);
+
+    let request = TestRequest::with_uri(&format!("/{}/", other.id))
+        .jwt_auth(staff.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(json!("jane"), body_json(response).await["username"]);
+
+    let request = TestRequest::with_uri(&format!("/{}/", other.id + 100))
+        .jwt_auth(staff.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[actix_rt::test]
+async fn show_default_settings() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+    let mut service = await_test_server!(Service);
+
+    let request = TestRequest::with_uri("/me/settings/")
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        json!({
+            "timezone": "UTC",
+            "default_currency": "CAD",
+            "week_start": 1,
+            "month_start_day": 1,
+        }),
+        body_json(response).await
+    );
+}
+
+#[actix_rt::test]
+async fn update_settings() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+    let mut service = await_test_server!(Service);
+
+    let request = patch_settings(json!({"timezone": "America/Vancouver", "month_start_day": 15}))
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+
+    let request = patch_settings(json!({"default_currency": "USD"}))
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        json!({
+            "timezone": "America/Vancouver",
+            "default_currency": "USD",
+            "week_start": 1,
+            "month_start_day": 15,
+        }),
+        body_json(response).await
+    );
+}
+
+#[actix_rt::test]
+async fn update_settings_with_invalid_data() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+    let mut service = await_test_server!(Service);
+
+    let request = patch_settings(json!({"timezone": "Mars/Olympus", "month_start_day": 15}))
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(
+        json!({"timezone": ["\"Mars/Olympus\" is not a valid choice."]}),
+        body_json(response).await
+    );
+
+    let request = TestRequest::with_uri("/me/settings/")
+        .jwt_auth(user.id)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(json!(1), body_json(response).await["month_start_day"]);
+}
+
+async fn personal_token(user_id: i32, scope: &str) -> String {
+    let data = Data {
+        name: "script".to_string(),
+        scope: scope.to_string(),
+    };
+
+    ConnectionPool::new()
+        .execute(CreatePersonalToken::new(user_id, data))
+        .await
+        .unwrap()
+        .1
+}
+
+#[actix_rt::test]
+async fn personal_token_auth() {
+    setup_env();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().username("john"));
+    let read = personal_token(user.id, "read").await;
+    let read_write = personal_token(user.id, "read_write").await;
+    let mut service = await_test_server!(Service);
+
+    let request = TestRequest::with_uri("/me/").token_auth(&read).to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(json!("john"), body_json(response).await["username"]);
+
+    let request = patch_settings(json!({"month_start_day": 15}))
+        .token_auth(&read)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+    let request = patch_settings(json!({"month_start_day": 15}))
+        .token_auth(&read_write)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(json!(15), body_json(response).await["month_start_day"]);
+
+    // the email is enough to take over the account with a password reset
+    let request = patch(json!({"email": "attacker@example.com"}))
+        .token_auth(&read_write)
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    assert_ne!(
+        "attacker@example.com",
+        session.find_user_by_name("john").email
+    );
+
+    let request = TestRequest::with_uri("/me/")
+        .token_auth("unknown")
+        .to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+}
@@ -0,0 +1,116 @@
+use actix_web::{
+    delete, get, post, put,
+    web::{self, Json, Path, Query},
+    HttpResponse, Result,
+};
+use octo_budget_lib::auth_token::UserId;
+
+use super::forms::year_budget::Form;
+use super::index_params::Params;
+use super::money_format::MoneyFormat;
+use crate::db::{
+    queries::{
+        CreateYearBudget, DeleteYearBudget, FindYearBudget, GetYearBudget, GetYearBudgets,
+        UpdateYearBudget,
+    },
+    ConnectionPool,
+};
+
+#[get("/year-budget-detail/")]
+async fn index(
+    user_id: UserId,
+    params: Query<Params>,
+    pool: web::Data<ConnectionPool>,
+    money_format: MoneyFormat,
+) -> Result<HttpResponse> {
+    let params = params.into_inner().validate()?;
+
+    let query = GetYearBudgets {
+        page: params.page,
+        per_page: params.per_page,
+        user_id: user_id.into(),
+    };
+
+    let budgets = pool.execute(query).await?;
+
+    Ok(money_format.json(&budgets))
+}
+
+#[get("/year-budget-detail/{id}/")]
+async fn show(
+    user_id: UserId,
+    budget_id: Path<i32>,
+    pool: web::Data<ConnectionPool>,
+    money_format: MoneyFormat,
+) -> Result<HttpResponse> {
+    let budget_id = budget_id.into_inner();
+    let budget = pool.execute(GetYearBudget::new(budget_id, user_id)).await?;
+
+    Ok(money_format.json(&budget))
+}
+
+#[post("/year-budget-detail/")]
+async fn create(
+    user_id: UserId,
+    form: Json<Form>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    use serde_json::json;
+
+    let data = form.into_inner().validate()?;
+    let id = pool.execute(CreateYearBudget::new(&data, user_id)).await?;
+
+    Ok(HttpResponse::Ok().json(json!({ "id": id })))
+}
+
+#[put("/year-budget-detail/{id}/")]
+async fn update(
+    user_id: UserId,
+    budget_id: Path<i32>,
+    form: Json<Form>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let budget_id = budget_id.into_inner();
+    let data = form.into_inner().validate()?;
+
+    let budget = pool
+        .execute(FindYearBudget::new(budget_id, user_id))
+        .await?;
+    pool.execute(UpdateYearBudget::new(budget.id, &data, user_id))
+        .await?;
+
+    Ok(HttpResponse::Ok().json(""))
+}
+
+#[delete("/year-budget-detail/{id}/")]
+async fn destroy(
+    user_id: UserId,
+    budget_id: Path<i32>,
+    pool: web::Data<ConnectionPool>,
+) -> Result<HttpResponse> {
+    let budget_id = budget_id.into_inner();
+    pool.execute(DeleteYearBudget::new(budget_id, user_id))
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub mod service {
+    use super::*;
+    use actix_web::dev::HttpServiceFactory;
+
+    pub struct Service;
+
+    impl HttpServiceFactory for Service {
+        fn register(self, config: &mut actix_web::dev::AppService) {
+            HttpServiceFactory::register(index, config);
+            HttpServiceFactory::register(show, config);
+            HttpServiceFactory::register(create, config);
+            HttpServiceFactory::register(update, config);
+            HttpServiceFactory::register(destroy, config);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
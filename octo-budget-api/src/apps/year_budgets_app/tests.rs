@@ -0,0 +1,213 @@
+use super::service::Service;
+use crate::{
+    await_test_server,
+    db::builders::{UserBuilder, YearBudgetBuilder},
+    tests::{self, setup_env, RequestJwtAuthExt as _},
+};
+use actix_web::{
+    http::{Method, StatusCode},
+    test::{call_service, read_body, TestRequest},
+};
+use bigdecimal::BigDecimal;
+use serde_json::{json, Value};
+
+fn year_budget_payload() -> Value {
+    json!({
+        "name": "Travel",
+        "amount": {"amount": 6000, "currency": { "code": "CAD", "name": "Canadian Dollar" }},
+        "year": 2020,
+        "tags": ["travel"],
+        "tags_type": "INCL",
+    })
+}
+
+#[actix_rt::test]
+async fn index_requires_auth() {
+    setup_env();
+
+    let mut service = await_test_server!(Service);
+    let request = TestRequest::with_uri("/year-budget-detail/").to_request();
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::UNAUTHORIZED,
+        response.status(),
+        "wrong status code"
+    );
+}
+
+#[actix_rt::test]
+async fn index_happy_path() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    let budget = session.create_year_budget(
+        YearBudgetBuilder::default()
+            .user_id(user.id)
+            .name("Gifts")
+            .amount(1200.0)
+            .finish(),
+    );
+
+    let request = TestRequest::with_uri("/year-budget-detail/")
+        .jwt_auth(user.id)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    assert_eq!(json!(1), response_body["total"]);
+    assert_eq!(json!(budget.id), response_body["results"][0]["id"]);
+    assert_eq!(json!("Gifts"), response_body["results"][0]["name"]);
+    assert_eq!(
+        json!(100.0),
+        response_body["results"][0]["average_per_month"]
+    );
+}
+
+#[actix_rt::test]
+async fn create_happy_path() {
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+
+    let request = TestRequest::with_uri("/year-budget-detail/")
+        .method(Method::POST)
+        .jwt_auth(user.id)
+        .set_json(&year_budget_payload())
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let response_body = read_body(response).await;
+    let response_body = serde_json::from_slice::<Value>(&response_body)
+        .unwrap_or_else(|_| panic!("Failed to deserialize: {:?}", response_body));
+
+    let new_budget_id = response_body.get("id").unwrap().as_i64().unwrap() as i32;
+    let budget = session.find_year_budget(new_budget_id);
+
+    assert_eq!("Travel", budget.name);
+    assert_eq!(BigDecimal::from(6000), budget.amount);
+    assert_eq!(2020, budget.year);
+    assert_eq!(vec!["travel"], budget.tags);
+    assert_eq!("INCL", budget.tags_type);
+    assert_eq!(user.id, budget.user_id);
+}
+
+#[actix_rt::test]
+async fn create_with_invalid_data() {
+    setup_env();
+
+    let session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+
+    let mut payload = year_budget_payload();
+    payload["year"] = json!(null);
+
+    let request = TestRequest::with_uri("/year-budget-detail/")
+        .method(Method::POST)
+        .jwt_auth(user.id)
+        .set_json(&payload)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::BAD_REQUEST,
+        response.status(),
+        "wrong status code"
+    );
+    assert_eq!(0, session.count_year_budgets());
+}
+
+#[actix_rt::test]
+async fn show_year_budget_of_other_user() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let owner = session.create_user(UserBuilder::default().username("foo"));
+    let other_user = session.create_user(UserBuilder::default().username("bar"));
+    let budget =
+        session.create_year_budget(YearBudgetBuilder::default().user_id(other_user.id).finish());
+
+    let request = TestRequest::with_uri(&format!("/year-budget-detail/{}/", budget.id))
+        .jwt_auth(owner.id)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::NOT_FOUND,
+        response.status(),
+        "wrong status code"
+    );
+}
+
+#[actix_rt::test]
+async fn update_happy_path() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    let budget = session.create_year_budget(YearBudgetBuilder::default().user_id(user.id).finish());
+
+    let request = TestRequest::with_uri(&format!("/year-budget-detail/{}/", budget.id))
+        .method(Method::PUT)
+        .jwt_auth(user.id)
+        .set_json(&year_budget_payload())
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(StatusCode::OK, response.status(), "wrong status code");
+
+    let updated_budget = session.find_year_budget(budget.id);
+
+    assert_eq!("Travel", updated_budget.name);
+    assert_eq!(BigDecimal::from(6000), updated_budget.amount);
+    assert_eq!(2020, updated_budget.year);
+}
+
+#[actix_rt::test]
+async fn delete_happy_path() {
+    setup_env();
+
+    let mut session = tests::DbSession::new();
+    let mut service = await_test_server!(Service);
+
+    let user = session.create_user(UserBuilder::default());
+    let budget = session.create_year_budget(YearBudgetBuilder::default().user_id(user.id).finish());
+
+    let request = TestRequest::with_uri(&format!("/year-budget-detail/{}/", budget.id))
+        .method(Method::DELETE)
+        .jwt_auth(user.id)
+        .to_request();
+
+    let response = call_service(&mut service, request).await;
+
+    assert_eq!(
+        StatusCode::NO_CONTENT,
+        response.status(),
+        "wrong status code"
+    );
+    assert_eq!(0, session.count_year_budgets());
+}
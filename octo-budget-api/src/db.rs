@@ -4,8 +4,14 @@ use diesel::{
     r2d2::{ConnectionManager, Pool},
 };
 
+pub mod calendar;
+pub mod exchange_rates;
+pub mod inactive_users;
 pub mod pagination;
+pub mod personal_tokens;
 pub mod queries;
+pub mod refresh_tokens;
+pub mod tags;
 pub use models::{self, schema};
 
 pub type PooledConnection =
@@ -16,6 +22,7 @@ pub trait DatabaseQuery {
     fn execute(&self, pool: PooledConnection) -> DbResult<Self::Data>;
 }
 
+#[derive(Clone)]
 pub struct ConnectionPool(Pool<ConnectionManager<PgConnection>>);
 
 use actix_web::web::block;
@@ -1,6 +1,6 @@
 use crate::db::models::*;
 use bigdecimal::BigDecimal;
-use chrono::offset::Local;
+use chrono::{naive::NaiveDateTime, offset::Local};
 
 #[derive(Debug, Clone, Default)]
 pub struct UserBuilder {
@@ -8,6 +8,8 @@ pub struct UserBuilder {
     pub password: String,
     pub username: String,
     pub tags: Vec<String>,
+    pub is_active: Option<bool>,
+    pub is_staff: bool,
 }
 
 impl UserBuilder {
@@ -16,6 +18,11 @@ impl UserBuilder {
         self
     }
 
+    pub fn email(mut self, email: &str) -> Self {
+        self.email = email.into();
+        self
+    }
+
     pub fn password(mut self, password: &str) -> Self {
         self.password = password.into();
         self
@@ -26,19 +33,29 @@ impl UserBuilder {
         self
     }
 
+    pub fn is_active(mut self, is_active: bool) -> Self {
+        self.is_active = Some(is_active);
+        self
+    }
+
+    pub fn is_staff(mut self, is_staff: bool) -> Self {
+        self.is_staff = is_staff;
+        self
+    }
+
     pub fn finish(self) -> AuthUser {
         AuthUser {
             id: 1,
             username: self.username,
             password: self.password,
             is_superuser: false,
-            is_active: true,
-            is_staff: false,
+            is_active: self.is_active.unwrap_or(true),
+            is_staff: self.is_staff,
             email: self.email,
             first_name: String::new(),
+            last_login: None,
             last_name: String::new(),
             date_joined: Local::now().naive_local(),
-            tags: self.tags,
         }
     }
 }
@@ -52,6 +69,7 @@ pub struct RecordBuilder {
     pub transaction_type: String,
     pub comment: String,
     pub user_id: i32,
+    pub created_at: Option<NaiveDateTime>,
 }
 
 impl RecordBuilder {
@@ -75,12 +93,29 @@ impl RecordBuilder {
         self
     }
 
+    pub fn currency(mut self, currency: &str) -> Self {
+        self.amount_currency = currency.to_string();
+        self
+    }
+
+    pub fn comment(mut self, comment: &str) -> Self {
+        self.comment = comment.to_string();
+        self
+    }
+
+    pub fn created_at(mut self, created_at: NaiveDateTime) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+
     pub fn finish(self) -> Record {
         Record {
             id: self.id,
             amount: self.amount,
             amount_currency: self.amount_currency,
-            created_at: Local::now().naive_local(),
+            created_at: self
+                .created_at
+                .unwrap_or_else(|| Local::now().naive_local()),
             tags: self.tags,
             transaction_type: self.transaction_type,
             user_id: self.user_id,
@@ -98,9 +133,25 @@ pub struct BudgetBuilder {
     pub tags: Vec<String>,
     pub tags_type: String,
     pub user_id: i32,
+    pub comment: Option<String>,
 }
 
 impl BudgetBuilder {
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn amount(mut self, amount: f64) -> Self {
+        self.amount = BigDecimal::from(amount);
+        self
+    }
+
+    pub fn currency(mut self, currency: &str) -> Self {
+        self.amount_currency = currency.to_string();
+        self
+    }
+
     pub fn tags_type(mut self, tags_type: &str) -> Self {
         self.tags_type = tags_type.to_string();
         self
@@ -128,6 +179,68 @@ impl BudgetBuilder {
             tags_type: self.tags_type,
             user_id: self.user_id,
             start_date: NaiveDate::from_ymd(2015, 3, 14),
+            comment: self.comment,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct YearBudgetBuilder {
+    pub amount: BigDecimal,
+    pub amount_currency: String,
+    pub id: i32,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub tags_type: String,
+    pub user_id: i32,
+    pub year: Option<i32>,
+    pub comment: Option<String>,
+}
+
+impl YearBudgetBuilder {
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn amount(mut self, amount: f64) -> Self {
+        self.amount = BigDecimal::from(amount);
+        self
+    }
+
+    pub fn year(mut self, year: i32) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    pub fn tags_type(mut self, tags_type: &str) -> Self {
+        self.tags_type = tags_type.to_string();
+        self
+    }
+
+    pub fn tags(mut self, tags: Vec<&str>) -> Self {
+        self.tags = tags.into_iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    pub fn user_id(mut self, user_id: i32) -> Self {
+        self.user_id = user_id;
+        self
+    }
+
+    pub fn finish(self) -> YearBudget {
+        use chrono::Datelike;
+
+        YearBudget {
+            amount: self.amount,
+            amount_currency: self.amount_currency,
+            comment: self.comment,
+            id: self.id,
+            name: self.name,
+            tags: self.tags,
+            tags_type: self.tags_type,
+            user_id: self.user_id,
+            year: self.year.unwrap_or_else(|| Local::today().year()),
         }
     }
 }
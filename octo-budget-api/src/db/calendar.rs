@@ -0,0 +1,102 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use diesel::prelude::*;
+
+use crate::db::{models::UserSettings, schema::user_settings, PooledConnection};
+use crate::errors::DbResult;
+
+/// Dates as the user sees them: days start at midnight in the user's timezone
+/// and budget months start on the user's `month_start_day`.
+///
+/// Records are stored with UTC timestamps, so every day boundary has to be
+/// converted with `start_of_day` before it is compared with `created_at`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calendar {
+    timezone: Tz,
+    month_start_day: u32,
+}
+
+impl Calendar {
+    pub fn new(settings: &UserSettings) -> Self {
+        Self {
+            // settings are validated on save, so it is never expected to fail
+            timezone: settings.timezone.parse().unwrap_or(Tz::UTC),
+            month_start_day: settings.month_start_day.max(1) as u32,
+        }
+    }
+
+    pub fn timezone(&self) -> &'static str {
+        self.timezone.name()
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.date_of(Utc::now().naive_utc())
+    }
+
+    /// A UTC timestamp in the user's timezone.
+    pub fn local(&self, timestamp: NaiveDateTime) -> DateTime<Tz> {
+        self.timezone.from_utc_datetime(&timestamp)
+    }
+
+    /// The user's local date of a UTC timestamp.
+    pub fn date_of(&self, timestamp: NaiveDateTime) -> NaiveDate {
+        self.local(timestamp).naive_local().date()
+    }
+
+    /// UTC timestamp of the local midnight of `day`.
+    pub fn start_of_day(&self, day: NaiveDate) -> NaiveDateTime {
+        self.to_utc(day.and_hms(0, 0, 0))
+    }
+
+    /// UTC timestamp of a local time of the user. Local times that are
+    /// repeated when DST ends are taken at their first occurrence.
+    pub fn to_utc(&self, mut local: NaiveDateTime) -> NaiveDateTime {
+        // local times skipped when DST starts, e.g. midnight in some
+        // timezones, are moved forward to the first existing one
+        loop {
+            if let Some(time) = self.timezone.from_local_datetime(&local).earliest() {
+                return time.naive_utc();
+            }
+            local += Duration::minutes(30);
+        }
+    }
+
+    /// The budget month `day` belongs to: the first day and the first day of
+    /// the next period.
+    pub fn budget_period(&self, day: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let (year, month) = if day.day() >= self.month_start_day {
+            (day.year(), day.month())
+        } else if day.month() == 1 {
+            (day.year() - 1, 12)
+        } else {
+            (day.year(), day.month() - 1)
+        };
+        let (next_year, next_month) = if month == 12 {
+            (year + 1, 1)
+        } else {
+            (year, month + 1)
+        };
+
+        (
+            NaiveDate::from_ymd(year, month, self.month_start_day),
+            NaiveDate::from_ymd(next_year, next_month, self.month_start_day),
+        )
+    }
+}
+
+/// Settings of the user, or the defaults if the user has never changed them.
+pub fn user_settings(user_id: i32, connection: &PooledConnection) -> DbResult<UserSettings> {
+    let settings = user_settings::table
+        .find(user_id)
+        .first(connection)
+        .optional()?;
+
+    Ok(settings.unwrap_or_else(|| UserSettings::defaults(user_id)))
+}
+
+pub fn user_calendar(user_id: i32, connection: &PooledConnection) -> DbResult<Calendar> {
+    user_settings(user_id, connection).map(|settings| Calendar::new(&settings))
+}
+
+#[cfg(test)]
+mod tests;
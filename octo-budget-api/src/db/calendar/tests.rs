@@ -0,0 +1,107 @@
+use super::*;
+
+fn calendar(timezone: &str, month_start_day: i16) -> Calendar {
+    Calendar::new(&UserSettings {
+        timezone: timezone.to_string(),
+        month_start_day,
+        ..UserSettings::defaults(1)
+    })
+}
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd(y, m, d)
+}
+
+#[test]
+fn date_of_uses_timezone() {
+    let vancouver = calendar("America/Vancouver", 1);
+    let late_evening = date(2020, 3, 1).and_hms(5, 30, 0);
+
+    assert_eq!(date(2020, 2, 29), vancouver.date_of(late_evening));
+    assert_eq!(date(2020, 3, 1), calendar("UTC", 1).date_of(late_evening));
+}
+
+#[test]
+fn start_of_day_is_in_utc() {
+    assert_eq!(
+        date(2020, 3, 1).and_hms(8, 0, 0),
+        calendar("America/Vancouver", 1).start_of_day(date(2020, 3, 1))
+    );
+    // PDT
+    assert_eq!(
+        date(2020, 7, 1).and_hms(7, 0, 0),
+        calendar("America/Vancouver", 1).start_of_day(date(2020, 7, 1))
+    );
+    assert_eq!(
+        date(2020, 2, 29).and_hms(21, 0, 0),
+        calendar("Europe/Minsk", 1).start_of_day(date(2020, 3, 1))
+    );
+}
+
+#[test]
+fn start_of_day_skipped_midnight() {
+    // clocks jump from 00:00 to 01:00 in Santiago when DST starts
+    assert_eq!(
+        date(2019, 9, 8).and_hms(4, 0, 0),
+        calendar("America/Santiago", 1).start_of_day(date(2019, 9, 8))
+    );
+}
+
+#[test]
+fn local_time_to_utc() {
+    let vancouver = calendar("America/Vancouver", 1);
+
+    assert_eq!(
+        date(2020, 3, 2).and_hms(1, 30, 0),
+        vancouver.to_utc(date(2020, 3, 1).and_hms(17, 30, 0))
+    );
+    // 01:30 happens twice when PDT ends, the first one is still PDT
+    assert_eq!(
+        date(2020, 11, 1).and_hms(8, 30, 0),
+        vancouver.to_utc(date(2020, 11, 1).and_hms(1, 30, 0))
+    );
+}
+
+#[test]
+fn local_of_utc_timestamp() {
+    let local = calendar("Europe/Minsk", 1).local(date(2020, 3, 1).and_hms(10, 30, 0));
+
+    assert_eq!("2020-03-01T13:30:00+03:00", local.to_rfc3339());
+}
+
+#[test]
+fn budget_period_is_calendar_month_by_default() {
+    let calendar = calendar("UTC", 1);
+
+    assert_eq!(
+        (date(2020, 3, 1), date(2020, 4, 1)),
+        calendar.budget_period(date(2020, 3, 1))
+    );
+    assert_eq!(
+        (date(2020, 12, 1), date(2021, 1, 1)),
+        calendar.budget_period(date(2020, 12, 31))
+    );
+}
+
+#[test]
+fn budget_period_starts_on_month_start_day() {
+    let calendar = calendar("UTC", 15);
+
+    assert_eq!(
+        (date(2020, 3, 15), date(2020, 4, 15)),
+        calendar.budget_period(date(2020, 3, 15))
+    );
+    assert_eq!(
+        (date(2020, 2, 15), date(2020, 3, 15)),
+        calendar.budget_period(date(2020, 3, 14))
+    );
+    assert_eq!(
+        (date(2019, 12, 15), date(2020, 1, 15)),
+        calendar.budget_period(date(2020, 1, 1))
+    );
+}
+
+#[test]
+fn unknown_timezone_falls_back_to_utc() {
+    assert_eq!("UTC", calendar("Mars/Olympus", 1).timezone());
+}
@@ -0,0 +1,168 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use bigdecimal::{BigDecimal, One};
+use chrono::{Duration, NaiveDate};
+use diesel::prelude::*;
+
+use crate::db::{models::MissingRate, PooledConnection};
+use crate::errors::DbResult;
+
+/// Rates are not published on weekends and holidays, so the latest rate
+/// known on the date is used, unless it is older than this.
+const MAX_RATE_AGE_DAYS: i64 = 7;
+
+/// Converts amounts into `currency` using the exchange rate for the date of
+/// each amount. Amounts without a known rate are remembered and can be
+/// reported back to the user with `missing_rates`.
+///
+/// Rates of every currency are loaded at once for the whole period, amounts
+/// outside of it are converted with a query per amount.
+pub struct Converter<'a> {
+    currency: &'a str,
+    /// first and last day, inclusive
+    period: (NaiveDate, NaiveDate),
+    connection: &'a PooledConnection,
+    rates: HashMap<String, Rates>,
+    missing: BTreeSet<MissingRate>,
+}
+
+impl<'a> Converter<'a> {
+    pub fn new(
+        currency: &'a str,
+        from: NaiveDate,
+        until: NaiveDate,
+        connection: &'a PooledConnection,
+    ) -> Self {
+        Self {
+            currency,
+            period: (from, until),
+            connection,
+            rates: HashMap::new(),
+            missing: BTreeSet::new(),
+        }
+    }
+
+    /// `amount` in the target currency, or `None` if there is no rate for `date`.
+    pub fn convert(
+        &mut self,
+        amount: BigDecimal,
+        currency: &str,
+        date: NaiveDate,
+    ) -> DbResult<Option<BigDecimal>> {
+        if currency == self.currency {
+            return Ok(Some(amount));
+        }
+
+        let (from, until) = self.period;
+        let rate = if date < from || date > until {
+            find_rate(currency, self.currency, date, self.connection)?
+        } else {
+            if !self.rates.contains_key(currency) {
+                let rates = Rates::load(&[currency, self.currency], from, until, self.connection)?;
+                self.rates.insert(currency.to_string(), rates);
+            }
+
+            self.rates[currency].find(currency, self.currency, date)
+        };
+
+        if rate.is_none() {
+            self.missing.insert(MissingRate {
+                currency: currency.to_string(),
+                date,
+            });
+        }
+
+        Ok(rate.map(|rate| amount * rate))
+    }
+
+    pub fn missing_rates(self) -> Vec<MissingRate> {
+        self.missing.into_iter().collect()
+    }
+}
+
+/// Rate to convert `from` into `to` on `date`: either stored directly, as the
+/// inverse pair, or crossed through a common base currency (e.g. EUR for ECB).
+pub fn find_rate(
+    from: &str,
+    to: &str,
+    date: NaiveDate,
+    connection: &PooledConnection,
+) -> DbResult<Option<BigDecimal>> {
+    Ok(Rates::load(&[from, to], date, date, connection)?.find(from, to, date))
+}
+
+/// Stored rates quoted in some currencies, by day. Every rate that can convert
+/// between them is quoted in one of them, directly or through a common base.
+struct Rates {
+    days: BTreeMap<NaiveDate, Vec<(String, String, BigDecimal)>>,
+}
+
+impl Rates {
+    /// Rates usable on days from `from` to `until`, inclusive.
+    fn load(
+        currencies: &[&str],
+        from: NaiveDate,
+        until: NaiveDate,
+        connection: &PooledConnection,
+    ) -> DbResult<Self> {
+        use crate::db::schema::exchange_rates::dsl::*;
+
+        let rows = exchange_rates
+            .select((date, base, quote, rate))
+            .filter(quote.eq_any(currencies))
+            .filter(date.le(until))
+            .filter(date.gt(from - Duration::days(MAX_RATE_AGE_DAYS)))
+            .load::<(NaiveDate, String, String, BigDecimal)>(connection)?;
+
+        let mut days = BTreeMap::new();
+
+        for (day, base_currency, quote_currency, rate_value) in rows {
+            days.entry(day).or_insert_with(Vec::new).push((
+                base_currency,
+                quote_currency,
+                rate_value,
+            ));
+        }
+
+        Ok(Self { days })
+    }
+
+    /// The same as `find_rate`, the latest rate known on the date wins.
+    fn find(&self, from: &str, to: &str, on: NaiveDate) -> Option<BigDecimal> {
+        let recent = || {
+            self.days
+                .range(on - Duration::days(MAX_RATE_AGE_DAYS - 1)..=on)
+                .rev()
+                .map(|(_, rates)| rates)
+        };
+        let stored = |base: &str, quote: &str| {
+            recent()
+                .flatten()
+                .find(|(b, q, _)| b == base && q == quote)
+                .map(|(_, _, rate)| rate.clone())
+        };
+
+        if let Some(rate) = stored(from, to) {
+            return Some(rate);
+        }
+
+        if let Some(rate) = stored(to, from) {
+            return Some(BigDecimal::one() / rate);
+        }
+
+        recent().find_map(|rates| {
+            rates
+                .iter()
+                .filter(|(_, quote, _)| quote == from)
+                .find_map(|(base, _, from_rate)| {
+                    rates
+                        .iter()
+                        .find(|(b, q, _)| b == base && q == to)
+                        .map(|(_, _, to_rate)| to_rate / from_rate)
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
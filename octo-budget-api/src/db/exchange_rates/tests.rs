@@ -0,0 +1,89 @@
+use super::*;
+use crate::tests::DbSession;
+
+fn day(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd(2020, 3, day)
+}
+
+#[test]
+fn finds_direct_inverse_and_cross_rates() {
+    let session = DbSession::new();
+    session.create_exchange_rate(day(2), "EUR", "USD", 1.25);
+    session.create_exchange_rate(day(2), "EUR", "CAD", 1.5);
+
+    let rate = |from, to| find_rate(from, to, day(2), session.conn()).unwrap();
+
+    assert_eq!(Some(BigDecimal::from(1.25)), rate("EUR", "USD"));
+    assert_eq!(Some(BigDecimal::from(0.8)), rate("USD", "EUR"));
+    assert_eq!(Some(BigDecimal::from(1.2)), rate("USD", "CAD"));
+    assert_eq!(None, rate("USD", "BYN"));
+}
+
+#[test]
+fn uses_latest_rate_known_on_the_date() {
+    let session = DbSession::new();
+    session.create_exchange_rate(day(5), "EUR", "USD", 1.1);
+    session.create_exchange_rate(day(6), "EUR", "USD", 1.2);
+    session.create_exchange_rate(day(9), "EUR", "USD", 1.3);
+
+    let rate = |on| find_rate("EUR", "USD", on, session.conn()).unwrap();
+
+    assert_eq!(None, rate(day(4)));
+    assert_eq!(Some(BigDecimal::from(1.2)), rate(day(8)));
+    assert_eq!(Some(BigDecimal::from(1.3)), rate(day(9)));
+    // too old to be used
+    assert_eq!(None, rate(day(17)));
+}
+
+#[test]
+fn converter_remembers_missing_rates() {
+    let session = DbSession::new();
+    session.create_exchange_rate(day(2), "EUR", "CAD", 1.5);
+
+    let mut converter = Converter::new("CAD", day(1), day(2), session.conn());
+    let mut convert = |amount: i32, currency, on| {
+        converter
+            .convert(BigDecimal::from(amount), currency, on)
+            .unwrap()
+    };
+
+    assert_eq!(Some(BigDecimal::from(10)), convert(10, "CAD", day(1)));
+    assert_eq!(Some(BigDecimal::from(15)), convert(10, "EUR", day(2)));
+    assert_eq!(None, convert(10, "EUR", day(1)));
+    assert_eq!(None, convert(10, "USD", day(2)));
+    assert_eq!(None, convert(20, "USD", day(2)));
+
+    assert_eq!(
+        vec![
+            MissingRate {
+                currency: "EUR".to_string(),
+                date: day(1),
+            },
+            MissingRate {
+                currency: "USD".to_string(),
+                date: day(2),
+            },
+        ],
+        converter.missing_rates()
+    );
+}
+
+#[test]
+fn converter_uses_the_same_rates_outside_of_period() {
+    let session = DbSession::new();
+    session.create_exchange_rate(day(2), "EUR", "USD", 1.25);
+    session.create_exchange_rate(day(2), "EUR", "CAD", 1.5);
+    session.create_exchange_rate(day(20), "EUR", "CAD", 2.0);
+
+    let mut converter = Converter::new("CAD", day(2), day(6), session.conn());
+    let mut convert = |currency, on| {
+        converter
+            .convert(BigDecimal::from(10), currency, on)
+            .unwrap()
+    };
+
+    assert_eq!(Some(BigDecimal::from(15)), convert("EUR", day(6)));
+    assert_eq!(Some(BigDecimal::from(12)), convert("USD", day(3)));
+    assert_eq!(Some(BigDecimal::from(20)), convert("EUR", day(20)));
+    assert_eq!(None, convert("EUR", day(1)));
+}
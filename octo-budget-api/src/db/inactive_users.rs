@@ -0,0 +1,32 @@
+use futures::Future;
+use log::error;
+use octo_budget_lib::auth_token::{AuthToken, Revocation};
+use std::pin::Pin;
+
+use crate::db::{queries::IsUserActive, ConnectionPool};
+
+/// Rejects tokens of users deactivated (or deleted) after the token was issued.
+pub struct InactiveUsers(ConnectionPool);
+
+impl InactiveUsers {
+    pub fn new(pool: ConnectionPool) -> Self {
+        Self(pool)
+    }
+}
+
+impl Revocation for InactiveUsers {
+    fn is_revoked(&self, token: &AuthToken) -> Pin<Box<dyn Future<Output = bool>>> {
+        let pool = self.0.clone();
+        let user_id = token.user_id().into();
+
+        Box::pin(async move {
+            match pool.execute(IsUserActive::new(user_id)).await {
+                Ok(is_active) => !is_active,
+                Err(e) => {
+                    error!("Failed to check if user is active: {}", e);
+                    false
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,43 @@
+use futures::Future;
+use log::error;
+use octo_budget_lib::auth_token::{self, Scope, TokenGrant};
+use std::pin::Pin;
+
+use crate::db::{queries::AuthenticatePersonalToken, ConnectionPool};
+
+pub const READ: &str = "read";
+pub const READ_WRITE: &str = "read_write";
+
+pub fn scope(name: &str) -> Option<Scope> {
+    match name {
+        READ => Some(Scope::Read),
+        READ_WRITE => Some(Scope::ReadWrite),
+        _ => None,
+    }
+}
+
+/// Accepts personal access tokens of active users.
+pub struct PersonalTokens(ConnectionPool);
+
+impl PersonalTokens {
+    pub fn new(pool: ConnectionPool) -> Self {
+        Self(pool)
+    }
+}
+
+impl auth_token::PersonalTokens for PersonalTokens {
+    fn authenticate(&self, token: &str) -> Pin<Box<dyn Future<Output = Option<TokenGrant>>>> {
+        let pool = self.0.clone();
+        let query = AuthenticatePersonalToken::new(token);
+
+        Box::pin(async move {
+            match pool.execute(query).await {
+                Ok(grant) => grant,
+                Err(e) => {
+                    error!("Failed to check personal token: {}", e);
+                    None
+                }
+            }
+        })
+    }
+}
@@ -1,17 +1,76 @@
+mod authenticate_personal_token;
+mod budget_crud;
+mod create_personal_token;
 mod create_record;
+mod create_user;
+mod delete_personal_token;
+mod delete_record;
+mod find_active_users_by_email;
 mod find_record;
+mod find_user;
 mod find_user_by_name;
+mod get_budget;
 mod get_budgets;
+mod get_personal_tokens;
 mod get_records;
+mod get_records_batch;
+mod get_tag_report;
+mod get_tag_scores;
+mod get_user_settings;
 mod get_user_tags;
+mod get_year_budget;
+mod get_year_budgets;
+mod import_records;
+mod is_user_active;
+mod issue_refresh_token;
+mod replace_tags;
+mod revoke_refresh_tokens;
+mod rotate_refresh_token;
+mod set_password;
 mod set_user_tags;
+mod suggest_tags;
+mod update_last_login;
+mod update_profile;
 mod update_record;
+mod update_user_settings;
+mod upgrade_password_hash;
 
+pub use authenticate_personal_token::AuthenticatePersonalToken;
+pub use budget_crud::budget::{CreateBudget, DeleteBudget, FindBudget, UpdateBudget};
+pub use budget_crud::year_budget::{
+    CreateYearBudget, DeleteYearBudget, FindYearBudget, UpdateYearBudget,
+};
+pub use create_personal_token::CreatePersonalToken;
 pub use create_record::CreateRecord;
+pub use create_user::CreateUser;
+pub use delete_personal_token::DeletePersonalToken;
+pub use delete_record::DeleteRecord;
+pub use find_active_users_by_email::FindActiveUsersByEmail;
 pub use find_record::FindRecord;
+pub use find_user::FindUser;
 pub use find_user_by_name::FindUserByName;
+pub use get_budget::GetBudget;
 pub use get_budgets::GetBudgets;
+pub use get_personal_tokens::GetPersonalTokens;
 pub use get_records::GetRecords;
+pub use get_records_batch::GetRecordsBatch;
+pub use get_tag_report::{GetTagReport, TagReport, TagSeries};
+pub use get_tag_scores::{GetTagScores, UserTagScores};
+pub use get_user_settings::GetUserSettings;
 pub use get_user_tags::GetUserTags;
+pub use get_year_budget::GetYearBudget;
+pub use get_year_budgets::GetYearBudgets;
+pub use import_records::ImportRecords;
+pub use is_user_active::IsUserActive;
+pub use issue_refresh_token::IssueRefreshToken;
+pub use replace_tags::ReplaceTags;
+pub use revoke_refresh_tokens::RevokeRefreshTokens;
+pub use rotate_refresh_token::{RotateRefreshToken, Rotated};
+pub use set_password::SetPassword;
 pub use set_user_tags::SetUserTags;
+pub use suggest_tags::SuggestTags;
+pub use update_last_login::UpdateLastLogin;
+pub use update_profile::UpdateProfile;
 pub use update_record::UpdateRecord;
+pub use update_user_settings::UpdateUserSettings;
+pub use upgrade_password_hash::UpgradePasswordHash;
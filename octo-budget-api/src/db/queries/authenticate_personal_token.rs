@@ -0,0 +1,59 @@
+use diesel::prelude::*;
+use octo_budget_lib::auth_token::TokenGrant;
+
+use crate::db::{
+    personal_tokens,
+    refresh_tokens::{hash, now},
+    schema::{auth_personal_tokens, auth_user},
+    DatabaseQuery, PooledConnection,
+};
+use crate::errors::DbResult;
+
+/// Finds the owner of a personal token and marks the token as used. Tokens of
+/// inactive users are not accepted.
+pub struct AuthenticatePersonalToken {
+    token_hash: String,
+}
+
+impl AuthenticatePersonalToken {
+    pub fn new(token: &str) -> Self {
+        Self {
+            token_hash: hash(token),
+        }
+    }
+}
+
+impl DatabaseQuery for AuthenticatePersonalToken {
+    type Data = Option<TokenGrant>;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<Option<TokenGrant>> {
+        let found: Option<(i32, i32, String)> = auth_personal_tokens::table
+            .inner_join(auth_user::table)
+            .filter(auth_personal_tokens::token_hash.eq(&self.token_hash))
+            .filter(auth_user::is_active.eq(true))
+            .select((
+                auth_personal_tokens::id,
+                auth_personal_tokens::user_id,
+                auth_personal_tokens::scope,
+            ))
+            .first(&connection)
+            .optional()?;
+
+        let (id, user_id, scope) = match found {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+
+        diesel::update(auth_personal_tokens::table.find(id))
+            .set(auth_personal_tokens::last_used_at.eq(now()))
+            .execute(&connection)?;
+
+        Ok(personal_tokens::scope(&scope).map(|scope| TokenGrant {
+            user_id: user_id.into(),
+            scope,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,74 @@
+use super::*;
+use crate::{
+    apps::forms::personal_token::Data,
+    db::{
+        builders::UserBuilder,
+        queries::{CreatePersonalToken, GetPersonalTokens},
+        ConnectionPool,
+    },
+    tests::DbSession,
+};
+use octo_budget_lib::auth_token::Scope;
+
+async fn create_token(pool: &ConnectionPool, user_id: i32, scope: &str) -> String {
+    let data = Data {
+        name: "script".to_string(),
+        scope: scope.to_string(),
+    };
+
+    pool.execute(CreatePersonalToken::new(user_id, data))
+        .await
+        .unwrap()
+        .1
+}
+
+#[actix_rt::test]
+async fn valid_token() {
+    let pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+    let token = create_token(&pool, user.id, "read_write").await;
+
+    let grant = pool
+        .execute(AuthenticatePersonalToken::new(&token))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        Some(TokenGrant {
+            user_id: user.id.into(),
+            scope: Scope::ReadWrite,
+        }),
+        grant
+    );
+
+    let tokens = pool.execute(GetPersonalTokens::new(user.id)).await.unwrap();
+    assert!(tokens[0].last_used_at.is_some());
+}
+
+#[actix_rt::test]
+async fn unknown_token() {
+    let pool = ConnectionPool::new();
+
+    let grant = pool
+        .execute(AuthenticatePersonalToken::new("unknown"))
+        .await
+        .unwrap();
+
+    assert_eq!(None, grant);
+}
+
+#[actix_rt::test]
+async fn token_of_inactive_user() {
+    let pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().is_active(false));
+    let token = create_token(&pool, user.id, "read").await;
+
+    let grant = pool
+        .execute(AuthenticatePersonalToken::new(&token))
+        .await
+        .unwrap();
+
+    assert_eq!(None, grant);
+}
@@ -0,0 +1,249 @@
+//! Budgets and year budgets differ only by their period, a start date or a
+//! year, so their create, find, update and delete queries are generated by the
+//! same macro for each table.
+
+macro_rules! budget_crud {
+    (
+        table: $table:ident,
+        model: $model:ty,
+        form: $form:ty,
+        period: $period:ident: $period_type:ty,
+        name: $name:literal,
+        create: $create:ident,
+        find: $find:ident,
+        update: $update:ident,
+        delete: $delete:ident $(,)?
+    ) => {
+        use bigdecimal::BigDecimal;
+        use octo_budget_lib::auth_token::UserId;
+
+        use crate::db::{DatabaseQuery, PooledConnection};
+        use crate::errors::{add_table_name, DbError, DbResult};
+
+        pub struct $create {
+            name: String,
+            amount: BigDecimal,
+            amount_currency: String,
+            $period: $period_type,
+            tags: Vec<String>,
+            tags_type: String,
+            comment: String,
+            user_id: i32,
+        }
+
+        impl $create {
+            pub fn new(data: &$form, user_id: UserId) -> Self {
+                let user_id: i32 = user_id.into();
+
+                Self {
+                    name: data.name.clone(),
+                    amount: data.amount.clone(),
+                    amount_currency: data.amount_currency.clone(),
+                    $period: data.$period,
+                    tags: data.tags.clone(),
+                    tags_type: data.tags_type.clone(),
+                    comment: data.comment.clone(),
+                    user_id,
+                }
+            }
+        }
+
+        impl DatabaseQuery for $create {
+            type Data = i32;
+
+            fn execute(&self, connection: PooledConnection) -> DbResult<i32> {
+                use crate::db::schema::$table::dsl::*;
+                use diesel::prelude::*;
+
+                let budget: $model = diesel::insert_into($table)
+                    .values((
+                        name.eq(&self.name),
+                        amount.eq(&self.amount),
+                        amount_currency.eq(&self.amount_currency),
+                        $period.eq(self.$period),
+                        tags.eq(&self.tags),
+                        tags_type.eq(&self.tags_type),
+                        comment.eq(&self.comment),
+                        user_id.eq(self.user_id),
+                    ))
+                    .get_result(&connection)?;
+
+                Ok(budget.id)
+            }
+        }
+
+        pub struct $find {
+            user_id: UserId,
+            id: i32,
+        }
+
+        impl $find {
+            pub fn new(id: i32, user_id: UserId) -> Self {
+                Self { id, user_id }
+            }
+        }
+
+        impl DatabaseQuery for $find {
+            type Data = $model;
+
+            fn execute(&self, connection: PooledConnection) -> DbResult<$model> {
+                find(self.id, self.user_id, &connection)
+            }
+        }
+
+        pub(in crate::db::queries) fn find(
+            budget_id: i32,
+            owner: UserId,
+            connection: &PooledConnection,
+        ) -> DbResult<$model> {
+            use crate::db::schema::$table::dsl::*;
+            use diesel::prelude::*;
+
+            let owner_user_id: i32 = owner.into();
+
+            let budget = $table
+                .filter(user_id.eq(owner_user_id))
+                .filter(id.eq(budget_id))
+                .first(connection)
+                .map_err(add_table_name(stringify!($table)))?;
+
+            Ok(budget)
+        }
+
+        pub struct $update {
+            name: String,
+            amount: BigDecimal,
+            amount_currency: String,
+            $period: $period_type,
+            tags: Vec<String>,
+            tags_type: String,
+            comment: String,
+            user_id: UserId,
+            id: i32,
+        }
+
+        impl $update {
+            pub fn new(id: i32, data: &$form, user_id: UserId) -> Self {
+                Self {
+                    name: data.name.clone(),
+                    amount: data.amount.clone(),
+                    amount_currency: data.amount_currency.clone(),
+                    $period: data.$period,
+                    tags: data.tags.clone(),
+                    tags_type: data.tags_type.clone(),
+                    comment: data.comment.clone(),
+                    user_id,
+                    id,
+                }
+            }
+        }
+
+        impl DatabaseQuery for $update {
+            type Data = ();
+
+            fn execute(&self, connection: PooledConnection) -> DbResult<()> {
+                use crate::db::schema::$table::dsl::*;
+                use diesel::prelude::*;
+
+                let current_user_id: i32 = self.user_id.into();
+
+                let target = $table
+                    .filter(user_id.eq(current_user_id))
+                    .filter(id.eq(self.id));
+
+                let result = diesel::update(target)
+                    .set((
+                        name.eq(&self.name),
+                        amount.eq(&self.amount),
+                        amount_currency.eq(&self.amount_currency),
+                        $period.eq(self.$period),
+                        tags.eq(&self.tags),
+                        tags_type.eq(&self.tags_type),
+                        comment.eq(&self.comment),
+                    ))
+                    .execute(&connection);
+
+                match result {
+                    Ok(1) => Ok(()),
+                    Ok(0) => Err(DbError::NotUpdated(stringify!($table), self.id)),
+                    Ok(_) => Err(DbError::UnexpectedResult(concat!(
+                        "More than one ",
+                        $name,
+                        " updated"
+                    ))),
+                    Err(err) => Err(DbError::Unknown(err)),
+                }
+            }
+        }
+
+        pub struct $delete {
+            user_id: UserId,
+            id: i32,
+        }
+
+        impl $delete {
+            pub fn new(id: i32, user_id: UserId) -> Self {
+                Self { id, user_id }
+            }
+        }
+
+        impl DatabaseQuery for $delete {
+            type Data = ();
+
+            fn execute(&self, connection: PooledConnection) -> DbResult<()> {
+                use crate::db::schema::$table::dsl::*;
+                use diesel::prelude::*;
+
+                let owner_user_id: i32 = self.user_id.into();
+
+                let target = $table
+                    .filter(user_id.eq(owner_user_id))
+                    .filter(id.eq(self.id));
+
+                match diesel::delete(target).execute(&connection) {
+                    Ok(1) => Ok(()),
+                    Ok(0) => Err(DbError::NotFound(stringify!($table))),
+                    Ok(_) => Err(DbError::UnexpectedResult(concat!(
+                        "More than one ",
+                        $name,
+                        " deleted"
+                    ))),
+                    Err(err) => Err(DbError::Unknown(err)),
+                }
+            }
+        }
+    };
+}
+
+pub mod budget {
+    use chrono::NaiveDate;
+
+    budget_crud! {
+        table: budgets_budget,
+        model: crate::db::models::Budget,
+        form: crate::apps::forms::budget::FormData,
+        period: start_date: NaiveDate,
+        name: "budget",
+        create: CreateBudget,
+        find: FindBudget,
+        update: UpdateBudget,
+        delete: DeleteBudget,
+    }
+}
+
+pub mod year_budget {
+    budget_crud! {
+        table: budgets_yearbudget,
+        model: crate::db::models::YearBudget,
+        form: crate::apps::forms::year_budget::FormData,
+        period: year: i32,
+        name: "year budget",
+        create: CreateYearBudget,
+        find: FindYearBudget,
+        update: UpdateYearBudget,
+        delete: DeleteYearBudget,
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,204 @@
+macro_rules! budget_crud_tests {
+    (
+        queries: $queries:ident,
+        form: $form:path,
+        builder: $builder:ident,
+        period: $period:ident = $period_value:expr,
+        table: $table:literal,
+        session: ($create_fn:ident, $find_fn:ident, $count_fn:ident),
+        create: $create:ident,
+        find: $find:ident,
+        update: $update:ident,
+        delete: $delete:ident $(,)?
+    ) => {
+        use bigdecimal::BigDecimal;
+        use octo_budget_lib::auth_token::UserId;
+
+        use crate::db::builders::{$builder, UserBuilder};
+        use crate::db::queries::budget_crud::$queries::*;
+        use crate::db::ConnectionPool;
+        use crate::tags_vec;
+        use crate::tests::DbSession;
+
+        fn form_data() -> $form {
+            $form {
+                name: "Travel".into(),
+                amount: BigDecimal::from(1000),
+                amount_currency: "CAD".into(),
+                $period: $period_value,
+                tags: tags_vec!["travel"],
+                tags_type: "EXCL".into(),
+                comment: "trips".into(),
+            }
+        }
+
+        #[actix_rt::test]
+        async fn create_for_user() {
+            let conn_pool = ConnectionPool::new();
+            let session = DbSession::new();
+
+            let user = session.create_user(UserBuilder::default());
+
+            let id = conn_pool
+                .execute($create::new(&form_data(), user.id.into()))
+                .await
+                .expect("Failed to create budget");
+
+            let budget = session.$find_fn(id);
+
+            assert_eq!(id, budget.id);
+            assert_eq!("Travel", budget.name);
+            assert_eq!(BigDecimal::from(1000), budget.amount);
+            assert_eq!("CAD", budget.amount_currency);
+            assert_eq!($period_value, budget.$period);
+            assert_eq!(tags_vec!["travel"], budget.tags);
+            assert_eq!("EXCL", budget.tags_type);
+            assert_eq!(Some("trips".to_string()), budget.comment);
+            assert_eq!(user.id, budget.user_id);
+        }
+
+        #[actix_rt::test]
+        async fn find_by_user_id() {
+            let conn_pool = ConnectionPool::new();
+            let mut session = DbSession::new();
+
+            let user = session.create_user(UserBuilder::default());
+            let budget = session.$create_fn($builder::default().user_id(user.id).finish());
+
+            let result_budget = conn_pool
+                .execute($find::new(budget.id, user.id.into()))
+                .await
+                .expect("Failed to find budget");
+
+            assert_eq!(budget, result_budget);
+        }
+
+        #[actix_rt::test]
+        async fn does_not_find_budget_of_other_user() {
+            let conn_pool = ConnectionPool::new();
+            let mut session = DbSession::new();
+
+            let owner = session.create_user(UserBuilder::default().username("foo"));
+            let other_user = session.create_user(UserBuilder::default().username("bar"));
+            let budget = session.$create_fn($builder::default().user_id(other_user.id).finish());
+
+            let error = conn_pool
+                .execute($find::new(budget.id, owner.id.into()))
+                .await
+                .expect_err("Is not expected to find anything");
+
+            assert_eq!(
+                concat!("Failed to find record from table ", $table),
+                error.to_string()
+            );
+        }
+
+        #[actix_rt::test]
+        async fn no_budget_updated() {
+            let conn_pool = ConnectionPool::new();
+            let user_id: UserId = 1.into();
+
+            let error = conn_pool
+                .execute($update::new(1, &form_data(), user_id))
+                .await
+                .expect_err("Is not expected to update anything");
+
+            assert_eq!(
+                concat!("Cannot update ", $table, " with id: `1'"),
+                error.to_string()
+            );
+        }
+
+        #[actix_rt::test]
+        async fn check_update_result() {
+            let conn_pool = ConnectionPool::new();
+            let mut session = DbSession::new();
+
+            let user = session.create_user(UserBuilder::default());
+            let budget = session.$create_fn($builder::default().user_id(user.id).finish());
+
+            conn_pool
+                .execute($update::new(budget.id, &form_data(), user.id.into()))
+                .await
+                .expect("Failed to update budget");
+
+            let updated_budget = session.$find_fn(budget.id);
+
+            assert_eq!("Travel", updated_budget.name);
+            assert_eq!(BigDecimal::from(1000), updated_budget.amount);
+            assert_eq!($period_value, updated_budget.$period);
+            assert_eq!(tags_vec!["travel"], updated_budget.tags);
+            assert_eq!("EXCL", updated_budget.tags_type);
+            assert_eq!(Some("trips".to_string()), updated_budget.comment);
+        }
+
+        #[actix_rt::test]
+        async fn delete_by_user_id() {
+            let conn_pool = ConnectionPool::new();
+            let mut session = DbSession::new();
+
+            let user = session.create_user(UserBuilder::default());
+            let budget = session.$create_fn($builder::default().user_id(user.id).finish());
+
+            conn_pool
+                .execute($delete::new(budget.id, user.id.into()))
+                .await
+                .expect("Failed to delete budget");
+
+            assert_eq!(0, session.$count_fn());
+        }
+
+        #[actix_rt::test]
+        async fn does_not_delete_budget_of_other_user() {
+            let conn_pool = ConnectionPool::new();
+            let mut session = DbSession::new();
+
+            let owner = session.create_user(UserBuilder::default().username("foo"));
+            let other_user = session.create_user(UserBuilder::default().username("bar"));
+            let budget = session.$create_fn($builder::default().user_id(other_user.id).finish());
+
+            let error = conn_pool
+                .execute($delete::new(budget.id, owner.id.into()))
+                .await
+                .expect_err("Is not expected to delete anything");
+
+            assert_eq!(
+                concat!("Failed to find record from table ", $table),
+                error.to_string()
+            );
+            assert_eq!(1, session.$count_fn());
+        }
+    };
+}
+
+mod budget {
+    use chrono::NaiveDate;
+
+    budget_crud_tests! {
+        queries: budget,
+        form: crate::apps::forms::budget::FormData,
+        builder: BudgetBuilder,
+        period: start_date = NaiveDate::from_ymd(2020, 1, 1),
+        table: "budgets_budget",
+        session: (create_budget, find_budget, count_budgets),
+        create: CreateBudget,
+        find: FindBudget,
+        update: UpdateBudget,
+        delete: DeleteBudget,
+    }
+}
+
+mod year_budget {
+    budget_crud_tests! {
+        queries: year_budget,
+        form: crate::apps::forms::year_budget::FormData,
+        builder: YearBudgetBuilder,
+        period: year = 2021,
+        table: "budgets_yearbudget",
+        session: (create_year_budget, find_year_budget, count_year_budgets),
+        create: CreateYearBudget,
+        find: FindYearBudget,
+        update: UpdateYearBudget,
+        delete: DeleteYearBudget,
+    }
+}
@@ -0,0 +1,51 @@
+use diesel::prelude::*;
+
+use crate::apps::forms::personal_token::Data;
+use crate::db::{
+    models::{NewPersonalToken, PersonalToken},
+    refresh_tokens::{generate, hash, now},
+    schema::auth_personal_tokens,
+    DatabaseQuery, PooledConnection,
+};
+use crate::errors::DbResult;
+
+/// Stores a new token and returns it along with the token in plain text, which
+/// is not possible to get later.
+pub struct CreatePersonalToken {
+    user_id: i32,
+    name: String,
+    scope: String,
+}
+
+impl CreatePersonalToken {
+    pub fn new(user_id: i32, data: Data) -> Self {
+        Self {
+            user_id,
+            name: data.name,
+            scope: data.scope,
+        }
+    }
+}
+
+impl DatabaseQuery for CreatePersonalToken {
+    type Data = (PersonalToken, String);
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<(PersonalToken, String)> {
+        let token = generate();
+
+        let personal_token = diesel::insert_into(auth_personal_tokens::table)
+            .values(NewPersonalToken {
+                user_id: self.user_id,
+                name: self.name.clone(),
+                token_hash: hash(&token),
+                scope: self.scope.clone(),
+                created_at: now(),
+            })
+            .get_result(&connection)?;
+
+        Ok((personal_token, token))
+    }
+}
+
+#[cfg(test)]
+mod tests;
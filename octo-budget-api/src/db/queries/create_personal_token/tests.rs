@@ -0,0 +1,27 @@
+use super::*;
+use crate::{
+    db::{builders::UserBuilder, refresh_tokens::hash, ConnectionPool},
+    tests::DbSession,
+};
+
+#[actix_rt::test]
+async fn stores_hash_of_the_token() {
+    let pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+    let data = Data {
+        name: "import".to_string(),
+        scope: "read".to_string(),
+    };
+
+    let (personal_token, token) = pool
+        .execute(CreatePersonalToken::new(user.id, data))
+        .await
+        .unwrap();
+
+    assert_eq!(user.id, personal_token.user_id);
+    assert_eq!("import", personal_token.name);
+    assert_eq!("read", personal_token.scope);
+    assert_eq!(hash(&token), personal_token.token_hash);
+    assert_eq!(None, personal_token.last_used_at);
+}
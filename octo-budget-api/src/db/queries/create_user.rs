@@ -0,0 +1,55 @@
+use crate::apps::forms::registration::Data;
+use crate::db::{models::AuthUser, DatabaseQuery, PooledConnection};
+use crate::errors::DbResult;
+
+/// Creates a regular (not staff) user. Returns `None` if the username is taken.
+pub struct CreateUser {
+    username: String,
+    email: String,
+    password: String,
+}
+
+impl CreateUser {
+    pub fn new(data: &Data) -> Self {
+        Self {
+            username: data.username.clone(),
+            email: data.email.clone(),
+            password: data.password.clone(),
+        }
+    }
+}
+
+impl DatabaseQuery for CreateUser {
+    type Data = Option<AuthUser>;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<Option<AuthUser>> {
+        use crate::db::schema::auth_user::dsl::*;
+        use chrono::Local;
+        use diesel::prelude::*;
+        use diesel::*;
+
+        let hashed_password = djangohashers::make_password(&self.password);
+
+        let user = insert_into(auth_user)
+            .values((
+                username.eq(&self.username),
+                password.eq(hashed_password),
+                email.eq(&self.email),
+                is_superuser.eq(false),
+                is_staff.eq(false),
+                is_active.eq(true),
+                first_name.eq(""),
+                last_name.eq(""),
+                date_joined.eq(Local::now().naive_local()),
+            ))
+            .on_conflict(username)
+            .do_nothing()
+            .get_result(&connection)
+            .optional()?;
+
+        Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,50 @@
+use super::*;
+use crate::{
+    db::{builders::UserBuilder, ConnectionPool},
+    tests::DbSession,
+};
+
+fn data(username: &str) -> Data {
+    Data {
+        username: username.to_string(),
+        email: "john@example.com".to_string(),
+        password: "correct horse".to_string(),
+    }
+}
+
+#[actix_rt::test]
+async fn creates_regular_user() {
+    let conn_pool = ConnectionPool::new();
+    let _session = DbSession::new();
+
+    let user = conn_pool
+        .execute(CreateUser::new(&data("john")))
+        .await
+        .expect("Failed to create user")
+        .expect("User is expected to be created");
+
+    assert_eq!("john", user.username);
+    assert_eq!("john@example.com", user.email);
+    assert!(user.is_active);
+    assert!(!user.is_staff);
+    assert!(!user.is_superuser);
+    assert_eq!(
+        Ok(true),
+        djangohashers::check_password("correct horse", &user.password)
+    );
+}
+
+#[actix_rt::test]
+async fn does_not_create_user_with_taken_username() {
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+
+    session.create_user(UserBuilder::default().username("john"));
+
+    let user = conn_pool
+        .execute(CreateUser::new(&data("john")))
+        .await
+        .expect("Failed to create user");
+
+    assert_eq!(None, user);
+}
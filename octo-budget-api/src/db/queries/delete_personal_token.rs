@@ -0,0 +1,35 @@
+use diesel::prelude::*;
+
+use crate::db::{
+    models::PersonalToken, schema::auth_personal_tokens, DatabaseQuery, PooledConnection,
+};
+use crate::errors::{add_table_name, DbResult};
+
+/// Revokes a token of the user, it stops working immediately.
+pub struct DeletePersonalToken {
+    id: i32,
+    user_id: i32,
+}
+
+impl DeletePersonalToken {
+    pub fn new(id: i32, user_id: i32) -> Self {
+        Self { id, user_id }
+    }
+}
+
+impl DatabaseQuery for DeletePersonalToken {
+    type Data = PersonalToken;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<PersonalToken> {
+        let target = auth_personal_tokens::table
+            .filter(auth_personal_tokens::user_id.eq(self.user_id))
+            .filter(auth_personal_tokens::id.eq(self.id));
+
+        diesel::delete(target)
+            .get_result(&connection)
+            .map_err(add_table_name("auth_personal_tokens"))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,44 @@
+use super::*;
+use crate::{
+    apps::forms::personal_token::Data,
+    db::{
+        builders::UserBuilder,
+        queries::{CreatePersonalToken, GetPersonalTokens},
+        ConnectionPool,
+    },
+    tests::DbSession,
+};
+
+#[actix_rt::test]
+async fn deletes_only_own_tokens() {
+    let pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().username("john"));
+    let other = session.create_user(UserBuilder::default().username("jane"));
+    let data = Data {
+        name: "script".to_string(),
+        scope: "read".to_string(),
+    };
+    let (personal_token, _) = pool
+        .execute(CreatePersonalToken::new(user.id, data))
+        .await
+        .unwrap();
+
+    let error = pool
+        .execute(DeletePersonalToken::new(personal_token.id, other.id))
+        .await
+        .expect_err("Token of other user must not be deleted");
+    assert_eq!(
+        "Failed to find record from table auth_personal_tokens",
+        error.to_string()
+    );
+
+    let deleted = pool
+        .execute(DeletePersonalToken::new(personal_token.id, user.id))
+        .await
+        .unwrap();
+    assert_eq!(personal_token, deleted);
+
+    let tokens = pool.execute(GetPersonalTokens::new(user.id)).await.unwrap();
+    assert!(tokens.is_empty());
+}
@@ -0,0 +1,54 @@
+use super::*;
+use crate::{
+    db::{builders::UserBuilder, queries::FindRecord, ConnectionPool},
+    tests::DbSession,
+};
+
+#[actix_rt::test]
+async fn delete_by_user_id() {
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default());
+    let record = session.create_record2(user.id);
+
+    let deleted_record = conn_pool
+        .execute(DeleteRecord::new(record.id, user.id.into()))
+        .await
+        .expect("Failed to delete record");
+
+    assert_eq!(record, deleted_record);
+
+    let error = conn_pool
+        .execute(FindRecord::new(record.id, user.id.into()))
+        .await
+        .expect_err("Record is expected to be deleted");
+
+    assert_eq!(
+        "Failed to find record from table records_record",
+        error.to_string()
+    );
+}
+
+#[actix_rt::test]
+async fn does_not_delete_record_of_other_user() {
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+
+    let owner = session.create_user(UserBuilder::default().username("foo"));
+    let other_user = session.create_user(UserBuilder::default().username("bar"));
+    let record = session.create_record2(other_user.id);
+
+    let error = conn_pool
+        .execute(DeleteRecord::new(record.id, owner.id.into()))
+        .await
+        .expect_err("Is not expected to delete anything");
+
+    assert_eq!(
+        "Failed to find record from table records_record",
+        error.to_string()
+    );
+
+    // record of the other user is still there
+    assert_eq!(record, session.find_record(record.id));
+}
@@ -0,0 +1,32 @@
+use crate::errors::DbResult;
+use diesel::prelude::*;
+
+use crate::db::{models::AuthUser, schema::auth_user, DatabaseQuery, PooledConnection};
+
+sql_function!(fn lower(x: diesel::sql_types::Text) -> diesel::sql_types::Text);
+
+/// Emails are not unique, several accounts may share one.
+pub struct FindActiveUsersByEmail(String);
+
+impl FindActiveUsersByEmail {
+    pub fn new(email: impl Into<String>) -> Self {
+        Self(email.into())
+    }
+}
+
+impl DatabaseQuery for FindActiveUsersByEmail {
+    type Data = Vec<AuthUser>;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<Self::Data> {
+        let users = auth_user::table
+            .filter(lower(auth_user::email).eq(self.0.to_lowercase()))
+            .filter(auth_user::is_active.eq(true))
+            .order(auth_user::id)
+            .load(&connection)?;
+
+        Ok(users)
+    }
+}
+
+#[cfg(test)]
+mod tests;
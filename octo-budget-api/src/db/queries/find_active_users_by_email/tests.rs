@@ -0,0 +1,36 @@
+use super::*;
+use crate::db::{builders::UserBuilder, ConnectionPool};
+
+#[actix_rt::test]
+async fn finds_active_users_ignoring_case() {
+    let pool = ConnectionPool::new();
+    let session = pool.start_session();
+    let john = session.create_user(
+        UserBuilder::default()
+            .username("john")
+            .email("John@example.com"),
+    );
+    let johnny = session.create_user(
+        UserBuilder::default()
+            .username("johnny")
+            .email("john@example.com"),
+    );
+    session.create_user(
+        UserBuilder::default()
+            .username("inactive")
+            .email("john@example.com")
+            .is_active(false),
+    );
+    session.create_user(
+        UserBuilder::default()
+            .username("jane")
+            .email("jane@example.com"),
+    );
+
+    let users = pool
+        .execute(FindActiveUsersByEmail::new("JOHN@example.com"))
+        .await
+        .expect("failed to find users");
+
+    assert_eq!(vec![john, johnny], users);
+}
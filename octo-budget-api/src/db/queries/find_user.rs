@@ -0,0 +1,28 @@
+use crate::errors::{add_table_name, DbResult};
+use diesel::prelude::*;
+
+use crate::db::{models::AuthUser, schema::auth_user, DatabaseQuery, PooledConnection};
+
+pub struct FindUser(i32);
+
+impl FindUser {
+    pub fn new(user_id: impl Into<i32>) -> Self {
+        Self(user_id.into())
+    }
+}
+
+impl DatabaseQuery for FindUser {
+    type Data = AuthUser;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<Self::Data> {
+        let user = auth_user::table
+            .find(self.0)
+            .first(&connection)
+            .map_err(add_table_name("auth_user"))?;
+
+        Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,27 @@
+use super::*;
+use crate::db::{builders::UserBuilder, ConnectionPool};
+
+#[actix_rt::test]
+async fn not_found_err() {
+    let pool = ConnectionPool::new();
+    let error = pool
+        .execute(FindUser::new(-1))
+        .await
+        .expect_err("Is not expected to find anything");
+
+    assert_eq!(
+        "Failed to find record from table auth_user",
+        error.to_string()
+    );
+}
+
+#[actix_rt::test]
+async fn found() {
+    let pool = ConnectionPool::new();
+    let session = pool.start_session();
+    let user = session.create_user(UserBuilder::default());
+
+    let result = pool.execute(FindUser::new(user.id)).await;
+
+    assert_eq!(user, result.expect("failed to find user"));
+}
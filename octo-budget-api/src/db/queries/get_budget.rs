@@ -0,0 +1,32 @@
+use octo_budget_lib::auth_token::UserId;
+
+use super::{budget_crud::budget::find, get_budgets::serialize_budget};
+use crate::db::{
+    calendar::user_calendar, models::SerializedBudget, DatabaseQuery, PooledConnection,
+};
+use crate::errors::DbResult;
+
+pub struct GetBudget {
+    user_id: UserId,
+    id: i32,
+}
+
+impl GetBudget {
+    pub fn new(id: i32, user_id: UserId) -> Self {
+        Self { id, user_id }
+    }
+}
+
+impl DatabaseQuery for GetBudget {
+    type Data = SerializedBudget;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<SerializedBudget> {
+        let budget = find(self.id, self.user_id, &connection)?;
+        let calendar = user_calendar(budget.user_id, &connection)?;
+
+        serialize_budget(budget, &calendar, &connection)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,56 @@
+use super::*;
+use crate::{
+    db::{
+        builders::{BudgetBuilder, UserBuilder},
+        ConnectionPool,
+    },
+    tests::DbSession,
+};
+use bigdecimal::BigDecimal;
+
+#[actix_rt::test]
+async fn get_serialized_budget() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default());
+    let budget = session.create_budget(
+        BudgetBuilder::default()
+            .user_id(user.id)
+            .name("Food")
+            .amount(300.0)
+            .finish(),
+    );
+
+    let result = conn_pool
+        .execute(GetBudget::new(budget.id, user.id.into()))
+        .await
+        .expect("Failed to get budget");
+
+    assert_eq!(budget.id, result.id);
+    assert_eq!("Food", result.name);
+    assert_eq!(BigDecimal::from(300), result.amount);
+    assert_eq!(BigDecimal::from(0), result.spent);
+    assert_eq!(BigDecimal::from(300), result.left);
+}
+
+#[actix_rt::test]
+async fn does_not_return_budget_of_other_user() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let owner = session.create_user(UserBuilder::default().username("foo"));
+    let other_user = session.create_user(UserBuilder::default().username("bar"));
+    let budget = session.create_budget(BudgetBuilder::default().user_id(other_user.id).finish());
+
+    let error = conn_pool
+        .execute(GetBudget::new(budget.id, owner.id.into()))
+        .await
+        .err()
+        .expect("Is not expected to find anything");
+
+    assert_eq!(
+        "Failed to find record from table budgets_budget",
+        error.to_string()
+    );
+}
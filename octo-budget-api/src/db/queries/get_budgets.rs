@@ -1,10 +1,12 @@
 use bigdecimal::{BigDecimal, Zero};
-use chrono::{Datelike, Local, NaiveDate};
+use chrono::NaiveDate;
 use diesel::prelude::*;
 
 use crate::apps::index_response::Data;
 use crate::db::{
-    models::{Budget, SerializedBudget},
+    calendar::{user_calendar, Calendar},
+    exchange_rates::Converter,
+    models::{Budget, MissingRate, SerializedBudget},
     pagination::*,
     schema::budgets_budget,
     DatabaseQuery, PooledConnection,
@@ -28,78 +30,146 @@ impl DatabaseQuery for GetBudgets {
     }
 }
 
-fn budget_spent(budget: &Budget, connection: &PooledConnection) -> DbResult<BigDecimal> {
-    use crate::db::schema::records_record;
-    use diesel::dsl::{not, sum};
-
-    let first_month_day = Local::now().naive_local().with_day0(0).unwrap();
-
-    let query = records_record::table
-        .select(sum(records_record::amount))
-        .filter(
-            records_record::user_id.eq(budget.user_id).and(
-                records_record::transaction_type
-                    .eq("EXP")
-                    .and(records_record::created_at.ge(first_month_day)),
-            ),
-        );
-
-    let query_result = match budget.tags_type.as_str() {
-        "INCL" => query
-            .filter(records_record::tags.overlaps_with(&budget.tags))
-            .first::<Option<BigDecimal>>(connection)?,
-        "EXCL" => query
-            .filter(not(records_record::tags.overlaps_with(&budget.tags)))
-            .first::<Option<BigDecimal>>(connection)?,
-        _ => query.first::<Option<BigDecimal>>(connection)?,
-    };
-
-    Ok(query_result.unwrap_or_else(BigDecimal::zero).with_scale(2))
+fn budget_spent(
+    budget: &Budget,
+    calendar: &Calendar,
+    connection: &PooledConnection,
+) -> DbResult<Spent> {
+    let (from, until) = calendar.budget_period(calendar.today());
+
+    spent_between(
+        BudgetFilter {
+            user_id: budget.user_id,
+            currency: &budget.amount_currency,
+            tags_type: &budget.tags_type,
+            tags: &budget.tags,
+        },
+        calendar,
+        from,
+        until,
+        connection,
+    )
 }
 
-fn ndays_in_the_current_month(today: NaiveDate) -> u32 {
-    let year = today.year();
-    let month = today.month();
-
-    // the first day of the next month...
-    let (y, m) = if month == 12 {
-        (year + 1, 1)
-    } else {
-        (year, month + 1)
-    };
-    let d = NaiveDate::from_ymd(y, m, 1);
-
-    // ...is preceded by the last day of the original month
-    d.pred().day()
+/// Which records count towards a budget and the currency it is kept in.
+pub(super) struct BudgetFilter<'a> {
+    pub user_id: i32,
+    pub currency: &'a str,
+    pub tags_type: &'a str,
+    pub tags: &'a [String],
 }
 
-// TODO: add tests
-fn serialize_budget(budget: Budget, conn: &PooledConnection) -> DbResult<SerializedBudget> {
-    use bigdecimal::ToPrimitive;
-
-    let mut res = SerializedBudget::default();
-    let today = Local::today().naive_local();
-    let spent = budget_spent(&budget, conn)?;
-    let days_in_this_month = ndays_in_the_current_month(today);
+pub(super) struct Spent {
+    pub amount: BigDecimal,
+    /// records that were not converted into the budget currency and thus are
+    /// not included into `amount`
+    pub missing_rates: Vec<MissingRate>,
+}
 
-    // we need to take into account spendings for today
-    let rest_days = days_in_this_month - today.day0() + 1;
+#[derive(QueryableByName)]
+struct DailySpent {
+    #[sql_type = "diesel::sql_types::Text"]
+    currency: String,
+    #[sql_type = "diesel::sql_types::Date"]
+    day: NaiveDate,
+    #[sql_type = "diesel::sql_types::Numeric"]
+    total: BigDecimal,
+}
 
-    let left = (budget.amount.clone() - spent.clone())
-        .to_f64()
-        .unwrap_or(0.0);
+// a tag of $5 matches itself and its descendants by name, the same as in
+// `db::tags::is_descendant`, whether the tags are in the `tags` table or not
+const SPENT_QUERY: &str = "
+    SELECT amount_currency AS currency, (created_at AT TIME ZONE $6)::date AS day,
+        SUM(amount) AS total
+    FROM records_record
+    WHERE user_id = $1
+      AND transaction_type = 'EXP'
+      AND created_at >= $2
+      AND created_at < $3
+      AND CASE $4
+          WHEN 'INCL' THEN EXISTS (
+              SELECT 1 FROM unnest(tags) AS t(tag), unnest($5::text[]) AS b(tag)
+              WHERE t.tag = b.tag OR left(t.tag, length(b.tag) + 1) = b.tag || '/'
+          )
+          WHEN 'EXCL' THEN NOT EXISTS (
+              SELECT 1 FROM unnest(tags) AS t(tag), unnest($5::text[]) AS b(tag)
+              WHERE t.tag = b.tag OR left(t.tag, length(b.tag) + 1) = b.tag || '/'
+          )
+          ELSE TRUE
+      END
+    GROUP BY currency, day
+";
+
+/// Sum of expenses of the user made on days from `from` and before `until`
+/// in the user's timezone, filtered by tags the same way as for budgets: a tag
+/// of a budget stands for itself and all its descendants. Every
+/// record is converted into the budget currency using the exchange rate for
+/// the day it was made.
+pub(super) fn spent_between(
+    filter: BudgetFilter,
+    calendar: &Calendar,
+    from: NaiveDate,
+    until: NaiveDate,
+    connection: &PooledConnection,
+) -> DbResult<Spent> {
+    use diesel::sql_types::{Array, Int4, Text, Timestamptz};
+
+    let rows = diesel::sql_query(SPENT_QUERY)
+        .bind::<Int4, _>(filter.user_id)
+        .bind::<Timestamptz, _>(calendar.start_of_day(from))
+        .bind::<Timestamptz, _>(calendar.start_of_day(until))
+        .bind::<Text, _>(filter.tags_type)
+        .bind::<Array<Text>, _>(filter.tags)
+        .bind::<Text, _>(calendar.timezone())
+        .load::<DailySpent>(connection)?;
+
+    let mut converter = Converter::new(filter.currency, from, until.pred(), connection);
+    let mut amount = BigDecimal::zero();
+
+    for row in rows {
+        if let Some(total) = converter.convert(row.total, &row.currency, row.day)? {
+            amount += total;
+        }
+    }
 
-    res.spent = spent.to_f64().unwrap_or(0.0);
-    res.left = left;
-    res.average_per_day = (budget.amount.clone() / BigDecimal::from(days_in_this_month))
-        .to_f64()
-        .unwrap_or(0.0);
+    Ok(Spent {
+        amount: amount.with_scale(2),
+        missing_rates: converter.missing_rates(),
+    })
+}
 
-    res.left_average_per_day = left / rest_days.to_f64().unwrap_or(0.0f64);
-    res.name = budget.name;
-    res.amount = budget.amount;
+pub(super) fn serialize_budget(
+    budget: Budget,
+    calendar: &Calendar,
+    conn: &PooledConnection,
+) -> DbResult<SerializedBudget> {
+    let today = calendar.today();
+    let (first_day, next_period) = calendar.budget_period(today);
+    let spent = budget_spent(&budget, calendar, conn)?;
+    let days_in_period = (next_period - first_day).num_days();
 
-    Ok(res)
+    // we need to take into account spendings for today
+    let rest_days = (next_period - today).num_days();
+
+    let left = budget.amount.clone() - spent.amount.clone();
+    let average_per_day = budget.amount.clone() / BigDecimal::from(days_in_period);
+    let left_average_per_day = left.clone() / BigDecimal::from(rest_days);
+
+    Ok(SerializedBudget {
+        id: budget.id,
+        name: budget.name,
+        amount: budget.amount,
+        amount_currency: budget.amount_currency,
+        start_date: budget.start_date,
+        tags: budget.tags,
+        tags_type: budget.tags_type,
+        comment: budget.comment,
+        spent: spent.amount,
+        left,
+        average_per_day,
+        left_average_per_day,
+        missing_rates: spent.missing_rates,
+    })
 }
 
 fn get_page_of_budgets(msg: &GetBudgets, conn: &PooledConnection) -> DbResult<(Vec<Budget>, i64)> {
@@ -122,10 +192,11 @@ fn get_page_of_budgets(msg: &GetBudgets, conn: &PooledConnection) -> DbResult<(V
 fn handle(msg: &GetBudgets, conn: &PooledConnection) -> GetBudgetsResult {
     let (results, total) = get_page_of_budgets(&msg, conn)?;
     let total_pages = (total as f64 / msg.per_page as f64).ceil() as i64;
+    let calendar = user_calendar(msg.user_id, conn)?;
 
     let results = results
         .into_iter()
-        .map(|budget| serialize_budget(budget, conn))
+        .map(|budget| serialize_budget(budget, &calendar, conn))
         .collect::<DbResult<Vec<SerializedBudget>>>()?;
 
     let previous = msg.page > 1;
@@ -1,7 +1,13 @@
 use super::*;
 use crate::db::builders::{BudgetBuilder, RecordBuilder, UserBuilder};
+use crate::db::models::UserSettings;
 use crate::tests::DbSession;
 use bigdecimal::ToPrimitive;
+use chrono::Duration;
+
+fn utc() -> Calendar {
+    Calendar::new(&UserSettings::defaults(1))
+}
 
 #[test]
 fn test_empty_result() {
@@ -105,7 +111,9 @@ fn amount_aggregation_with_other_tags_type() {
 
     session.create_record(record.clone().amount(1.123).finish());
 
-    let amount = budget_spent(&budget, session.conn()).unwrap();
+    let amount = budget_spent(&budget, &utc(), session.conn())
+        .unwrap()
+        .amount;
 
     assert_eq!(1.12, amount.to_f64().unwrap());
 }
@@ -128,7 +136,9 @@ fn amount_aggregation_with_including_tags() {
         session.create_record(record.clone().amount(*amount).tags(vec![tag]).finish());
     }
 
-    let amount = budget_spent(&budget, session.conn()).unwrap();
+    let amount = budget_spent(&budget, &utc(), session.conn())
+        .unwrap()
+        .amount;
 
     assert_eq!(BigDecimal::from(4), amount);
 }
@@ -153,7 +163,197 @@ fn amount_aggregation_with_excluding_tags() {
         session.create_record(rec);
     }
 
-    let amount = budget_spent(&budget, session.conn()).unwrap();
+    let amount = budget_spent(&budget, &utc(), session.conn())
+        .unwrap()
+        .amount;
+
+    assert_eq!(BigDecimal::from(6), amount);
+}
+
+#[test]
+fn including_tags_match_their_descendants() {
+    let mut session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().tags(vec![
+        "food/groceries",
+        "food/cafe/lunch",
+        "foodstuff",
+    ]));
+    let budget = BudgetBuilder::default()
+        .user_id(user.id)
+        .tags_type("INCL")
+        .tags(vec!["food"])
+        .finish();
+
+    let record = RecordBuilder::default()
+        .user_id(user.id)
+        .transaction_type("EXP");
+
+    let test_data = [
+        (1.0, "food"),
+        (2.0, "food/groceries"),
+        (4.0, "food/cafe/lunch"),
+        (8.0, "foodstuff"),
+    ];
+    for (amount, tag) in test_data.iter() {
+        session.create_record(record.clone().amount(*amount).tags(vec![tag]).finish());
+    }
+
+    let amount = budget_spent(&budget, &utc(), session.conn())
+        .unwrap()
+        .amount;
+
+    assert_eq!(BigDecimal::from(7), amount);
+}
+
+#[test]
+fn descendants_match_by_name_even_if_not_saved_as_user_tags() {
+    let mut session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+    let budget = BudgetBuilder::default()
+        .user_id(user.id)
+        .tags_type("INCL")
+        .tags(vec!["food"])
+        .finish();
+
+    let record = RecordBuilder::default()
+        .user_id(user.id)
+        .transaction_type("EXP");
+
+    for (amount, tag) in [(1.0, "food/snacks"), (2.0, "foods")].iter() {
+        session.create_record(record.clone().amount(*amount).tags(vec![tag]).finish());
+    }
+
+    let amount = budget_spent(&budget, &utc(), session.conn())
+        .unwrap()
+        .amount;
+
+    assert_eq!(BigDecimal::from(1), amount);
+}
+
+#[test]
+fn excluding_tags_exclude_their_descendants() {
+    let mut session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().tags(vec!["food/groceries", "car"]));
+    let budget = BudgetBuilder::default()
+        .user_id(user.id)
+        .tags_type("EXCL")
+        .tags(vec!["food"])
+        .finish();
+
+    let record = RecordBuilder::default()
+        .user_id(user.id)
+        .transaction_type("EXP");
+
+    for (amount, tag) in [(1.0, "food"), (2.0, "food/groceries"), (4.0, "car")].iter() {
+        session.create_record(record.clone().amount(*amount).tags(vec![tag]).finish());
+    }
+
+    let amount = budget_spent(&budget, &utc(), session.conn())
+        .unwrap()
+        .amount;
+
+    assert_eq!(BigDecimal::from(4), amount);
+}
+
+#[test]
+fn amount_aggregation_converts_currencies() {
+    let mut session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().password("dummy password"));
+    let budget = BudgetBuilder::default()
+        .user_id(user.id)
+        .currency("CAD")
+        .finish();
+
+    let today = utc().today();
+    session.create_exchange_rate(today, "EUR", "CAD", 1.5);
+    session.create_exchange_rate(today, "EUR", "USD", 1.25);
+
+    let record = RecordBuilder::default()
+        .user_id(user.id)
+        .transaction_type("EXP");
+
+    for (amount, currency) in [(10.0, "CAD"), (10.0, "EUR"), (5.0, "USD"), (7.0, "BYN")].iter() {
+        session.create_record(record.clone().amount(*amount).currency(currency).finish());
+    }
+
+    let spent = budget_spent(&budget, &utc(), session.conn()).unwrap();
+
+    // 10 CAD + 15 CAD + 6 CAD, BYN is skipped
+    assert_eq!(BigDecimal::from(31), spent.amount);
+    assert_eq!(
+        vec![MissingRate {
+            currency: "BYN".to_string(),
+            date: today,
+        }],
+        spent.missing_rates
+    );
+}
+
+#[test]
+fn spent_is_limited_by_the_budget_period() {
+    let mut session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+    let budget = BudgetBuilder::default().user_id(user.id).finish();
+    let calendar = Calendar::new(&UserSettings {
+        month_start_day: 15,
+        ..UserSettings::defaults(user.id)
+    });
+    let (first_day, next_period) = calendar.budget_period(calendar.today());
+
+    let record = RecordBuilder::default()
+        .user_id(user.id)
+        .transaction_type("EXP");
+    let timestamps = [
+        calendar.start_of_day(first_day) - Duration::seconds(1),
+        calendar.start_of_day(first_day),
+        calendar.start_of_day(next_period) - Duration::seconds(1),
+        calendar.start_of_day(next_period),
+    ];
+    for (amount, created_at) in [1.0, 2.0, 4.0, 8.0].iter().zip(timestamps.iter()) {
+        session.create_record(
+            record
+                .clone()
+                .amount(*amount)
+                .created_at(*created_at)
+                .finish(),
+        );
+    }
+
+    let amount = budget_spent(&budget, &calendar, session.conn())
+        .unwrap()
+        .amount;
 
     assert_eq!(BigDecimal::from(6), amount);
 }
+
+#[test]
+fn spent_days_are_in_user_timezone() {
+    let mut session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+    let budget = BudgetBuilder::default().user_id(user.id).finish();
+    let calendar = Calendar::new(&UserSettings {
+        timezone: "America/Vancouver".to_string(),
+        ..UserSettings::defaults(user.id)
+    });
+    let (first_day, _) = calendar.budget_period(calendar.today());
+    let midnight = calendar.start_of_day(first_day);
+
+    let record = RecordBuilder::default()
+        .user_id(user.id)
+        .transaction_type("EXP");
+    // late evening of the last day of the previous month in Vancouver
+    session.create_record(
+        record
+            .clone()
+            .amount(1.0)
+            .created_at(midnight - Duration::hours(1))
+            .finish(),
+    );
+    session.create_record(record.amount(2.0).created_at(midnight).finish());
+
+    let amount = budget_spent(&budget, &calendar, session.conn())
+        .unwrap()
+        .amount;
+
+    assert_eq!(BigDecimal::from(2), amount);
+}
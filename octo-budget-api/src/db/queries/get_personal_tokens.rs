@@ -0,0 +1,33 @@
+use diesel::prelude::*;
+
+use crate::db::{
+    models::PersonalToken, schema::auth_personal_tokens, DatabaseQuery, PooledConnection,
+};
+use crate::errors::DbResult;
+
+/// Tokens of the user, oldest first.
+pub struct GetPersonalTokens {
+    user_id: i32,
+}
+
+impl GetPersonalTokens {
+    pub fn new(user_id: i32) -> Self {
+        Self { user_id }
+    }
+}
+
+impl DatabaseQuery for GetPersonalTokens {
+    type Data = Vec<PersonalToken>;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<Vec<PersonalToken>> {
+        let tokens = auth_personal_tokens::table
+            .filter(auth_personal_tokens::user_id.eq(self.user_id))
+            .order(auth_personal_tokens::id.asc())
+            .load(&connection)?;
+
+        Ok(tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,29 @@
+use super::*;
+use crate::{
+    apps::forms::personal_token::Data,
+    db::{builders::UserBuilder, queries::CreatePersonalToken, ConnectionPool},
+    tests::DbSession,
+};
+
+#[actix_rt::test]
+async fn tokens_of_the_user() {
+    let pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().username("john"));
+    let other = session.create_user(UserBuilder::default().username("jane"));
+
+    for (user_id, name) in &[(user.id, "first"), (other.id, "other"), (user.id, "second")] {
+        let data = Data {
+            name: name.to_string(),
+            scope: "read".to_string(),
+        };
+        pool.execute(CreatePersonalToken::new(*user_id, data))
+            .await
+            .unwrap();
+    }
+
+    let tokens = pool.execute(GetPersonalTokens::new(user.id)).await.unwrap();
+    let names: Vec<_> = tokens.iter().map(|token| token.name.as_str()).collect();
+
+    assert_eq!(vec!["first", "second"], names);
+}
@@ -1,9 +1,13 @@
 use crate::db::{
-    models::Record as RecordModel, pagination::*, schema::records_record, DatabaseQuery,
-    PooledConnection,
+    calendar::{user_calendar, Calendar},
+    models::Record as RecordModel,
+    pagination::*,
+    schema::records_record,
+    DatabaseQuery, PooledConnection,
 };
 use crate::errors::DbResult;
 
+use crate::apps::forms::record_filters::{Filters, TagsMatch};
 use crate::apps::index_response::Data;
 
 pub type ResponseData = Data<RecordModel>;
@@ -13,6 +17,7 @@ pub struct GetRecords {
     pub user_id: i32,
     pub page: i64,
     pub per_page: i64,
+    pub filters: Filters,
 }
 
 impl DatabaseQuery for GetRecords {
@@ -21,9 +26,8 @@ impl DatabaseQuery for GetRecords {
     fn execute(&self, connection: PooledConnection) -> DbResult<Self::Data> {
         use diesel::prelude::*;
 
-        let query = records_record::table
-            .select(records_record::all_columns)
-            .filter(records_record::user_id.eq(self.user_id))
+        let calendar = user_calendar(self.user_id, &connection)?;
+        let query = filtered_query(self.user_id, &self.filters, &calendar)
             .order(records_record::created_at.desc())
             .paginate(self.page)
             .per_page(self.per_page);
@@ -47,5 +51,76 @@ impl DatabaseQuery for GetRecords {
     }
 }
 
+/// Records of the user narrowed down by the index filters, dates are days in
+/// the user's timezone.
+pub(super) fn filtered_query<'a>(
+    user_id: i32,
+    filters: &'a Filters,
+    calendar: &Calendar,
+) -> records_record::BoxedQuery<'a, diesel::pg::Pg> {
+    use diesel::prelude::*;
+
+    let Filters {
+        date_from,
+        date_to,
+        transaction_type,
+        tags,
+        tags_match,
+        amount_min,
+        amount_max,
+        search,
+    } = filters;
+
+    let mut query = records_record::table
+        .select(records_record::all_columns)
+        .filter(records_record::user_id.eq(user_id))
+        .into_boxed();
+
+    if let Some(date) = date_from {
+        query = query.filter(records_record::created_at.ge(calendar.start_of_day(*date)));
+    }
+
+    if let Some(date) = date_to {
+        // the whole `date_to` day is included
+        query = query.filter(records_record::created_at.lt(calendar.start_of_day(date.succ())));
+    }
+
+    if let Some(transaction_type) = transaction_type {
+        query = query.filter(records_record::transaction_type.eq(transaction_type));
+    }
+
+    if !tags.is_empty() {
+        query = match tags_match {
+            TagsMatch::Any => query.filter(records_record::tags.overlaps_with(tags)),
+            TagsMatch::All => query.filter(records_record::tags.contains(tags)),
+        };
+    }
+
+    if let Some(amount) = amount_min {
+        query = query.filter(records_record::amount.ge(amount));
+    }
+
+    if let Some(amount) = amount_max {
+        query = query.filter(records_record::amount.le(amount));
+    }
+
+    if let Some(search) = search {
+        query = query.filter(records_record::comment.ilike(like_pattern(search)));
+    }
+
+    query
+}
+
+/// Wraps `value` with `%` so it matches as a substring, escaping the
+/// characters `LIKE` treats specially.
+fn like_pattern(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+
+    format!("%{}%", escaped)
+}
+
 #[cfg(test)]
 mod tests;
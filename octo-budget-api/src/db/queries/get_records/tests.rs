@@ -11,6 +11,7 @@ async fn empty_result() {
         page: 1,
         per_page: 10,
         user_id: 123,
+        filters: Filters::default(),
     };
 
     let data = conn_pool
@@ -33,6 +34,7 @@ async fn first_page_result() {
         page: 1,
         per_page: 10,
         user_id: user.id,
+        filters: Filters::default(),
     };
     let conn_pool = ConnectionPool::new();
 
@@ -57,6 +59,7 @@ async fn second_page_result() {
         page: 2,
         per_page: 10,
         user_id: user.id,
+        filters: Filters::default(),
     };
     let conn_pool = ConnectionPool::new();
 
@@ -93,6 +96,7 @@ async fn records_for_correct_user() {
         page: 1,
         per_page: 10,
         user_id: user1.id,
+        filters: Filters::default(),
     };
 
     let data = conn_pool
@@ -105,3 +109,188 @@ async fn records_for_correct_user() {
     assert_eq!(false, data.next);
     assert_eq!(2, data.results.len());
 }
+
+mod filters {
+    use super::*;
+    use crate::db::{builders::RecordBuilder, models::UserSettings};
+    use bigdecimal::BigDecimal;
+    use chrono::NaiveDate;
+
+    async fn filtered_amounts(user_id: i32, filters: Filters) -> Vec<BigDecimal> {
+        let query = GetRecords {
+            page: 1,
+            per_page: 10,
+            user_id,
+            filters,
+        };
+
+        let data = ConnectionPool::new()
+            .execute(query)
+            .await
+            .expect("failed to get records");
+
+        let mut amounts: Vec<BigDecimal> = data.results.into_iter().map(|r| r.amount).collect();
+        amounts.sort();
+        amounts
+    }
+
+    fn create_records(session: &mut DbSession, user_id: i32) {
+        let record = RecordBuilder::default()
+            .user_id(user_id)
+            .transaction_type("EXP");
+
+        let at = |day| NaiveDate::from_ymd(2020, 3, day).and_hms(12, 0, 0);
+
+        session.create_record(
+            record
+                .clone()
+                .amount(1.0)
+                .tags(vec!["food", "groceries"])
+                .comment("Milk and bread")
+                .created_at(at(1))
+                .finish(),
+        );
+        session.create_record(
+            record
+                .clone()
+                .amount(2.0)
+                .tags(vec!["food"])
+                .comment("Pizza 100%")
+                .created_at(at(15))
+                .finish(),
+        );
+        session.create_record(
+            record
+                .amount(3.0)
+                .transaction_type("INC")
+                .tags(vec!["salary"])
+                .created_at(at(31))
+                .finish(),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn by_date_range() {
+        let mut session = DbSession::new();
+        let user = session.create_user(UserBuilder::default());
+        create_records(&mut session, user.id);
+
+        let filters = Filters {
+            date_from: Some(NaiveDate::from_ymd(2020, 3, 2)),
+            date_to: Some(NaiveDate::from_ymd(2020, 3, 31)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![BigDecimal::from(2), BigDecimal::from(3)],
+            filtered_amounts(user.id, filters).await
+        );
+    }
+
+    #[actix_rt::test]
+    async fn by_date_range_in_user_timezone() {
+        let mut session = DbSession::new();
+        let user = session.create_user(UserBuilder::default());
+        session.set_user_settings(UserSettings {
+            timezone: "Pacific/Kiritimati".to_string(),
+            ..UserSettings::defaults(user.id)
+        });
+        create_records(&mut session, user.id);
+
+        // it is already the next day in UTC+14 for all the records
+        let filters = Filters {
+            date_from: Some(NaiveDate::from_ymd(2020, 3, 2)),
+            date_to: Some(NaiveDate::from_ymd(2020, 3, 31)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![BigDecimal::from(1), BigDecimal::from(2)],
+            filtered_amounts(user.id, filters).await
+        );
+    }
+
+    #[actix_rt::test]
+    async fn by_transaction_type() {
+        let mut session = DbSession::new();
+        let user = session.create_user(UserBuilder::default());
+        create_records(&mut session, user.id);
+
+        let filters = Filters {
+            transaction_type: Some("INC".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![BigDecimal::from(3)],
+            filtered_amounts(user.id, filters).await
+        );
+    }
+
+    #[actix_rt::test]
+    async fn by_any_or_all_tags() {
+        let mut session = DbSession::new();
+        let user = session.create_user(UserBuilder::default());
+        create_records(&mut session, user.id);
+
+        let any = Filters {
+            tags: vec!["groceries".to_string(), "salary".to_string()],
+            tags_match: TagsMatch::Any,
+            ..Default::default()
+        };
+        let all = Filters {
+            tags: vec!["food".to_string(), "groceries".to_string()],
+            tags_match: TagsMatch::All,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![BigDecimal::from(1), BigDecimal::from(3)],
+            filtered_amounts(user.id, any).await
+        );
+        assert_eq!(
+            vec![BigDecimal::from(1)],
+            filtered_amounts(user.id, all).await
+        );
+    }
+
+    #[actix_rt::test]
+    async fn by_amount_range() {
+        let mut session = DbSession::new();
+        let user = session.create_user(UserBuilder::default());
+        create_records(&mut session, user.id);
+
+        let filters = Filters {
+            amount_min: Some(BigDecimal::from(2)),
+            amount_max: Some(BigDecimal::from(2)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![BigDecimal::from(2)],
+            filtered_amounts(user.id, filters).await
+        );
+    }
+
+    #[actix_rt::test]
+    async fn by_comment_substring() {
+        let mut session = DbSession::new();
+        let user = session.create_user(UserBuilder::default());
+        create_records(&mut session, user.id);
+
+        let search = |text: &str| Filters {
+            search: Some(text.to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![BigDecimal::from(1)],
+            filtered_amounts(user.id, search("MILK")).await
+        );
+        assert_eq!(
+            vec![BigDecimal::from(2)],
+            filtered_amounts(user.id, search("0%")).await
+        );
+        assert!(filtered_amounts(user.id, search("%%")).await.is_empty());
+    }
+}
@@ -0,0 +1,40 @@
+use super::get_records::filtered_query;
+use crate::apps::forms::record_filters::Filters;
+use crate::db::{
+    calendar::user_calendar, models::Record, schema::records_record, DatabaseQuery,
+    PooledConnection,
+};
+use crate::errors::DbResult;
+
+/// Loads records in `id` order, one batch after another, so callers can walk
+/// through the whole history without keeping it in memory.
+#[derive(Clone)]
+pub struct GetRecordsBatch {
+    pub user_id: i32,
+    pub filters: Filters,
+    /// id of the last record of the previous batch
+    pub after_id: Option<i32>,
+    pub limit: i64,
+}
+
+impl DatabaseQuery for GetRecordsBatch {
+    type Data = Vec<Record>;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<Vec<Record>> {
+        use diesel::prelude::*;
+
+        let calendar = user_calendar(self.user_id, &connection)?;
+        let mut query = filtered_query(self.user_id, &self.filters, &calendar)
+            .order(records_record::id.asc())
+            .limit(self.limit);
+
+        if let Some(after_id) = self.after_id {
+            query = query.filter(records_record::id.gt(after_id));
+        }
+
+        Ok(query.load(&connection)?)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,42 @@
+use super::*;
+use crate::{
+    db::{builders::UserBuilder, ConnectionPool},
+    tests::DbSession,
+};
+
+fn batch(user_id: i32, after_id: Option<i32>) -> GetRecordsBatch {
+    GetRecordsBatch {
+        user_id,
+        filters: Filters::default(),
+        after_id,
+        limit: 2,
+    }
+}
+
+#[actix_rt::test]
+async fn walks_through_all_records() {
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default());
+    let other_user = session.create_user(UserBuilder::default().username("other"));
+    let records = session.create_records2(user.id, 3);
+    session.create_records2(other_user.id, 2);
+
+    let first = conn_pool
+        .execute(batch(user.id, None))
+        .await
+        .expect("failed to get records");
+    let second = conn_pool
+        .execute(batch(user.id, Some(first[1].id)))
+        .await
+        .expect("failed to get records");
+    let third = conn_pool
+        .execute(batch(user.id, Some(second[0].id)))
+        .await
+        .expect("failed to get records");
+
+    assert_eq!(records[..2], first[..]);
+    assert_eq!(records[2..], second[..]);
+    assert!(third.is_empty());
+}
@@ -0,0 +1,201 @@
+use bigdecimal::{BigDecimal, ToPrimitive, Zero};
+use chrono::{Datelike, NaiveDate};
+use models::{Legacy, LegacySerialize, Money};
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+use crate::db::{
+    calendar::user_calendar, exchange_rates::Converter, models::MissingRate, DatabaseQuery,
+    PooledConnection,
+};
+use crate::errors::DbResult;
+
+/// Sums of records per month and tag, shaped for a chart: `months` is the x
+/// axis and every tag is a series with a value for each month.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct TagReport {
+    pub currency: String,
+    pub months: Vec<String>,
+    pub series: Vec<TagSeries>,
+    /// records that are not included because they cannot be converted
+    pub missing_rates: Vec<MissingRate>,
+}
+
+/// Sums are serialized as decimal strings, or as floats with `LegacySerialize`.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct TagSeries {
+    pub tag: String,
+    /// rounded to the minor units of the report currency
+    pub data: Vec<BigDecimal>,
+}
+
+impl LegacySerialize for TagReport {
+    fn serialize_legacy<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("TagReport", 4)?;
+
+        state.serialize_field("currency", &self.currency)?;
+        state.serialize_field("months", &self.months)?;
+        state.serialize_field("series", &Legacy(&self.series))?;
+        state.serialize_field("missing_rates", &self.missing_rates)?;
+        state.end()
+    }
+}
+
+impl LegacySerialize for TagSeries {
+    fn serialize_legacy<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let data: Vec<f64> = self
+            .data
+            .iter()
+            .map(|total| total.to_f64().unwrap_or(0.0))
+            .collect();
+        let mut state = serializer.serialize_struct("TagSeries", 2)?;
+
+        state.serialize_field("tag", &self.tag)?;
+        state.serialize_field("data", &data)?;
+        state.end()
+    }
+}
+
+pub struct GetTagReport {
+    pub user_id: i32,
+    pub transaction_type: String,
+    /// all sums are converted into this currency
+    pub currency: String,
+    /// first day of the first month, in the user's timezone as all other days
+    pub from: NaiveDate,
+    /// last day of the report, inclusive
+    pub to: NaiveDate,
+    /// Nested tags deeper than this are rolled up into their ancestor at this
+    /// level, e.g. "food/cafe/lunch" into "food" at level 1. All tags are kept
+    /// as they are if not set.
+    pub level: Option<i32>,
+}
+
+#[derive(QueryableByName, Debug)]
+struct Row {
+    /// first day of the month for amounts in the report currency
+    #[sql_type = "diesel::sql_types::Date"]
+    day: NaiveDate,
+    #[sql_type = "diesel::sql_types::Text"]
+    tag: String,
+    #[sql_type = "diesel::sql_types::Text"]
+    currency: String,
+    #[sql_type = "diesel::sql_types::Numeric"]
+    total: BigDecimal,
+}
+
+// A record with several tags rolled up into the same one counts once for it.
+// Amounts in other currencies are converted with the rate of their day, so
+// they are summed per day, amounts already in the report currency per month.
+const QUERY: &str = "
+    SELECT
+        CASE WHEN amount_currency = $7
+            THEN date_trunc('month', created_at AT TIME ZONE $5)::date
+            ELSE (created_at AT TIME ZONE $5)::date
+        END AS day,
+        tag,
+        amount_currency AS currency,
+        SUM(amount) AS total
+    FROM records_record
+    CROSS JOIN LATERAL (
+        SELECT DISTINCT CASE
+            WHEN $6::int4 IS NULL THEN tag
+            ELSE array_to_string((string_to_array(tag, '/'))[1:$6], '/')
+        END AS tag
+        FROM unnest(tags) AS tag
+    ) AS rolled_up
+    WHERE user_id = $1
+      AND transaction_type = $2
+      AND created_at >= $3
+      AND created_at < $4
+    GROUP BY day, tag, currency
+    ORDER BY tag, day
+";
+
+impl DatabaseQuery for GetTagReport {
+    type Data = TagReport;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<TagReport> {
+        use diesel::prelude::*;
+        use diesel::sql_types::{Int4, Nullable, Text, Timestamptz};
+
+        let calendar = user_calendar(self.user_id, &connection)?;
+        let rows = diesel::sql_query(QUERY)
+            .bind::<Int4, _>(self.user_id)
+            .bind::<Text, _>(&self.transaction_type)
+            .bind::<Timestamptz, _>(calendar.start_of_day(self.from))
+            .bind::<Timestamptz, _>(calendar.start_of_day(self.to.succ()))
+            .bind::<Text, _>(calendar.timezone())
+            .bind::<Nullable<Int4>, _>(self.level)
+            .bind::<Text, _>(&self.currency)
+            .load::<Row>(&connection)?;
+
+        self.build_report(rows, &connection)
+    }
+}
+
+impl GetTagReport {
+    fn months(&self) -> Vec<NaiveDate> {
+        let mut months = vec![];
+        let mut month = self.from.with_day(1).unwrap();
+
+        while month <= self.to {
+            months.push(month);
+            month = if month.month() == 12 {
+                NaiveDate::from_ymd(month.year() + 1, 1, 1)
+            } else {
+                NaiveDate::from_ymd(month.year(), month.month() + 1, 1)
+            };
+        }
+
+        months
+    }
+
+    fn build_report(&self, rows: Vec<Row>, connection: &PooledConnection) -> DbResult<TagReport> {
+        let months = self.months();
+        let mut converter = Converter::new(&self.currency, self.from, self.to, connection);
+        let mut series: Vec<(String, Vec<BigDecimal>)> = vec![];
+
+        // rows are ordered by tag, so all days of a tag come together
+        for row in rows {
+            if series.last().map(|s| s.0 != row.tag).unwrap_or(true) {
+                series.push((row.tag.clone(), vec![BigDecimal::zero(); months.len()]));
+            }
+
+            let month = row.day.with_day(1).unwrap();
+            let total = converter.convert(row.total, &row.currency, row.day)?;
+
+            if let (Some(idx), Some(total)) = (months.iter().position(|m| *m == month), total) {
+                let current = series.last_mut().unwrap();
+                current.1[idx] += total;
+            }
+        }
+
+        Ok(TagReport {
+            currency: self.currency.clone(),
+            months: months
+                .iter()
+                .map(|m| m.format("%Y-%m").to_string())
+                .collect(),
+            series: series
+                .into_iter()
+                .map(|(tag, data)| TagSeries {
+                    tag,
+                    data: data
+                        .into_iter()
+                        .map(|total| Money::new(total, &self.currency).amount)
+                        .collect(),
+                })
+                .collect(),
+            missing_rates: converter.missing_rates(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,237 @@
+use super::*;
+use crate::{
+    db::{
+        builders::{RecordBuilder, UserBuilder},
+        ConnectionPool,
+    },
+    tests::DbSession,
+};
+use chrono::NaiveDateTime;
+
+fn at(month: u32, day: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd(2020, month, day).and_hms(12, 0, 0)
+}
+
+fn amounts(values: &[i32]) -> Vec<BigDecimal> {
+    values
+        .iter()
+        .map(|value| BigDecimal::from(*value))
+        .collect()
+}
+
+fn report_query(user_id: i32) -> GetTagReport {
+    GetTagReport {
+        user_id,
+        transaction_type: "EXP".to_string(),
+        currency: "CAD".to_string(),
+        from: NaiveDate::from_ymd(2020, 1, 1),
+        to: NaiveDate::from_ymd(2020, 3, 31),
+        level: None,
+    }
+}
+
+#[actix_rt::test]
+async fn empty_report_has_all_months() {
+    let conn_pool = ConnectionPool::new();
+
+    let report = conn_pool
+        .execute(report_query(123))
+        .await
+        .expect("Failed to build report");
+
+    assert_eq!(
+        TagReport {
+            currency: "CAD".into(),
+            months: vec!["2020-01".into(), "2020-02".into(), "2020-03".into()],
+            series: vec![],
+            missing_rates: vec![],
+        },
+        report
+    );
+}
+
+#[actix_rt::test]
+async fn sums_per_month_and_tag() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default());
+    let other_user = session.create_user(UserBuilder::default().username("other"));
+    let record = RecordBuilder::default()
+        .user_id(user.id)
+        .transaction_type("EXP")
+        .currency("CAD");
+
+    for (amount, tags, created_at) in [
+        (10.0, vec!["food", "lunch"], at(1, 5)),
+        (5.0, vec!["food"], at(1, 20)),
+        (7.0, vec!["food"], at(3, 31)),
+        (100.0, vec!["food"], at(4, 1)),
+        (3.0, vec!["taxi"], at(2, 1)),
+    ] {
+        session.create_record(
+            record
+                .clone()
+                .amount(amount)
+                .tags(tags)
+                .created_at(created_at)
+                .finish(),
+        );
+    }
+
+    // income and records of other users are not included
+    session.create_record(
+        record
+            .clone()
+            .transaction_type("INC")
+            .amount(50.0)
+            .tags(vec!["food"])
+            .created_at(at(1, 5))
+            .finish(),
+    );
+    session.create_record(
+        record
+            .user_id(other_user.id)
+            .amount(50.0)
+            .tags(vec!["food"])
+            .created_at(at(1, 5))
+            .finish(),
+    );
+
+    let report = conn_pool
+        .execute(report_query(user.id))
+        .await
+        .expect("Failed to build report");
+
+    assert_eq!(
+        vec![
+            TagSeries {
+                tag: "food".into(),
+                data: amounts(&[15, 0, 7])
+            },
+            TagSeries {
+                tag: "lunch".into(),
+                data: amounts(&[10, 0, 0])
+            },
+            TagSeries {
+                tag: "taxi".into(),
+                data: amounts(&[0, 3, 0])
+            },
+        ],
+        report.series
+    );
+}
+
+#[actix_rt::test]
+async fn converts_sums_into_report_currency() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default());
+    let record = RecordBuilder::default()
+        .user_id(user.id)
+        .transaction_type("EXP")
+        .tags(vec!["travel"]);
+
+    session.create_exchange_rate(NaiveDate::from_ymd(2020, 1, 3), "EUR", "CAD", 1.5);
+    session.create_exchange_rate(NaiveDate::from_ymd(2020, 2, 3), "EUR", "CAD", 1.4);
+
+    for (amount, currency, created_at) in [
+        (10.0, "CAD", at(1, 3)),
+        (10.0, "EUR", at(1, 3)),
+        (10.0, "EUR", at(2, 4)),
+        (10.0, "USD", at(2, 4)),
+    ] {
+        session.create_record(
+            record
+                .clone()
+                .amount(amount)
+                .currency(currency)
+                .created_at(created_at)
+                .finish(),
+        );
+    }
+
+    let report = conn_pool
+        .execute(report_query(user.id))
+        .await
+        .expect("Failed to build report");
+
+    assert_eq!(
+        TagReport {
+            currency: "CAD".into(),
+            months: vec!["2020-01".into(), "2020-02".into(), "2020-03".into()],
+            series: vec![TagSeries {
+                tag: "travel".into(),
+                data: amounts(&[25, 14, 0])
+            }],
+            missing_rates: vec![MissingRate {
+                currency: "USD".into(),
+                date: NaiveDate::from_ymd(2020, 2, 4),
+            }],
+        },
+        report
+    );
+}
+
+#[actix_rt::test]
+async fn nested_tags_are_rolled_up_to_level() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default());
+    let record = RecordBuilder::default()
+        .user_id(user.id)
+        .transaction_type("EXP")
+        .currency("CAD")
+        .created_at(at(1, 5));
+
+    for (amount, tags) in [
+        (1.0, vec!["food/cafe/lunch"]),
+        (2.0, vec!["food/cafe"]),
+        (4.0, vec!["food/groceries", "food/cafe/dinner"]),
+        (8.0, vec!["car"]),
+    ] {
+        session.create_record(record.clone().amount(amount).tags(tags).finish());
+    }
+
+    // a record with two tags of the same parent counts once for it
+    assert_eq!(
+        vec![("car".to_string(), 8.0), ("food".to_string(), 7.0)],
+        january(&conn_pool, user.id, Some(1)).await
+    );
+    assert_eq!(
+        vec![
+            ("car".to_string(), 8.0),
+            ("food/cafe".to_string(), 7.0),
+            ("food/groceries".to_string(), 4.0),
+        ],
+        january(&conn_pool, user.id, Some(2)).await
+    );
+    assert_eq!(
+        january(&conn_pool, user.id, None).await,
+        january(&conn_pool, user.id, Some(3)).await,
+        "is not rolled up below the deepest level"
+    );
+}
+
+/// sums of January per tag
+async fn january(
+    conn_pool: &ConnectionPool,
+    user_id: i32,
+    level: Option<i32>,
+) -> Vec<(String, f64)> {
+    let query = GetTagReport {
+        level,
+        ..report_query(user_id)
+    };
+
+    conn_pool
+        .execute(query)
+        .await
+        .expect("Failed to build report")
+        .series
+        .into_iter()
+        .map(|s| (s.tag, s.data[0].to_f64().unwrap()))
+        .collect()
+}
@@ -0,0 +1,85 @@
+use crate::db::{DatabaseQuery, PooledConnection};
+use crate::errors::DbResult;
+
+/// How often every tag was used in records, per user. With a half-life a use
+/// counts less the older the record is: a record created `half_life_days`
+/// ago counts half.
+pub struct GetTagScores {
+    /// all users if not set
+    pub user_id: Option<i32>,
+    pub half_life_days: Option<f64>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct UserTagScores {
+    pub user_id: i32,
+    /// the most used tags first
+    pub scores: Vec<(String, f64)>,
+}
+
+#[derive(QueryableByName, Debug)]
+struct Row {
+    #[sql_type = "diesel::sql_types::Int4"]
+    user_id: i32,
+    #[sql_type = "diesel::sql_types::Nullable<diesel::sql_types::Text>"]
+    tag: Option<String>,
+    #[sql_type = "diesel::sql_types::Nullable<diesel::sql_types::Float8>"]
+    score: Option<f64>,
+}
+
+// every user is listed, even without records, to clear their stale scores;
+// the exponent is capped, 0.5 to a power over ~1074 underflows float8
+const QUERY: &str = "
+    SELECT auth_user.id AS user_id, scores.tag, scores.score
+    FROM auth_user
+    LEFT JOIN LATERAL (
+        SELECT tag, SUM(CASE
+            WHEN $2::float8 IS NULL THEN 1
+            ELSE power(0.5, LEAST(GREATEST(EXTRACT(EPOCH FROM now() - created_at), 0) / 86400 / $2, 1000))
+        END)::float8 AS score
+        FROM records_record, unnest(tags) AS tag
+        WHERE records_record.user_id = auth_user.id
+        GROUP BY tag
+    ) AS scores ON true
+    WHERE $1::int4 IS NULL OR auth_user.id = $1
+    ORDER BY auth_user.id, scores.score DESC, scores.tag
+";
+
+impl DatabaseQuery for GetTagScores {
+    type Data = Vec<UserTagScores>;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<Vec<UserTagScores>> {
+        use diesel::prelude::*;
+        use diesel::sql_types::{Float8, Int4, Nullable};
+
+        let rows = diesel::sql_query(QUERY)
+            .bind::<Nullable<Int4>, _>(self.user_id)
+            .bind::<Nullable<Float8>, _>(self.half_life_days)
+            .load::<Row>(&connection)?;
+
+        let mut result: Vec<UserTagScores> = vec![];
+
+        // rows are ordered by user, so all tags of a user come together
+        for row in rows {
+            if result
+                .last()
+                .map(|s| s.user_id != row.user_id)
+                .unwrap_or(true)
+            {
+                result.push(UserTagScores {
+                    user_id: row.user_id,
+                    scores: vec![],
+                });
+            }
+
+            if let (Some(tag), Some(score)) = (row.tag, row.score) {
+                result.last_mut().unwrap().scores.push((tag, score));
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,157 @@
+use super::*;
+use crate::{
+    db::{
+        builders::{RecordBuilder, UserBuilder},
+        ConnectionPool,
+    },
+    tests::DbSession,
+};
+use chrono::{Duration, Utc};
+
+#[actix_rt::test]
+async fn counts_uses_of_tags() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default().username("foo"));
+    let other_user = session.create_user(UserBuilder::default().username("bar"));
+
+    for tags in [vec!["car"], vec!["food", "car"], vec!["food"], vec!["car"]] {
+        session.create_record(
+            RecordBuilder::default()
+                .user_id(user.id)
+                .tags(tags)
+                .finish(),
+        );
+    }
+    session.create_record(
+        RecordBuilder::default()
+            .user_id(other_user.id)
+            .tags(vec!["food"])
+            .finish(),
+    );
+
+    let scores = conn_pool
+        .execute(GetTagScores {
+            user_id: Some(user.id),
+            half_life_days: None,
+        })
+        .await
+        .expect("Failed to get tag scores");
+
+    assert_eq!(
+        vec![UserTagScores {
+            user_id: user.id,
+            scores: vec![("car".to_string(), 3.0), ("food".to_string(), 2.0)],
+        }],
+        scores
+    );
+}
+
+#[actix_rt::test]
+async fn lists_all_users_even_without_records() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default().username("foo"));
+    let other_user = session.create_user(UserBuilder::default().username("bar"));
+
+    session.create_record(
+        RecordBuilder::default()
+            .user_id(other_user.id)
+            .tags(vec!["food"])
+            .finish(),
+    );
+
+    let scores = conn_pool
+        .execute(GetTagScores {
+            user_id: None,
+            half_life_days: None,
+        })
+        .await
+        .expect("Failed to get tag scores");
+
+    assert_eq!(
+        vec![
+            UserTagScores {
+                user_id: user.id,
+                scores: vec![],
+            },
+            UserTagScores {
+                user_id: other_user.id,
+                scores: vec![("food".to_string(), 1.0)],
+            },
+        ],
+        scores
+    );
+}
+
+#[actix_rt::test]
+async fn old_uses_count_less_with_half_life() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default());
+    let now = Utc::now().naive_utc();
+
+    for _ in 0..3 {
+        session.create_record(
+            RecordBuilder::default()
+                .user_id(user.id)
+                .tags(vec!["old"])
+                .created_at(now - Duration::days(60))
+                .finish(),
+        );
+    }
+    session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["new"])
+            .created_at(now)
+            .finish(),
+    );
+
+    let scores = conn_pool
+        .execute(GetTagScores {
+            user_id: Some(user.id),
+            half_life_days: Some(30.0),
+        })
+        .await
+        .expect("Failed to get tag scores")
+        .remove(0)
+        .scores;
+
+    // 3 uses two half-lives ago are worth 0.75 now
+    assert_eq!("new", scores[0].0);
+    assert!((scores[0].1 - 1.0).abs() < 0.01);
+    assert_eq!("old", scores[1].0);
+    assert!((scores[1].1 - 0.75).abs() < 0.01);
+}
+
+#[actix_rt::test]
+async fn very_old_uses_do_not_underflow() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default());
+    session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["ancient"])
+            .created_at(Utc::now().naive_utc() - Duration::days(3650))
+            .finish(),
+    );
+
+    let scores = conn_pool
+        .execute(GetTagScores {
+            user_id: Some(user.id),
+            half_life_days: Some(1.0),
+        })
+        .await
+        .expect("Failed to get tag scores")
+        .remove(0)
+        .scores;
+
+    assert_eq!("ancient", scores[0].0);
+    assert!(scores[0].1 < 0.01);
+}
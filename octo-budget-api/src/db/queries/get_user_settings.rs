@@ -0,0 +1,21 @@
+use crate::db::{calendar::user_settings, models::UserSettings, DatabaseQuery, PooledConnection};
+use crate::errors::DbResult;
+
+pub struct GetUserSettings(i32);
+
+impl GetUserSettings {
+    pub fn new(user_id: impl Into<i32>) -> Self {
+        Self(user_id.into())
+    }
+}
+
+impl DatabaseQuery for GetUserSettings {
+    type Data = UserSettings;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<UserSettings> {
+        user_settings(self.0, &connection)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,34 @@
+use super::*;
+use crate::{
+    db::{builders::UserBuilder, queries::UpdateUserSettings, ConnectionPool},
+    tests::DbSession,
+};
+
+#[actix_rt::test]
+async fn defaults_if_never_changed() {
+    let pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+
+    let settings = pool.execute(GetUserSettings::new(user.id)).await.unwrap();
+
+    assert_eq!(UserSettings::defaults(user.id), settings);
+}
+
+#[actix_rt::test]
+async fn saved_settings() {
+    let pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+    let saved = UserSettings {
+        timezone: "America/Vancouver".to_string(),
+        ..UserSettings::defaults(user.id)
+    };
+    pool.execute(UpdateUserSettings::new(saved.clone()))
+        .await
+        .unwrap();
+
+    let settings = pool.execute(GetUserSettings::new(user.id)).await.unwrap();
+
+    assert_eq!(saved, settings);
+}
@@ -1,4 +1,4 @@
-use crate::db::{schema::auth_user, DatabaseQuery, PooledConnection};
+use crate::db::{schema::auth_user, tags::load_user_tags, DatabaseQuery, PooledConnection};
 use crate::errors::{add_table_name, DbResult};
 use octo_budget_lib::auth_token::UserId;
 
@@ -22,11 +22,16 @@ impl DatabaseQuery for GetUserTags {
 
         let owner_user_id: i32 = self.user_id.into();
 
-        let tags = auth_user::table
-            .select(auth_user::tags)
-            .filter(auth_user::id.eq(owner_user_id))
-            .first(&connection)
-            .map_err(add_table_name("auth_user"))?;
+        let tags = load_user_tags(owner_user_id, &connection)?;
+
+        // a user without tags is told apart from a missing one
+        if tags.is_empty() {
+            auth_user::table
+                .select(auth_user::id)
+                .find(owner_user_id)
+                .first::<i32>(&connection)
+                .map_err(add_table_name("auth_user"))?;
+        }
 
         Ok(tags)
     }
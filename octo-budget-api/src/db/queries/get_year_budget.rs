@@ -0,0 +1,32 @@
+use octo_budget_lib::auth_token::UserId;
+
+use super::{budget_crud::year_budget::find, get_year_budgets::serialize_year_budget};
+use crate::db::{
+    calendar::user_calendar, models::SerializedYearBudget, DatabaseQuery, PooledConnection,
+};
+use crate::errors::DbResult;
+
+pub struct GetYearBudget {
+    user_id: UserId,
+    id: i32,
+}
+
+impl GetYearBudget {
+    pub fn new(id: i32, user_id: UserId) -> Self {
+        Self { id, user_id }
+    }
+}
+
+impl DatabaseQuery for GetYearBudget {
+    type Data = SerializedYearBudget;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<SerializedYearBudget> {
+        let budget = find(self.id, self.user_id, &connection)?;
+        let calendar = user_calendar(budget.user_id, &connection)?;
+
+        serialize_year_budget(budget, &calendar, &connection)
+    }
+}
+
+#[cfg(test)]
+mod tests;
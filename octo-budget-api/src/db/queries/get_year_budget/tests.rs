@@ -0,0 +1,61 @@
+use super::*;
+use crate::{
+    db::{
+        builders::{UserBuilder, YearBudgetBuilder},
+        ConnectionPool,
+    },
+    tests::DbSession,
+};
+use bigdecimal::BigDecimal;
+
+#[actix_rt::test]
+async fn get_serialized_year_budget() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default());
+    let budget = session.create_year_budget(
+        YearBudgetBuilder::default()
+            .user_id(user.id)
+            .name("Gifts")
+            .amount(1200.0)
+            .year(2019)
+            .finish(),
+    );
+
+    let result = conn_pool
+        .execute(GetYearBudget::new(budget.id, user.id.into()))
+        .await
+        .expect("Failed to get year budget");
+
+    assert_eq!(budget.id, result.id);
+    assert_eq!("Gifts", result.name);
+    assert_eq!(BigDecimal::from(1200), result.amount);
+    assert_eq!(2019, result.year);
+    assert_eq!(BigDecimal::from(0), result.spent);
+    assert_eq!(BigDecimal::from(1200), result.left);
+    assert_eq!(BigDecimal::from(100), result.average_per_month);
+    assert_eq!(BigDecimal::from(0), result.left_average_per_month);
+}
+
+#[actix_rt::test]
+async fn does_not_return_year_budget_of_other_user() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let owner = session.create_user(UserBuilder::default().username("foo"));
+    let other_user = session.create_user(UserBuilder::default().username("bar"));
+    let budget =
+        session.create_year_budget(YearBudgetBuilder::default().user_id(other_user.id).finish());
+
+    let error = conn_pool
+        .execute(GetYearBudget::new(budget.id, owner.id.into()))
+        .await
+        .err()
+        .expect("Is not expected to find anything");
+
+    assert_eq!(
+        "Failed to find record from table budgets_yearbudget",
+        error.to_string()
+    );
+}
@@ -0,0 +1,143 @@
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{Datelike, NaiveDate};
+use diesel::prelude::*;
+
+use super::get_budgets::{spent_between, BudgetFilter, Spent};
+use crate::apps::index_response::Data;
+use crate::db::{
+    calendar::{user_calendar, Calendar},
+    models::{SerializedYearBudget, YearBudget},
+    pagination::*,
+    schema::budgets_yearbudget,
+    DatabaseQuery, PooledConnection,
+};
+use crate::errors::DbResult;
+
+pub type GetYearBudgetsResult = DbResult<Data<SerializedYearBudget>>;
+
+#[derive(Clone)]
+pub struct GetYearBudgets {
+    pub user_id: i32,
+    pub page: i64,
+    pub per_page: i64,
+}
+
+impl DatabaseQuery for GetYearBudgets {
+    type Data = Data<SerializedYearBudget>;
+
+    fn execute(&self, connection: PooledConnection) -> GetYearBudgetsResult {
+        handle(self, &connection)
+    }
+}
+
+fn year_budget_spent(
+    budget: &YearBudget,
+    calendar: &Calendar,
+    connection: &PooledConnection,
+) -> DbResult<Spent> {
+    let first_year_day = NaiveDate::from_ymd(budget.year, 1, 1);
+    let first_next_year_day = NaiveDate::from_ymd(budget.year + 1, 1, 1);
+
+    spent_between(
+        BudgetFilter {
+            user_id: budget.user_id,
+            currency: &budget.amount_currency,
+            tags_type: &budget.tags_type,
+            tags: &budget.tags,
+        },
+        calendar,
+        first_year_day,
+        first_next_year_day,
+        connection,
+    )
+}
+
+/// Number of months of `year` that are still ahead (including the current one).
+fn nmonths_left_in_the_year(year: i32, today: NaiveDate) -> u32 {
+    use std::cmp::Ordering;
+
+    match year.cmp(&today.year()) {
+        Ordering::Less => 0,
+        Ordering::Equal => 12 - today.month0(),
+        Ordering::Greater => 12,
+    }
+}
+
+pub(super) fn serialize_year_budget(
+    budget: YearBudget,
+    calendar: &Calendar,
+    conn: &PooledConnection,
+) -> DbResult<SerializedYearBudget> {
+    let today = calendar.today();
+    let spent = year_budget_spent(&budget, calendar, conn)?;
+    let rest_months = nmonths_left_in_the_year(budget.year, today);
+
+    let left = budget.amount.clone() - spent.amount.clone();
+    let average_per_month = budget.amount.clone() / BigDecimal::from(12);
+
+    let left_average_per_month = if rest_months == 0 {
+        BigDecimal::zero()
+    } else {
+        left.clone() / BigDecimal::from(rest_months)
+    };
+
+    Ok(SerializedYearBudget {
+        id: budget.id,
+        name: budget.name,
+        amount: budget.amount,
+        amount_currency: budget.amount_currency,
+        year: budget.year,
+        tags: budget.tags,
+        tags_type: budget.tags_type,
+        comment: budget.comment,
+        spent: spent.amount,
+        left,
+        average_per_month,
+        left_average_per_month,
+        missing_rates: spent.missing_rates,
+    })
+}
+
+fn get_page_of_budgets(
+    msg: &GetYearBudgets,
+    conn: &PooledConnection,
+) -> DbResult<(Vec<YearBudget>, i64)> {
+    let query = budgets_yearbudget::table
+        .select(budgets_yearbudget::all_columns)
+        .filter(budgets_yearbudget::user_id.eq(msg.user_id))
+        .order(budgets_yearbudget::id.asc())
+        .paginate(msg.page)
+        .per_page(msg.per_page);
+
+    let query_results = query.load::<(YearBudget, i64)>(conn)?;
+
+    let total = query_results.first().map(|x| x.1).unwrap_or(0);
+
+    let results: Vec<YearBudget> = query_results.into_iter().map(|x| x.0).collect();
+
+    Ok((results, total))
+}
+
+fn handle(msg: &GetYearBudgets, conn: &PooledConnection) -> GetYearBudgetsResult {
+    let (results, total) = get_page_of_budgets(msg, conn)?;
+    let total_pages = (total as f64 / msg.per_page as f64).ceil() as i64;
+    let calendar = user_calendar(msg.user_id, conn)?;
+
+    let results = results
+        .into_iter()
+        .map(|budget| serialize_year_budget(budget, &calendar, conn))
+        .collect::<DbResult<Vec<SerializedYearBudget>>>()?;
+
+    let previous = msg.page > 1;
+    let next = msg.page < total_pages;
+
+    Ok(Data {
+        total,
+        results,
+        next,
+        previous,
+    })
+}
+
+#[cfg(test)]
+mod tests;
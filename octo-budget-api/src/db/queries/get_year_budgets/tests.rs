@@ -0,0 +1,111 @@
+use super::*;
+use crate::db::builders::{RecordBuilder, UserBuilder, YearBudgetBuilder};
+use crate::db::models::UserSettings;
+use crate::tests::DbSession;
+
+#[test]
+fn test_empty_result() {
+    let message = GetYearBudgets {
+        page: 1,
+        per_page: 10,
+        user_id: 123,
+    };
+    let session = DbSession::new();
+
+    let data = handle(&message, session.conn()).unwrap();
+
+    assert_eq!(0, data.total);
+    assert!(!data.next);
+    assert!(!data.previous);
+    assert!(data.results.is_empty());
+}
+
+#[test]
+fn test_second_page_result() {
+    let mut session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+    for _ in 0..12 {
+        session.create_year_budget(YearBudgetBuilder::default().user_id(user.id).finish());
+    }
+
+    let message = GetYearBudgets {
+        page: 2,
+        per_page: 10,
+        user_id: user.id,
+    };
+    let data = handle(&message, session.conn()).unwrap();
+
+    assert_eq!(12, data.total);
+    assert!(data.previous);
+    assert!(!data.next);
+    assert_eq!(2, data.results.len());
+}
+
+#[test]
+fn test_records_for_correct_user() {
+    let mut session = DbSession::new();
+    let user1 = session.create_user(UserBuilder::default().username("user1"));
+    session.create_year_budget(YearBudgetBuilder::default().user_id(user1.id).finish());
+
+    let user2 = session.create_user(UserBuilder::default().username("user2"));
+    session.create_year_budget(YearBudgetBuilder::default().user_id(user2.id).finish());
+
+    let message = GetYearBudgets {
+        page: 1,
+        per_page: 10,
+        user_id: user1.id,
+    };
+    let data = handle(&message, session.conn()).unwrap();
+
+    assert_eq!(1, data.total);
+    assert_eq!(1, data.results.len());
+}
+
+#[test]
+fn spent_is_limited_by_the_budget_year() {
+    let mut session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+    let calendar = Calendar::new(&UserSettings::defaults(user.id));
+    let this_year = calendar.today().year();
+
+    let record = RecordBuilder::default()
+        .user_id(user.id)
+        .transaction_type("EXP")
+        .tags(vec!["travel"]);
+
+    session.create_record(record.clone().amount(100.0).finish());
+    session.create_record(record.amount(50.0).finish());
+
+    let current = YearBudgetBuilder::default()
+        .user_id(user.id)
+        .tags_type("INCL")
+        .tags(vec!["travel"])
+        .year(this_year)
+        .finish();
+    let previous = YearBudgetBuilder::default()
+        .user_id(user.id)
+        .year(this_year - 1)
+        .finish();
+
+    assert_eq!(
+        BigDecimal::from(150),
+        year_budget_spent(&current, &calendar, session.conn())
+            .unwrap()
+            .amount
+    );
+    assert_eq!(
+        BigDecimal::from(0),
+        year_budget_spent(&previous, &calendar, session.conn())
+            .unwrap()
+            .amount
+    );
+}
+
+#[test]
+fn months_left_in_the_year() {
+    let today = NaiveDate::from_ymd(2020, 3, 15);
+
+    assert_eq!(0, nmonths_left_in_the_year(2019, today));
+    assert_eq!(10, nmonths_left_in_the_year(2020, today));
+    assert_eq!(12, nmonths_left_in_the_year(2021, today));
+}
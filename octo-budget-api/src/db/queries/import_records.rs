@@ -0,0 +1,85 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use octo_budget_lib::auth_token::UserId;
+
+use crate::apps::forms::record_import::Row;
+use crate::db::{DatabaseQuery, PooledConnection};
+use crate::errors::DbResult;
+
+// keeps the number of bind parameters of a single INSERT well below the limit
+const BATCH_SIZE: usize = 1000;
+
+struct NewRecord {
+    amount: BigDecimal,
+    amount_currency: String,
+    created_at: NaiveDateTime,
+    tags: Vec<String>,
+    transaction_type: String,
+    comment: String,
+}
+
+pub struct ImportRecords {
+    records: Vec<NewRecord>,
+    user_id: i32,
+}
+
+impl ImportRecords {
+    pub fn new(rows: &[Row], user_id: UserId) -> Self {
+        let records = rows
+            .iter()
+            .map(|row| NewRecord {
+                amount: row.data.amount.clone(),
+                amount_currency: row.data.amount_currency.clone(),
+                created_at: row.created_at,
+                tags: row.data.tags.clone(),
+                transaction_type: row.data.transaction_type.clone(),
+                comment: row.data.comment.clone(),
+            })
+            .collect();
+
+        Self {
+            records,
+            user_id: user_id.into(),
+        }
+    }
+}
+
+impl DatabaseQuery for ImportRecords {
+    type Data = usize;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<usize> {
+        use crate::db::schema::records_record::dsl::*;
+        use diesel::prelude::*;
+        use diesel::*;
+
+        connection.transaction(|| {
+            let mut inserted = 0;
+
+            for batch in self.records.chunks(BATCH_SIZE) {
+                let values: Vec<_> = batch
+                    .iter()
+                    .map(|record| {
+                        (
+                            amount.eq(&record.amount),
+                            amount_currency.eq(&record.amount_currency),
+                            created_at.eq(record.created_at),
+                            tags.eq(&record.tags),
+                            transaction_type.eq(&record.transaction_type),
+                            user_id.eq(self.user_id),
+                            comment.eq(&record.comment),
+                        )
+                    })
+                    .collect();
+
+                inserted += insert_into(records_record)
+                    .values(&values)
+                    .execute(&connection)?;
+            }
+
+            Ok(inserted)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
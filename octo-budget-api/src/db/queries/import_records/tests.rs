@@ -0,0 +1,39 @@
+use super::*;
+use crate::apps::forms::record::FormData;
+use crate::{
+    db::{builders::UserBuilder, ConnectionPool},
+    tags_vec,
+    tests::DbSession,
+};
+use chrono::NaiveDate;
+
+fn row(line: u64, amount: i32) -> Row {
+    Row {
+        line,
+        created_at: NaiveDate::from_ymd(2020, 3, line as u32).and_hms(0, 0, 0),
+        data: FormData {
+            transaction_type: "EXP".to_string(),
+            tags: tags_vec!["imported"],
+            amount: BigDecimal::from(amount),
+            amount_currency: "CAD".to_string(),
+            comment: format!("row {}", line),
+        },
+    }
+}
+
+#[actix_rt::test]
+async fn imports_all_rows() {
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default());
+    let rows = vec![row(1, 10), row(2, 20)];
+
+    let count = conn_pool
+        .execute(ImportRecords::new(&rows, user.id.into()))
+        .await
+        .expect("Failed to import records");
+
+    assert_eq!(2, count);
+    assert_eq!(2, session.count_records());
+}
@@ -0,0 +1,32 @@
+use diesel::prelude::*;
+
+use crate::db::{schema::auth_user, DatabaseQuery, PooledConnection};
+use crate::errors::DbResult;
+
+/// `false` for deactivated and deleted users.
+pub struct IsUserActive {
+    user_id: i32,
+}
+
+impl IsUserActive {
+    pub fn new(user_id: i32) -> Self {
+        Self { user_id }
+    }
+}
+
+impl DatabaseQuery for IsUserActive {
+    type Data = bool;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<bool> {
+        let is_active = auth_user::table
+            .find(self.user_id)
+            .select(auth_user::is_active)
+            .first(&connection)
+            .optional()?;
+
+        Ok(is_active.unwrap_or(false))
+    }
+}
+
+#[cfg(test)]
+mod tests;
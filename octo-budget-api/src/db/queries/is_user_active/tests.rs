@@ -0,0 +1,31 @@
+use super::*;
+use crate::{
+    db::{builders::UserBuilder, ConnectionPool},
+    tests::DbSession,
+};
+
+#[actix_rt::test]
+async fn active_user() {
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+
+    assert!(conn_pool.execute(IsUserActive::new(user.id)).await.unwrap());
+}
+
+#[actix_rt::test]
+async fn inactive_user() {
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().is_active(false));
+
+    assert!(!conn_pool.execute(IsUserActive::new(user.id)).await.unwrap());
+}
+
+#[actix_rt::test]
+async fn missing_user() {
+    let conn_pool = ConnectionPool::new();
+    let _session = DbSession::new();
+
+    assert!(!conn_pool.execute(IsUserActive::new(-1)).await.unwrap());
+}
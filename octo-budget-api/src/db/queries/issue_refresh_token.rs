@@ -0,0 +1,26 @@
+use crate::db::{refresh_tokens, DatabaseQuery, PooledConnection};
+use crate::errors::DbResult;
+
+/// Starts a new token family for a fresh login and returns its first token.
+pub struct IssueRefreshToken {
+    user_id: i32,
+}
+
+impl IssueRefreshToken {
+    pub fn new(user_id: i32) -> Self {
+        Self { user_id }
+    }
+}
+
+impl DatabaseQuery for IssueRefreshToken {
+    type Data = String;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<String> {
+        let family = refresh_tokens::generate();
+
+        refresh_tokens::issue(self.user_id, &family, &connection)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,54 @@
+use super::*;
+use crate::{
+    db::{builders::UserBuilder, models::RefreshToken, ConnectionPool},
+    tests::DbSession,
+};
+use diesel::prelude::*;
+
+#[actix_rt::test]
+async fn stores_only_hash_of_token() {
+    use crate::db::schema::auth_refresh_tokens::dsl::*;
+
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+
+    let token = conn_pool
+        .execute(IssueRefreshToken::new(user.id))
+        .await
+        .expect("Failed to issue refresh token");
+
+    let stored: RefreshToken = auth_refresh_tokens
+        .first(&conn_pool.conn())
+        .expect("Failed to load refresh token");
+
+    assert_eq!(user.id, stored.user_id);
+    assert_ne!(token, stored.token_hash);
+    assert_eq!(refresh_tokens::hash(&token), stored.token_hash);
+    assert_eq!(None, stored.used_at);
+    assert_eq!(None, stored.revoked_at);
+}
+
+#[actix_rt::test]
+async fn every_login_starts_new_family() {
+    use crate::db::schema::auth_refresh_tokens::dsl::*;
+
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+
+    for _ in 0..2 {
+        conn_pool
+            .execute(IssueRefreshToken::new(user.id))
+            .await
+            .expect("Failed to issue refresh token");
+    }
+
+    let families: Vec<String> = auth_refresh_tokens
+        .select(family)
+        .load(&conn_pool.conn())
+        .expect("Failed to load refresh tokens");
+
+    assert_eq!(2, families.len());
+    assert_ne!(families[0], families[1]);
+}
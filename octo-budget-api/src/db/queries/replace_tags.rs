@@ -0,0 +1,104 @@
+use octo_budget_lib::auth_token::UserId;
+
+use crate::db::{
+    tags::{load_user_tags, replaced, save_user_tags},
+    DatabaseQuery, PooledConnection,
+};
+use crate::errors::DbResult;
+
+/// Replaces tags with another one, or removes them, everywhere they are used:
+/// in records, budgets, year budgets and the user's own list of tags.
+/// Descendants of the tags are moved along, e.g. "food/cafe" becomes
+/// "meals/cafe" when "food" is renamed into "meals".
+///
+/// Tags keep their position, a tag that would appear twice after the
+/// replacement is kept only once.
+pub struct ReplaceTags {
+    user_id: i32,
+    tags: Vec<String>,
+    into: Option<String>,
+}
+
+impl ReplaceTags {
+    pub fn merge(user_id: UserId, tags: Vec<String>, into: String) -> Self {
+        Self {
+            user_id: user_id.into(),
+            tags,
+            into: Some(into),
+        }
+    }
+
+    pub fn delete(user_id: UserId, tag: String) -> Self {
+        Self {
+            user_id: user_id.into(),
+            tags: vec![tag],
+            into: None,
+        }
+    }
+}
+
+// the same as `db::tags::replaced`: a tag is replaced by the first of $2 it
+// descends from
+fn replace_query(table: &str) -> String {
+    format!(
+        "
+        UPDATE {table}
+        SET tags = ARRAY(
+            SELECT tag FROM (
+                SELECT CASE
+                    WHEN source.tag IS NULL THEN t.tag
+                    ELSE $3 || substr(t.tag, length(source.tag) + 1)
+                END AS tag, MIN(t.position) AS position
+                FROM unnest(tags) WITH ORDINALITY AS t(tag, position)
+                LEFT JOIN LATERAL (
+                    SELECT s.tag
+                    FROM unnest($2::text[]) WITH ORDINALITY AS s(tag, position)
+                    WHERE t.tag = s.tag OR left(t.tag, length(s.tag) + 1) = s.tag || '/'
+                    ORDER BY s.position
+                    LIMIT 1
+                ) AS source ON true
+                GROUP BY 1
+            ) AS replaced
+            WHERE tag IS NOT NULL
+            ORDER BY position
+        )
+        WHERE user_id = $1 AND EXISTS (
+            SELECT 1
+            FROM unnest(tags) AS t(tag), unnest($2::text[]) AS s(tag)
+            WHERE t.tag = s.tag OR left(t.tag, length(s.tag) + 1) = s.tag || '/'
+        )
+        ",
+        table = table
+    )
+}
+
+impl DatabaseQuery for ReplaceTags {
+    /// the user's list of tags after the replacement
+    type Data = Vec<String>;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<Vec<String>> {
+        use diesel::prelude::*;
+        use diesel::sql_types::{Array, Int4, Nullable, Text};
+
+        connection.transaction(|| {
+            for table in &["records_record", "budgets_budget", "budgets_yearbudget"] {
+                diesel::sql_query(replace_query(table))
+                    .bind::<Int4, _>(self.user_id)
+                    .bind::<Array<Text>, _>(&self.tags)
+                    .bind::<Nullable<Text>, _>(&self.into)
+                    .execute(&connection)?;
+            }
+
+            let user_tags: Vec<String> = load_user_tags(self.user_id, &connection)?
+                .iter()
+                .filter_map(|tag| replaced(tag, &self.tags, self.into.as_deref()))
+                .collect();
+
+            // `save_user_tags` keeps only the first of repeated tags
+            Ok(save_user_tags(self.user_id, &user_tags, &connection)?)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,192 @@
+use super::*;
+use crate::{
+    db::{
+        builders::{BudgetBuilder, RecordBuilder, UserBuilder, YearBudgetBuilder},
+        ConnectionPool,
+    },
+    tags_vec,
+    tests::DbSession,
+};
+
+#[actix_rt::test]
+async fn merge_tags_everywhere() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default().tags(vec!["food", "cafe", "car"]));
+    let record = session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["car", "cafe", "food"])
+            .finish(),
+    );
+    let budget = session.create_budget(
+        BudgetBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["cafe"])
+            .finish(),
+    );
+    let year_budget = session.create_year_budget(
+        YearBudgetBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["cafe", "car"])
+            .finish(),
+    );
+
+    let user_tags = conn_pool
+        .execute(ReplaceTags::merge(
+            user.id.into(),
+            tags_vec!["cafe", "food"],
+            "eating out".to_string(),
+        ))
+        .await
+        .expect("Failed to merge tags");
+
+    // tags keep the position of the first replaced one
+    assert_eq!(tags_vec!["eating out", "car"], user_tags);
+    assert_eq!(
+        tags_vec!["car", "eating out"],
+        session.find_record(record.id).tags
+    );
+    assert_eq!(tags_vec!["eating out"], session.find_budget(budget.id).tags);
+    assert_eq!(
+        tags_vec!["eating out", "car"],
+        session.find_year_budget(year_budget.id).tags
+    );
+}
+
+#[actix_rt::test]
+async fn merge_into_existing_tag() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default().tags(vec!["food", "groceries"]));
+    let record = session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["groceries", "food"])
+            .finish(),
+    );
+
+    let user_tags = conn_pool
+        .execute(ReplaceTags::merge(
+            user.id.into(),
+            tags_vec!["food"],
+            "groceries".to_string(),
+        ))
+        .await
+        .expect("Failed to merge tags");
+
+    assert_eq!(tags_vec!["groceries"], user_tags);
+    assert_eq!(tags_vec!["groceries"], session.find_record(record.id).tags);
+}
+
+#[actix_rt::test]
+async fn delete_tag_everywhere() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default().tags(vec!["food", "car"]));
+    let record = session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["food", "car"])
+            .finish(),
+    );
+    let budget = session.create_budget(
+        BudgetBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["food"])
+            .finish(),
+    );
+
+    let user_tags = conn_pool
+        .execute(ReplaceTags::delete(user.id.into(), "food".to_string()))
+        .await
+        .expect("Failed to delete tag");
+
+    assert_eq!(tags_vec!["car"], user_tags);
+    assert_eq!(tags_vec!["car"], session.find_record(record.id).tags);
+    assert_eq!(tags_vec![], session.find_budget(budget.id).tags);
+}
+
+#[actix_rt::test]
+async fn does_not_touch_tags_of_other_user() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let owner = session.create_user(UserBuilder::default().username("foo").tags(vec!["food"]));
+    let other_user = session.create_user(UserBuilder::default().username("bar").tags(vec!["food"]));
+    let record = session.create_record(
+        RecordBuilder::default()
+            .user_id(other_user.id)
+            .tags(vec!["food"])
+            .finish(),
+    );
+
+    conn_pool
+        .execute(ReplaceTags::delete(owner.id.into(), "food".to_string()))
+        .await
+        .expect("Failed to delete tag");
+
+    assert_eq!(tags_vec!["food"], session.find_record(record.id).tags);
+    assert_eq!(tags_vec!["food"], session.user_tags(other_user.id));
+}
+
+#[actix_rt::test]
+async fn descendants_move_along() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default().tags(vec![
+        "food/cafe",
+        "meals/groceries",
+        "foodstuff",
+    ]));
+    let record = session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["foodstuff", "food/cafe", "meals"])
+            .finish(),
+    );
+
+    let user_tags = conn_pool
+        .execute(ReplaceTags::merge(
+            user.id.into(),
+            tags_vec!["food"],
+            "meals".to_string(),
+        ))
+        .await
+        .expect("Failed to merge tags");
+
+    assert_eq!(
+        tags_vec!["meals", "meals/cafe", "meals/groceries", "foodstuff"],
+        user_tags
+    );
+    assert_eq!(
+        tags_vec!["foodstuff", "meals/cafe", "meals"],
+        session.find_record(record.id).tags
+    );
+}
+
+#[actix_rt::test]
+async fn deleting_tag_deletes_descendants() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default().tags(vec!["food/cafe", "car"]));
+    let record = session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["food/cafe", "car"])
+            .finish(),
+    );
+
+    let user_tags = conn_pool
+        .execute(ReplaceTags::delete(user.id.into(), "food".to_string()))
+        .await
+        .expect("Failed to delete tag");
+
+    assert_eq!(tags_vec!["car"], user_tags);
+    assert_eq!(tags_vec!["car"], session.find_record(record.id).tags);
+}
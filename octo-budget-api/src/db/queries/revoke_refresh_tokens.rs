@@ -0,0 +1,52 @@
+use diesel::prelude::*;
+
+use crate::db::{refresh_tokens, DatabaseQuery, PooledConnection};
+use crate::errors::DbResult;
+
+/// Revokes refresh tokens on logout, so they cannot be used to get new
+/// access tokens.
+pub enum RevokeRefreshTokens {
+    /// The family of the given token, i.e. a single session, if the token
+    /// belongs to the user.
+    Family(i32, String),
+    /// Every session of the user.
+    User(i32),
+}
+
+impl DatabaseQuery for RevokeRefreshTokens {
+    type Data = usize;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<usize> {
+        use crate::db::schema::auth_refresh_tokens::dsl::*;
+
+        let revoked = match self {
+            Self::Family(id_of_user, token) => {
+                let token_family: Option<String> = auth_refresh_tokens
+                    .select(family)
+                    .filter(user_id.eq(id_of_user))
+                    .filter(token_hash.eq(refresh_tokens::hash(token)))
+                    .first(&connection)
+                    .optional()?;
+
+                match token_family {
+                    Some(token_family) => diesel::update(auth_refresh_tokens)
+                        .filter(family.eq(token_family))
+                        .filter(revoked_at.is_null())
+                        .set(revoked_at.eq(refresh_tokens::now()))
+                        .execute(&connection)?,
+                    None => 0,
+                }
+            }
+            Self::User(id_of_user) => diesel::update(auth_refresh_tokens)
+                .filter(user_id.eq(id_of_user))
+                .filter(revoked_at.is_null())
+                .set(revoked_at.eq(refresh_tokens::now()))
+                .execute(&connection)?,
+        };
+
+        Ok(revoked)
+    }
+}
+
+#[cfg(test)]
+mod tests;
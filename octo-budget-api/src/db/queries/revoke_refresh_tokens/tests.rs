@@ -0,0 +1,90 @@
+use super::*;
+use crate::{
+    db::{
+        builders::UserBuilder,
+        queries::{IssueRefreshToken, RotateRefreshToken},
+        ConnectionPool,
+    },
+    tests::DbSession,
+};
+
+#[actix_rt::test]
+async fn revokes_single_family() {
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+
+    let first = conn_pool
+        .execute(IssueRefreshToken::new(user.id))
+        .await
+        .unwrap();
+    let second = conn_pool
+        .execute(IssueRefreshToken::new(user.id))
+        .await
+        .unwrap();
+
+    let revoked = conn_pool
+        .execute(RevokeRefreshTokens::Family(user.id, first.clone()))
+        .await
+        .unwrap();
+    assert_eq!(1, revoked);
+
+    let first = conn_pool
+        .execute(RotateRefreshToken::new(first))
+        .await
+        .unwrap();
+    let second = conn_pool
+        .execute(RotateRefreshToken::new(second))
+        .await
+        .unwrap();
+
+    assert_eq!(None, first);
+    assert!(second.is_some());
+}
+
+#[actix_rt::test]
+async fn does_not_revoke_family_of_other_user() {
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().username("john"));
+    let other_user = session.create_user(UserBuilder::default().username("jane"));
+
+    let token = conn_pool
+        .execute(IssueRefreshToken::new(other_user.id))
+        .await
+        .unwrap();
+
+    let revoked = conn_pool
+        .execute(RevokeRefreshTokens::Family(user.id, token.clone()))
+        .await
+        .unwrap();
+    assert_eq!(0, revoked);
+
+    let token = conn_pool
+        .execute(RotateRefreshToken::new(token))
+        .await
+        .unwrap();
+    assert!(token.is_some());
+}
+
+#[actix_rt::test]
+async fn revokes_all_tokens_of_user() {
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().username("john"));
+    let other_user = session.create_user(UserBuilder::default().username("jane"));
+
+    for id in &[user.id, user.id, other_user.id] {
+        conn_pool
+            .execute(IssueRefreshToken::new(*id))
+            .await
+            .unwrap();
+    }
+
+    let revoked = conn_pool
+        .execute(RevokeRefreshTokens::User(user.id))
+        .await
+        .unwrap();
+
+    assert_eq!(2, revoked);
+}
@@ -0,0 +1,84 @@
+use diesel::prelude::*;
+
+use crate::db::{models::RefreshToken, refresh_tokens, DatabaseQuery, PooledConnection};
+use crate::errors::{DbError, DbResult};
+
+/// Exchanges a refresh token for a new one of the same family.
+///
+/// A token can be used only once. Presenting a token that was already
+/// rotated means it was stolen (or the client is broken), so the whole
+/// family is revoked and both parties have to log in again.
+///
+/// Deactivated users cannot renew their tokens.
+pub struct RotateRefreshToken {
+    token: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Rotated {
+    pub user_id: i32,
+    pub refresh: String,
+}
+
+impl RotateRefreshToken {
+    pub fn new(token: String) -> Self {
+        Self { token }
+    }
+}
+
+impl DatabaseQuery for RotateRefreshToken {
+    type Data = Option<Rotated>;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<Option<Rotated>> {
+        use crate::db::schema::auth_refresh_tokens::dsl::*;
+        use crate::db::schema::auth_user;
+
+        connection.transaction::<_, DbError, _>(|| {
+            let current_time = refresh_tokens::now();
+            let token: Option<RefreshToken> = auth_refresh_tokens
+                .filter(token_hash.eq(refresh_tokens::hash(&self.token)))
+                .for_update()
+                .first(&connection)
+                .optional()?;
+
+            let token = match token {
+                Some(token) if token.revoked_at.is_none() && token.expires_at > current_time => {
+                    token
+                }
+                _ => return Ok(None),
+            };
+
+            if token.used_at.is_some() {
+                diesel::update(auth_refresh_tokens.filter(family.eq(&token.family)))
+                    .filter(revoked_at.is_null())
+                    .set(revoked_at.eq(current_time))
+                    .execute(&connection)?;
+
+                return Ok(None);
+            }
+
+            let is_active: bool = auth_user::table
+                .find(token.user_id)
+                .select(auth_user::is_active)
+                .first(&connection)?;
+
+            if !is_active {
+                return Ok(None);
+            }
+
+            diesel::update(auth_refresh_tokens.find(token.id))
+                .set(used_at.eq(current_time))
+                .execute(&connection)?;
+
+            let refresh = refresh_tokens::issue(token.user_id, &token.family, &connection)?;
+
+            Ok(Some(Rotated {
+                user_id: token.user_id,
+                refresh,
+            }))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,144 @@
+use super::*;
+use crate::{
+    db::{builders::UserBuilder, queries::IssueRefreshToken, ConnectionPool},
+    tests::DbSession,
+};
+
+fn tokens(conn_pool: &ConnectionPool) -> Vec<RefreshToken> {
+    use crate::db::schema::auth_refresh_tokens::dsl::*;
+
+    auth_refresh_tokens
+        .order(id)
+        .load(&conn_pool.conn())
+        .expect("Failed to load refresh tokens")
+}
+
+#[actix_rt::test]
+async fn rotates_token() {
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+
+    let token = conn_pool
+        .execute(IssueRefreshToken::new(user.id))
+        .await
+        .unwrap();
+    let rotated = conn_pool
+        .execute(RotateRefreshToken::new(token.clone()))
+        .await
+        .unwrap()
+        .expect("Token is expected to be rotated");
+
+    assert_eq!(user.id, rotated.user_id);
+    assert_ne!(token, rotated.refresh);
+
+    let tokens = tokens(&conn_pool);
+    assert_eq!(2, tokens.len());
+    assert_eq!(tokens[0].family, tokens[1].family);
+    assert!(tokens[0].used_at.is_some());
+    assert_eq!(None, tokens[1].used_at);
+}
+
+#[actix_rt::test]
+async fn unknown_token_is_rejected() {
+    let conn_pool = ConnectionPool::new();
+    let _session = DbSession::new();
+
+    let rotated = conn_pool
+        .execute(RotateRefreshToken::new("unknown".to_string()))
+        .await
+        .unwrap();
+
+    assert_eq!(None, rotated);
+}
+
+#[actix_rt::test]
+async fn expired_token_is_rejected() {
+    use crate::db::schema::auth_refresh_tokens::dsl::*;
+
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+
+    let token = conn_pool
+        .execute(IssueRefreshToken::new(user.id))
+        .await
+        .unwrap();
+
+    diesel::update(auth_refresh_tokens)
+        .set(expires_at.eq(refresh_tokens::now()))
+        .execute(&conn_pool.conn())
+        .unwrap();
+
+    let rotated = conn_pool
+        .execute(RotateRefreshToken::new(token))
+        .await
+        .unwrap();
+
+    assert_eq!(None, rotated);
+}
+
+#[actix_rt::test]
+async fn reused_token_revokes_family() {
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+
+    let stolen = conn_pool
+        .execute(IssueRefreshToken::new(user.id))
+        .await
+        .unwrap();
+    let other_login = conn_pool
+        .execute(IssueRefreshToken::new(user.id))
+        .await
+        .unwrap();
+    let rotated = conn_pool
+        .execute(RotateRefreshToken::new(stolen.clone()))
+        .await
+        .unwrap()
+        .unwrap();
+
+    let reused = conn_pool
+        .execute(RotateRefreshToken::new(stolen))
+        .await
+        .unwrap();
+    assert_eq!(None, reused);
+
+    let after_reuse = conn_pool
+        .execute(RotateRefreshToken::new(rotated.refresh))
+        .await
+        .unwrap();
+    assert_eq!(None, after_reuse, "whole family is expected to be revoked");
+
+    let other_login = conn_pool
+        .execute(RotateRefreshToken::new(other_login))
+        .await
+        .unwrap();
+    assert!(other_login.is_some(), "other families are not affected");
+}
+
+#[actix_rt::test]
+async fn token_of_inactive_user_is_rejected() {
+    use crate::db::schema::auth_user::dsl::*;
+
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+
+    let token = conn_pool
+        .execute(IssueRefreshToken::new(user.id))
+        .await
+        .unwrap();
+
+    diesel::update(auth_user.find(user.id))
+        .set(is_active.eq(false))
+        .execute(&conn_pool.conn())
+        .unwrap();
+
+    let rotated = conn_pool
+        .execute(RotateRefreshToken::new(token))
+        .await
+        .unwrap();
+
+    assert_eq!(None, rotated);
+}
@@ -0,0 +1,37 @@
+use diesel::prelude::*;
+
+use crate::db::{schema::auth_user, DatabaseQuery, PooledConnection};
+use crate::errors::{DbError, DbResult};
+
+/// Hashes the password the same way Django does.
+pub struct SetPassword {
+    user_id: i32,
+    password: String,
+}
+
+impl SetPassword {
+    pub fn new(user_id: i32, password: String) -> Self {
+        Self { user_id, password }
+    }
+}
+
+impl DatabaseQuery for SetPassword {
+    type Data = ();
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<()> {
+        let hashed_password = djangohashers::make_password(&self.password);
+
+        let updated = diesel::update(auth_user::table.find(self.user_id))
+            .set(auth_user::password.eq(hashed_password))
+            .execute(&connection)?;
+
+        if updated == 1 {
+            Ok(())
+        } else {
+            Err(DbError::NotUpdated("auth_user", self.user_id))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,35 @@
+use super::*;
+use crate::{
+    db::{builders::UserBuilder, ConnectionPool},
+    tests::DbSession,
+};
+
+#[actix_rt::test]
+async fn sets_hashed_password() {
+    let pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().username("john").password("old"));
+
+    pool.execute(SetPassword::new(user.id, "new password".to_string()))
+        .await
+        .expect("failed to set password");
+
+    let user = session.find_user_by_name("john");
+    assert_eq!(
+        Ok(true),
+        djangohashers::check_password("new password", &user.password)
+    );
+}
+
+#[actix_rt::test]
+async fn missing_user() {
+    let pool = ConnectionPool::new();
+    let _session = DbSession::new();
+
+    let error = pool
+        .execute(SetPassword::new(-1, "new password".to_string()))
+        .await
+        .expect_err("is not expected to update anything");
+
+    assert_eq!("Cannot update auth_user with id: `-1'", error.to_string());
+}
@@ -1,4 +1,4 @@
-use crate::db::{schema::auth_user, DatabaseQuery, PooledConnection};
+use crate::db::{tags::save_user_tags, DatabaseQuery, PooledConnection};
 use crate::errors::{DbError, DbResult};
 use octo_budget_lib::auth_token::UserId;
 use std::sync::Arc;
@@ -6,6 +6,8 @@ use std::sync::Arc;
 type DataType = Arc<Vec<String>>;
 pub type TagsResult = DbResult<DataType>;
 
+/// Parents of nested tags, like "food" of "food/groceries", are added if
+/// they are missing.
 pub struct SetUserTags {
     tags: DataType,
     user_id: UserId,
@@ -22,17 +24,12 @@ impl DatabaseQuery for SetUserTags {
     type Data = DataType;
 
     fn execute(&self, connection: PooledConnection) -> TagsResult {
-        use diesel::prelude::*;
-
         let owner_user_id: i32 = self.user_id.into();
-        let target = auth_user::table.filter(auth_user::id.eq(owner_user_id));
 
-        diesel::update(target)
-            .set(auth_user::tags.eq(&*self.tags))
-            .execute(&connection)
-            .map_err(DbError::Unknown)?;
+        let tags =
+            save_user_tags(owner_user_id, &self.tags, &connection).map_err(DbError::Unknown)?;
 
-        Ok(self.tags.clone())
+        Ok(Arc::new(tags))
     }
 }
 
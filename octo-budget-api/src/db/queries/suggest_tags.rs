@@ -0,0 +1,103 @@
+use octo_budget_lib::auth_token::UserId;
+
+use crate::db::{DatabaseQuery, PooledConnection};
+use crate::errors::DbResult;
+
+const LIMIT: i64 = 10;
+/// shorter words, like "at" or "to", say nothing about a record
+const MIN_WORD_LENGTH: usize = 3;
+/// A past use of a tag with a word of the comment counts this many times more
+/// than any other use.
+const COOCCURRENCE_WEIGHT: f64 = 3.0;
+
+/// Tags of the user starting with `prefix`, the most likely ones for a new
+/// record first. Nested tags match by any part of their name, e.g. "gro"
+/// matches "food/groceries".
+///
+/// Every past use of a tag is scored by frecency: it counts less the older
+/// the record is. Uses in records whose comment shares words with `comment`
+/// count more. Tags that were never used come last, in the user's order.
+pub struct SuggestTags {
+    user_id: i32,
+    prefix: String,
+    words: Vec<String>,
+    half_life_days: f64,
+}
+
+impl SuggestTags {
+    pub fn new(user_id: UserId, prefix: &str, comment: &str, half_life_days: f64) -> Self {
+        Self {
+            user_id: user_id.into(),
+            prefix: prefix.trim().to_lowercase(),
+            words: comment_words(comment),
+            half_life_days,
+        }
+    }
+}
+
+/// Distinct lowercase words, split the same way as comments in `QUERY`.
+fn comment_words(comment: &str) -> Vec<String> {
+    let mut words: Vec<String> = vec![];
+
+    for word in comment.to_lowercase().split(|c: char| !c.is_alphanumeric()) {
+        if word.chars().count() >= MIN_WORD_LENGTH && !words.iter().any(|w| w == word) {
+            words.push(word.to_string());
+        }
+    }
+
+    words
+}
+
+#[derive(QueryableByName, Debug)]
+struct Row {
+    #[sql_type = "diesel::sql_types::Text"]
+    tag: String,
+}
+
+// the exponent is capped the same as in `GetTagScores`
+const QUERY: &str = "
+    WITH uses AS (
+        SELECT tag,
+            power(0.5, LEAST(GREATEST(EXTRACT(EPOCH FROM now() - created_at), 0) / 86400 / $2, 1000)) AS weight,
+            regexp_split_to_array(lower(COALESCE(comment, '')), '[^[:alnum:]]+') AS words
+        FROM records_record, unnest(tags) AS tag
+        WHERE user_id = $1
+    ), scores AS (
+        SELECT tag,
+            SUM(weight) AS frecency,
+            SUM(CASE WHEN words && $4 THEN weight ELSE 0 END) AS cooccurrence
+        FROM uses
+        GROUP BY tag
+    )
+    SELECT user_tags.name AS tag
+    FROM tags AS user_tags
+    LEFT JOIN scores ON scores.tag = user_tags.name
+    WHERE user_tags.user_id = $1
+      AND (left(lower(user_tags.name), length($3)) = $3
+        OR strpos(lower(user_tags.name), '/' || $3) > 0)
+    ORDER BY COALESCE(scores.frecency + $5 * scores.cooccurrence, 0) DESC, user_tags.position
+    LIMIT $6
+";
+
+impl DatabaseQuery for SuggestTags {
+    type Data = Vec<String>;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<Vec<String>> {
+        use diesel::prelude::*;
+        use diesel::sql_types::{Array, Float8, Int4, Int8, Text};
+
+        let rows = diesel::sql_query(QUERY)
+            .bind::<Int4, _>(self.user_id)
+            .bind::<Float8, _>(self.half_life_days)
+            .bind::<Text, _>(&self.prefix)
+            .bind::<Array<Text>, _>(&self.words)
+            .bind::<Float8, _>(COOCCURRENCE_WEIGHT)
+            .bind::<Int8, _>(LIMIT)
+            .load::<Row>(&connection)?;
+
+        Ok(rows.into_iter().map(|row| row.tag).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests;
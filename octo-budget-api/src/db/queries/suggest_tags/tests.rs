@@ -0,0 +1,142 @@
+use super::*;
+use crate::{
+    db::{
+        builders::{RecordBuilder, UserBuilder},
+        ConnectionPool,
+    },
+    tags_vec,
+    tests::DbSession,
+};
+use chrono::{Duration, Utc};
+
+#[test]
+fn words_of_comment() {
+    assert_eq!(
+        tags_vec!["latte", "starbucks", "кофе"],
+        comment_words("Latte at Starbucks, latte & КОФЕ!")
+    );
+}
+
+#[actix_rt::test]
+async fn recent_uses_count_more() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default().tags(vec!["old", "new", "unused"]));
+    let now = Utc::now().naive_utc();
+
+    for _ in 0..5 {
+        session.create_record(
+            RecordBuilder::default()
+                .user_id(user.id)
+                .tags(vec!["old"])
+                .created_at(now - Duration::days(365))
+                .finish(),
+        );
+    }
+    session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["new"])
+            .created_at(now)
+            .finish(),
+    );
+
+    let tags = conn_pool
+        .execute(SuggestTags::new(user.id.into(), "", "", 30.0))
+        .await
+        .expect("Failed to suggest tags");
+
+    assert_eq!(tags_vec!["new", "old", "unused"], tags);
+}
+
+#[actix_rt::test]
+async fn very_old_uses_do_not_underflow() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default().tags(vec!["ancient"]));
+    session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["ancient"])
+            .created_at(Utc::now().naive_utc() - Duration::days(3650))
+            .finish(),
+    );
+
+    let tags = conn_pool
+        .execute(SuggestTags::new(user.id.into(), "", "", 1.0))
+        .await
+        .expect("Failed to suggest tags");
+
+    assert_eq!(tags_vec!["ancient"], tags);
+}
+
+#[actix_rt::test]
+async fn tags_used_with_words_of_comment_come_first() {
+    let conn_pool = ConnectionPool::new();
+    let mut session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default().tags(vec!["food", "coffee"]));
+
+    for _ in 0..3 {
+        session.create_record(
+            RecordBuilder::default()
+                .user_id(user.id)
+                .tags(vec!["food"])
+                .comment("groceries")
+                .finish(),
+        );
+    }
+    session.create_record(
+        RecordBuilder::default()
+            .user_id(user.id)
+            .tags(vec!["coffee"])
+            .comment("Latte at Starbucks")
+            .finish(),
+    );
+
+    let tags = conn_pool
+        .execute(SuggestTags::new(user.id.into(), "", "starbucks", 30.0))
+        .await
+        .expect("Failed to suggest tags");
+
+    assert_eq!(tags_vec!["coffee", "food"], tags);
+
+    let tags = conn_pool
+        .execute(SuggestTags::new(user.id.into(), "", "", 30.0))
+        .await
+        .expect("Failed to suggest tags");
+
+    assert_eq!(tags_vec!["food", "coffee"], tags);
+}
+
+#[actix_rt::test]
+async fn only_tags_starting_with_prefix() {
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default().tags(vec!["Food", "fun", "car"]));
+
+    let tags = conn_pool
+        .execute(SuggestTags::new(user.id.into(), "F", "", 30.0))
+        .await
+        .expect("Failed to suggest tags");
+
+    assert_eq!(tags_vec!["Food", "fun"], tags);
+}
+
+#[actix_rt::test]
+async fn nested_tags_match_by_any_part() {
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+
+    let user = session.create_user(UserBuilder::default().tags(vec!["food/groceries", "gym"]));
+
+    let tags = conn_pool
+        .execute(SuggestTags::new(user.id.into(), "gr", "", 30.0))
+        .await
+        .expect("Failed to suggest tags");
+
+    assert_eq!(tags_vec!["food/groceries"], tags);
+}
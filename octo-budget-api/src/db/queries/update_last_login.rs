@@ -0,0 +1,31 @@
+use chrono::Utc;
+use diesel::prelude::*;
+
+use crate::db::{schema::auth_user, DatabaseQuery, PooledConnection};
+use crate::errors::DbResult;
+
+/// Remembers when the user logged in for the last time, to spot stale accounts.
+pub struct UpdateLastLogin {
+    user_id: i32,
+}
+
+impl UpdateLastLogin {
+    pub fn new(user_id: i32) -> Self {
+        Self { user_id }
+    }
+}
+
+impl DatabaseQuery for UpdateLastLogin {
+    type Data = ();
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<()> {
+        diesel::update(auth_user::table.find(self.user_id))
+            .set(auth_user::last_login.eq(Utc::now().naive_utc()))
+            .execute(&connection)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,24 @@
+use super::*;
+use crate::{
+    db::{builders::UserBuilder, ConnectionPool},
+    tests::DbSession,
+};
+
+#[actix_rt::test]
+async fn sets_last_login() {
+    let conn_pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().username("john"));
+
+    assert_eq!(None, user.last_login);
+
+    conn_pool
+        .execute(UpdateLastLogin::new(user.id))
+        .await
+        .expect("Failed to update last login");
+
+    let user = session.find_user_by_name("john");
+    let last_login = user.last_login.expect("last_login is expected to be set");
+
+    assert!((Utc::now().naive_utc() - last_login).num_seconds() < 5);
+}
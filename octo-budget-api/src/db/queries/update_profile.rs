@@ -0,0 +1,70 @@
+use diesel::prelude::*;
+
+use crate::apps::forms::profile::Data;
+use crate::db::{models::AuthUser, schema::auth_user, DatabaseQuery, PooledConnection};
+use crate::errors::{add_table_name, DbResult};
+
+/// Updates only the fields that are set, returns the updated user.
+pub struct UpdateProfile {
+    user_id: i32,
+    changes: Changes,
+}
+
+#[derive(AsChangeset)]
+#[table_name = "auth_user"]
+struct Changes {
+    email: Option<String>,
+    first_name: Option<String>,
+    last_name: Option<String>,
+}
+
+impl UpdateProfile {
+    pub fn new(user_id: i32, data: Data) -> Self {
+        let Data {
+            email,
+            first_name,
+            last_name,
+        } = data;
+
+        Self {
+            user_id,
+            changes: Changes {
+                email,
+                first_name,
+                last_name,
+            },
+        }
+    }
+
+    fn has_changes(&self) -> bool {
+        let Changes {
+            email,
+            first_name,
+            last_name,
+        } = &self.changes;
+
+        email.is_some() || first_name.is_some() || last_name.is_some()
+    }
+}
+
+impl DatabaseQuery for UpdateProfile {
+    type Data = AuthUser;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<AuthUser> {
+        let target = auth_user::table.find(self.user_id);
+
+        // diesel refuses to run an update without any changes
+        let user = if self.has_changes() {
+            diesel::update(target)
+                .set(&self.changes)
+                .get_result(&connection)
+        } else {
+            target.first(&connection)
+        };
+
+        user.map_err(add_table_name("auth_user"))
+    }
+}
+
+#[cfg(test)]
+mod tests;
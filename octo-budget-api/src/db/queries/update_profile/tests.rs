@@ -0,0 +1,43 @@
+use super::*;
+use crate::{
+    db::{builders::UserBuilder, ConnectionPool},
+    tests::DbSession,
+};
+
+#[actix_rt::test]
+async fn updates_only_given_fields() {
+    let pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(
+        UserBuilder::default()
+            .username("john")
+            .email("john@example.com"),
+    );
+
+    let data = Data {
+        first_name: Some("John".to_string()),
+        ..Default::default()
+    };
+    let updated = pool
+        .execute(UpdateProfile::new(user.id, data))
+        .await
+        .expect("failed to update profile");
+
+    assert_eq!("John", updated.first_name);
+    assert_eq!("john@example.com", updated.email);
+    assert_eq!(updated, session.find_user_by_name("john"));
+}
+
+#[actix_rt::test]
+async fn nothing_to_update() {
+    let pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+
+    let updated = pool
+        .execute(UpdateProfile::new(user.id, Data::default()))
+        .await
+        .expect("failed to update profile");
+
+    assert_eq!(user, updated);
+}
@@ -79,6 +79,7 @@ async fn check_update_result() {
             user_id: user.id,
             page: 1,
             per_page: 1,
+            filters: Default::default(),
         })
         .await;
     assert!(res.is_ok(), "result is not Ok, {:?}", res);
@@ -0,0 +1,31 @@
+use diesel::prelude::*;
+
+use crate::db::{models::UserSettings, schema::user_settings, DatabaseQuery, PooledConnection};
+use crate::errors::DbResult;
+
+/// Saves settings of the user, creating the row on the first change.
+pub struct UpdateUserSettings(UserSettings);
+
+impl UpdateUserSettings {
+    pub fn new(settings: UserSettings) -> Self {
+        Self(settings)
+    }
+}
+
+impl DatabaseQuery for UpdateUserSettings {
+    type Data = UserSettings;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<UserSettings> {
+        let settings = diesel::insert_into(user_settings::table)
+            .values(&self.0)
+            .on_conflict(user_settings::user_id)
+            .do_update()
+            .set(&self.0)
+            .get_result(&connection)?;
+
+        Ok(settings)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,31 @@
+use super::*;
+use crate::{
+    db::{builders::UserBuilder, ConnectionPool},
+    tests::DbSession,
+};
+
+#[actix_rt::test]
+async fn creates_and_updates_settings() {
+    let pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+
+    let created = pool
+        .execute(UpdateUserSettings::new(UserSettings {
+            month_start_day: 15,
+            ..UserSettings::defaults(user.id)
+        }))
+        .await
+        .unwrap();
+    assert_eq!(15, created.month_start_day);
+
+    let updated = pool
+        .execute(UpdateUserSettings::new(UserSettings {
+            default_currency: "USD".to_string(),
+            ..created
+        }))
+        .await
+        .unwrap();
+    assert_eq!(15, updated.month_start_day);
+    assert_eq!("USD", updated.default_currency);
+}
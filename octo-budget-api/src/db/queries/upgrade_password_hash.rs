@@ -0,0 +1,75 @@
+use diesel::prelude::*;
+use lazy_static::lazy_static;
+
+use crate::db::{models::AuthUser, schema::auth_user, DatabaseQuery, PooledConnection};
+use crate::errors::DbResult;
+
+lazy_static! {
+    /// Algorithm and number of iterations `make_password` currently uses,
+    /// e.g. `("pbkdf2_sha256", 180000)`.
+    static ref CURRENT_HASHER: (String, u32) = {
+        let hash = djangohashers::make_password("");
+        let (algorithm, iterations) = parse(&hash);
+
+        (
+            algorithm.to_string(),
+            iterations.expect("Default hasher is expected to have iterations"),
+        )
+    };
+}
+
+/// Re-hashes the password of a user with the current default algorithm.
+///
+/// Users migrated from Django may still have hashes made by weaker algorithms
+/// or with fewer iterations. The plain password is known only on login, so
+/// that is when the hash is upgraded. If the password was changed meanwhile,
+/// the new hash is kept.
+pub struct UpgradePasswordHash {
+    user_id: i32,
+    old_hash: String,
+    password: String,
+}
+
+impl UpgradePasswordHash {
+    pub fn new(user: &AuthUser, password: String) -> Self {
+        Self {
+            user_id: user.id,
+            old_hash: user.password.clone(),
+            password,
+        }
+    }
+
+    /// Same as Django's `must_update`.
+    pub fn is_needed(user: &AuthUser) -> bool {
+        let (algorithm, iterations) = parse(&user.password);
+        let (current_algorithm, current_iterations) = &*CURRENT_HASHER;
+
+        algorithm != current_algorithm || iterations.filter(|n| n >= current_iterations).is_none()
+    }
+}
+
+impl DatabaseQuery for UpgradePasswordHash {
+    type Data = bool;
+
+    fn execute(&self, connection: PooledConnection) -> DbResult<bool> {
+        let new_hash = djangohashers::make_password(&self.password);
+
+        let updated = diesel::update(auth_user::table.find(self.user_id))
+            .filter(auth_user::password.eq(&self.old_hash))
+            .set(auth_user::password.eq(new_hash))
+            .execute(&connection)?;
+
+        Ok(updated == 1)
+    }
+}
+
+fn parse(encoded: &str) -> (&str, Option<u32>) {
+    let mut parts = encoded.splitn(3, '$');
+    let algorithm = parts.next().unwrap_or_default();
+    let iterations = parts.next().and_then(|n| n.parse().ok());
+
+    (algorithm, iterations)
+}
+
+#[cfg(test)]
+mod tests;
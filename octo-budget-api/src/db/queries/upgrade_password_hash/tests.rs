@@ -0,0 +1,87 @@
+use super::*;
+use crate::{
+    db::{builders::UserBuilder, ConnectionPool},
+    tests::DbSession,
+};
+use djangohashers::{make_password_with_algorithm, Algorithm, Django, DjangoVersion};
+
+fn user_with_hash(hash: String) -> AuthUser {
+    let mut user = UserBuilder::default().finish();
+    user.password = hash;
+    user
+}
+
+#[test]
+fn current_hash_is_not_upgraded() {
+    let user = user_with_hash(djangohashers::make_password("foo"));
+
+    assert!(!UpgradePasswordHash::is_needed(&user));
+}
+
+#[test]
+fn hash_with_fewer_iterations_is_upgraded() {
+    let django = Django {
+        version: DjangoVersion::V1_11,
+    };
+    let user = user_with_hash(django.make_password("foo"));
+
+    assert!(UpgradePasswordHash::is_needed(&user));
+}
+
+#[test]
+fn hash_of_other_algorithm_is_upgraded() {
+    let user = user_with_hash(make_password_with_algorithm("foo", Algorithm::SHA1));
+    assert!(UpgradePasswordHash::is_needed(&user));
+
+    let user = user_with_hash(make_password_with_algorithm("foo", Algorithm::UnsaltedMD5));
+    assert!(UpgradePasswordHash::is_needed(&user));
+}
+
+#[actix_rt::test]
+async fn stores_new_hash() {
+    use crate::db::schema::auth_user::dsl::*;
+
+    let pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().username("john"));
+    let legacy_hash = make_password_with_algorithm("foo", Algorithm::SHA1);
+
+    diesel::update(auth_user.find(user.id))
+        .set(password.eq(&legacy_hash))
+        .execute(&pool.conn())
+        .unwrap();
+    let user = session.find_user_by_name("john");
+
+    let upgraded = pool
+        .execute(UpgradePasswordHash::new(&user, "foo".to_string()))
+        .await
+        .unwrap();
+    assert!(upgraded);
+
+    let user = session.find_user_by_name("john");
+    assert!(!UpgradePasswordHash::is_needed(&user));
+    assert_eq!(
+        Ok(true),
+        djangohashers::check_password("foo", &user.password)
+    );
+}
+
+#[actix_rt::test]
+async fn keeps_password_changed_meanwhile() {
+    let pool = ConnectionPool::new();
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default().username("john"));
+    let stale_user = user_with_hash("sha1$salt$outdated".to_string());
+    let stale_user = AuthUser {
+        id: user.id,
+        ..stale_user
+    };
+
+    let upgraded = pool
+        .execute(UpgradePasswordHash::new(&stale_user, "foo".to_string()))
+        .await
+        .unwrap();
+    assert!(!upgraded);
+
+    assert_eq!(user.password, session.find_user_by_name("john").password);
+}
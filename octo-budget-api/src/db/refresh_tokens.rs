@@ -0,0 +1,50 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use sha2::{Digest, Sha256};
+
+use crate::db::{models::NewRefreshToken, PooledConnection};
+use crate::errors::DbResult;
+
+/// Refresh tokens are rotated on every use, so this is how long a client can
+/// stay away before it has to log in with a password again.
+pub const EXPIRE_IN_DAYS: i64 = 30;
+
+const TOKEN_LENGTH: usize = 64;
+
+/// A random, url-safe string used both for tokens and for token families.
+pub fn generate() -> String {
+    thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(TOKEN_LENGTH)
+        .collect()
+}
+
+/// Only hashes are stored, so a database dump does not leak usable tokens.
+pub fn hash(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+pub fn now() -> NaiveDateTime {
+    Utc::now().naive_utc()
+}
+
+/// Stores a new refresh token of `family` and returns it in plain text.
+pub fn issue(user_id: i32, family: &str, connection: &PooledConnection) -> DbResult<String> {
+    use crate::db::schema::auth_refresh_tokens;
+
+    let token = generate();
+    let created_at = now();
+
+    diesel::insert_into(auth_refresh_tokens::table)
+        .values(NewRefreshToken {
+            user_id,
+            family: family.to_string(),
+            token_hash: hash(&token),
+            created_at,
+            expires_at: created_at + Duration::days(EXPIRE_IN_DAYS),
+        })
+        .execute(connection)?;
+
+    Ok(token)
+}
@@ -0,0 +1,110 @@
+//! Tags form a tree by their names: "food/groceries" is a child of "food".
+//! A user's tags are kept in the `tags` table, where every tag refers to its
+//! parent, while records and budgets keep full names of their tags.
+
+use diesel::prelude::*;
+use diesel::PgConnection;
+
+use crate::db::schema::tags;
+
+pub const SEPARATOR: char = '/';
+
+/// Tags with all their ancestors, every ancestor right before its first
+/// descendant, and without repetitions.
+pub fn with_ancestors(names: &[String]) -> Vec<String> {
+    let mut result: Vec<String> = vec![];
+
+    for name in names {
+        let ancestors = name
+            .match_indices(SEPARATOR)
+            .map(|(idx, _)| &name[..idx])
+            .filter(|ancestor| !ancestor.is_empty());
+
+        for tag in ancestors.chain(std::iter::once(name.as_str())) {
+            if !result.iter().any(|t| t == tag) {
+                result.push(tag.to_string());
+            }
+        }
+    }
+
+    result
+}
+
+/// The parent of a tag, i.e. its name without the last part.
+pub fn parent(name: &str) -> Option<&str> {
+    name.rfind(SEPARATOR)
+        .map(|idx| &name[..idx])
+        .filter(|parent| !parent.is_empty())
+}
+
+/// The new name of `tag` if `tags` and all their descendants are replaced by
+/// `into` and its descendants, `None` if the tag is removed. The first of
+/// `tags` the tag descends from is replaced, the same as in `ReplaceTags`.
+pub fn replaced(tag: &str, tags: &[String], into: Option<&str>) -> Option<String> {
+    let source = tags.iter().find(|source| is_descendant(tag, source));
+
+    match (source, into) {
+        (None, _) => Some(tag.to_string()),
+        (Some(source), Some(into)) => Some(format!("{}{}", into, &tag[source.len()..])),
+        (Some(_), None) => None,
+    }
+}
+
+/// `true` for the tag itself too.
+pub fn is_descendant(tag: &str, ancestor: &str) -> bool {
+    tag.starts_with(ancestor)
+        && (tag.len() == ancestor.len() || tag[ancestor.len()..].starts_with(SEPARATOR))
+}
+
+/// The user's tags in the user's order.
+pub fn load_user_tags(owner_id: i32, connection: &PgConnection) -> QueryResult<Vec<String>> {
+    tags::table
+        .select(tags::name)
+        .filter(tags::user_id.eq(owner_id))
+        .order(tags::position)
+        .load(connection)
+}
+
+/// Replaces the user's tags, adding missing ancestors, see `with_ancestors`.
+/// Returns the saved tags.
+pub fn save_user_tags(
+    owner_id: i32,
+    names: &[String],
+    connection: &PgConnection,
+) -> QueryResult<Vec<String>> {
+    let names = with_ancestors(names);
+
+    connection.transaction::<_, diesel::result::Error, _>(|| {
+        diesel::delete(tags::table.filter(tags::user_id.eq(owner_id))).execute(connection)?;
+
+        // ancestors come first, so a parent is always inserted before its children
+        let mut ids: Vec<(&str, i32)> = Vec::with_capacity(names.len());
+
+        for (position, name) in names.iter().enumerate() {
+            let parent_id = parent(name).and_then(|parent| {
+                ids.iter()
+                    .find(|(tag, _)| *tag == parent)
+                    .map(|(_, id)| *id)
+            });
+
+            let id = diesel::insert_into(tags::table)
+                .values((
+                    tags::user_id.eq(owner_id),
+                    tags::parent_id.eq(parent_id),
+                    tags::name.eq(name),
+                    tags::position.eq(position as i32),
+                ))
+                .returning(tags::id)
+                .get_result(connection)?;
+
+            ids.push((name, id));
+        }
+
+        Ok(())
+    })?;
+
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests;
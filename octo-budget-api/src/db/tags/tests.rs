@@ -0,0 +1,80 @@
+use super::*;
+use crate::{db::builders::UserBuilder, tags_vec, tests::DbSession};
+
+#[test]
+fn ancestors_come_before_their_first_descendant() {
+    let tags = tags_vec!["car", "food/groceries", "food", "food/cafe/lunch"];
+
+    assert_eq!(
+        tags_vec![
+            "car",
+            "food",
+            "food/groceries",
+            "food/cafe",
+            "food/cafe/lunch"
+        ],
+        with_ancestors(&tags)
+    );
+}
+
+#[test]
+fn parent_of_tag() {
+    assert_eq!(Some("food/cafe"), parent("food/cafe/lunch"));
+    assert_eq!(None, parent("food"));
+}
+
+#[test]
+fn descendants_are_replaced_too() {
+    let tags = tags_vec!["food"];
+
+    assert_eq!(
+        Some("meals/cafe".to_string()),
+        replaced("food/cafe", &tags, Some("meals"))
+    );
+    assert_eq!(
+        Some("meals".to_string()),
+        replaced("food", &tags, Some("meals"))
+    );
+    assert_eq!(None, replaced("food/cafe", &tags, None));
+    assert_eq!(
+        Some("foodstuff".to_string()),
+        replaced("foodstuff", &tags, None)
+    );
+}
+
+#[test]
+fn saved_tags_refer_to_their_parents() {
+    let session = DbSession::new();
+    let user = session.create_user(UserBuilder::default());
+
+    let saved = save_user_tags(user.id, &tags_vec!["food/cafe", "car"], session.conn())
+        .expect("Failed to save tags");
+    assert_eq!(tags_vec!["food", "food/cafe", "car"], saved);
+
+    let parents: Vec<(String, Option<String>)> = diesel::sql_query(
+        "SELECT t.name, p.name AS parent FROM tags t LEFT JOIN tags p ON p.id = t.parent_id ORDER BY t.position",
+    )
+    .load::<ParentRow>(session.conn())
+    .unwrap()
+    .into_iter()
+    .map(|row| (row.name, row.parent))
+    .collect();
+
+    assert_eq!(
+        vec![
+            ("food".to_string(), None),
+            ("food/cafe".to_string(), Some("food".to_string())),
+            ("car".to_string(), None),
+        ],
+        parents
+    );
+    assert_eq!(saved, load_user_tags(user.id, session.conn()).unwrap());
+}
+
+#[derive(QueryableByName)]
+struct ParentRow {
+    #[sql_type = "diesel::sql_types::Text"]
+    name: String,
+    #[sql_type = "diesel::sql_types::Nullable<diesel::sql_types::Text>"]
+    parent: Option<String>,
+}
@@ -9,6 +9,8 @@ use serde::{Serialize, Serializer};
 pub enum ValidationError {
     #[fail(display = "Unable to log in with provided credentials.")]
     AuthFailed,
+    #[fail(display = "User account is disabled.")]
+    AccountDisabled,
     #[fail(display = "This field may not be blank.")]
     CannotBeBlank,
     #[fail(display = "This field is required.")]
@@ -115,6 +117,12 @@ impl From<redis::RedisError> for Error {
     }
 }
 
+impl From<DbError> for Error {
+    fn from(error: DbError) -> Self {
+        Error::Unknown(error.into())
+    }
+}
+
 impl From<failure::Error> for Error {
     fn from(error: failure::Error) -> Self {
         Error::Unknown(error)
@@ -5,6 +5,7 @@ pub mod apps;
 pub mod config;
 pub mod db;
 pub mod errors;
+pub mod mailer;
 pub mod redis;
 pub mod routes;
 
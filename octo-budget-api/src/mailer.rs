@@ -0,0 +1,166 @@
+use failure::Error;
+use log::info;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Delivers emails. Sending is blocking, use `send` from async code.
+pub trait Mailer: Send + Sync {
+    fn deliver(&self, email: &Email) -> Result<(), Error>;
+}
+
+/// Sends emails through an SMTP server over TLS (submissions port).
+pub struct SmtpMailer {
+    host: String,
+    username: String,
+    password: String,
+    from: String,
+}
+
+impl SmtpMailer {
+    pub fn new(host: String, username: String, password: String, from: String) -> Self {
+        Self {
+            host,
+            username,
+            password,
+            from,
+        }
+    }
+}
+
+impl Mailer for SmtpMailer {
+    fn deliver(&self, email: &Email) -> Result<(), Error> {
+        use lettre::{
+            smtp::authentication::Credentials, EmailAddress, Envelope, SendableEmail, SmtpClient,
+            Transport,
+        };
+
+        let envelope = Envelope::new(
+            Some(EmailAddress::new(self.from.clone())?),
+            vec![EmailAddress::new(email.to.clone())?],
+        )?;
+        let message_id = format!("{}@{}", timestamp(), self.host);
+        let sendable = SendableEmail::new(envelope, message_id, message(&self.from, email));
+
+        SmtpClient::new_simple(&self.host)?
+            .credentials(Credentials::new(
+                self.username.clone(),
+                self.password.clone(),
+            ))
+            .transport()
+            .send(sendable)?;
+
+        Ok(())
+    }
+}
+
+/// Keeps emails instead of sending them, for local development and tests.
+/// Every email is written into its own file in `dir`, or to the log if there
+/// is no directory configured.
+pub struct Outbox {
+    dir: Option<PathBuf>,
+}
+
+impl Outbox {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self { dir }
+    }
+
+    /// Emails sent to `to`, oldest first.
+    pub fn emails_to(&self, to: &str) -> Vec<String> {
+        let dir = match &self.dir {
+            Some(dir) => dir,
+            None => return Vec::new(),
+        };
+        let suffix = format!("-{}.eml", to);
+
+        let mut paths: Vec<_> = fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|path| path.to_string_lossy().ends_with(&suffix))
+                    .collect()
+            })
+            .unwrap_or_default();
+        paths.sort();
+
+        paths
+            .iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .collect()
+    }
+}
+
+impl Mailer for Outbox {
+    fn deliver(&self, email: &Email) -> Result<(), Error> {
+        let message = message("outbox@localhost", email);
+
+        match &self.dir {
+            Some(dir) => {
+                fs::create_dir_all(dir)?;
+                let path = dir.join(format!("{}-{}.eml", timestamp(), email.to));
+                fs::write(&path, message)?;
+                info!("Email to {} is saved to {:?}", email.to, path);
+            }
+            None => info!(
+                "Email to {}:\n{}",
+                email.to,
+                String::from_utf8_lossy(&message)
+            ),
+        }
+
+        Ok(())
+    }
+}
+
+/// SMTP mailer if `SMTP_HOST` is set, outbox otherwise.
+pub fn from_config() -> Arc<dyn Mailer> {
+    match config::smtp_host() {
+        Some(host) => Arc::new(SmtpMailer::new(
+            host,
+            config::SMTP_USERNAME.to_string(),
+            config::SMTP_PASSWORD.to_string(),
+            config::MAIL_FROM.to_string(),
+        )),
+        None => Arc::new(Outbox::new(config::mail_outbox_dir())),
+    }
+}
+
+pub async fn send(mailer: Arc<dyn Mailer>, email: Email) -> Result<(), crate::errors::Error> {
+    use actix_http::error::BlockingError;
+
+    actix_web::web::block(move || mailer.deliver(&email))
+        .await
+        .map_err(|e| match e {
+            BlockingError::Error(err) => err.into(),
+            BlockingError::Canceled => failure::err_msg("Mailer thread pool is gone").into(),
+        })
+}
+
+fn message(from: &str, email: &Email) -> Vec<u8> {
+    format!(
+        "From: {}\r\nTo: {}\r\nSubject: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}",
+        from, email.to, email.subject, email.body
+    )
+    .into_bytes()
+}
+
+fn timestamp() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,35 @@
+use super::*;
+
+fn email(to: &str) -> Email {
+    Email {
+        to: to.to_string(),
+        subject: "Hello".to_string(),
+        body: "Hello there".to_string(),
+    }
+}
+
+#[test]
+fn outbox_saves_every_email_into_file() {
+    let dir = std::env::temp_dir().join(format!("octo-budget-outbox-{}", timestamp()));
+    let outbox = Outbox::new(Some(dir.clone()));
+
+    outbox.deliver(&email("john@example.com")).unwrap();
+    outbox.deliver(&email("john@example.com")).unwrap();
+    outbox.deliver(&email("jane@example.com")).unwrap();
+
+    let emails = outbox.emails_to("john@example.com");
+    assert_eq!(2, emails.len());
+    assert!(emails[0].contains("To: john@example.com\r\n"));
+    assert!(emails[0].contains("Subject: Hello\r\n"));
+    assert!(emails[0].ends_with("\r\n\r\nHello there"));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn outbox_without_dir_only_logs() {
+    let outbox = Outbox::new(None);
+
+    assert!(outbox.deliver(&email("john@example.com")).is_ok());
+    assert!(outbox.emails_to("john@example.com").is_empty());
+}
@@ -1,38 +1,198 @@
+use log::{error, info};
+use std::collections::VecDeque;
+use std::io;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex, RwLock,
+};
+use std::time::Duration;
+
 use crate::config;
 
 pub type RedisConnection = redis::aio::MultiplexedConnection;
 
+/// Updates queued while Redis is unavailable, the oldest are dropped beyond it.
+const MAX_PENDING_UPDATES: usize = 10_000;
+
+/// Delay between attempts to reconnect, it doubles after every failed one...
+const MIN_RECONNECT_DELAY: Duration = Duration::from_millis(100);
+/// ...up to this.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
 #[derive(Clone)]
 pub struct Redis {
     client: redis::Client,
-    connection: RedisConnection,
+    connection: Arc<RwLock<Option<RedisConnection>>>,
+    pending: Arc<Mutex<VecDeque<redis::Pipeline>>>,
+    reconnecting: Arc<AtomicBool>,
 }
 
 impl Redis {
-    pub async fn new() -> Self {
-        config::redis_url();
-        let redis_url = config::REDIS_URL.to_string();
-        let client =
-            redis::Client::open(redis_url.as_str()).expect("Failed to create redis client");
-        let (connection, driver) = client
-            .get_multiplexed_async_connection()
-            .await
-            .expect("Failed to connect to redis");
+    /// Starts without a connection, in degraded mode until connected in the
+    /// background, so the server starts even if Redis is down.
+    pub fn new() -> Self {
+        Self::with_url(&config::REDIS_URL)
+    }
+
+    pub(crate) fn with_url(url: &str) -> Self {
+        let redis = Self::disconnected(url);
+        redis.reconnecting.store(true, Ordering::SeqCst);
+
+        let background = redis.clone();
+        actix_rt::spawn(async move { background.reconnect().await });
+
+        redis
+    }
+
+    /// Connects right away, for tools and tests which can't do without Redis.
+    pub async fn connect() -> redis::RedisResult<Self> {
+        let redis = Self::disconnected(&config::REDIS_URL);
+        let (connection, driver) = redis.client.get_multiplexed_async_connection().await?;
 
         actix_rt::spawn(driver);
+        *redis.connection.write().unwrap() = Some(connection);
 
-        Self { client, connection }
+        Ok(redis)
+    }
+
+    fn disconnected(url: &str) -> Self {
+        let client = redis::Client::open(url).expect("Failed to create redis client");
+
+        Self {
+            client,
+            connection: Arc::new(RwLock::new(None)),
+            pending: Arc::new(Mutex::new(VecDeque::new())),
+            reconnecting: Arc::new(AtomicBool::new(false)),
+        }
     }
 
     pub async fn execute(&self, pipeline: redis::Pipeline) -> Result<(), crate::errors::Error> {
-        pipeline.query_async(&mut self.connection()).await?;
+        self.query(&pipeline).await.map_err(Into::into)
+    }
+
+    async fn query(&self, pipeline: &redis::Pipeline) -> redis::RedisResult<()> {
+        let result = match self.connection() {
+            Ok(mut connection) => pipeline.query_async(&mut connection).await,
+            Err(e) => Err(e),
+        };
+
+        if let Err(ref e) = result {
+            self.recover(e);
+        }
 
-        Ok(())
+        result
     }
 
-    pub fn connection(&self) -> RedisConnection {
-        self.connection.clone()
+    /// Executes an update that must not be lost, e.g. of tag counters.
+    ///
+    /// If Redis is unavailable the update is queued and the error is
+    /// returned. Queued updates are replayed in order once the connection is
+    /// restored, or before the next update.
+    pub async fn execute_or_queue(
+        &self,
+        pipeline: redis::Pipeline,
+    ) -> Result<(), crate::errors::Error> {
+        let result = match self.replay_pending().await {
+            Ok(()) => self.query(&pipeline).await,
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Err(ref e) if is_unavailable(e) => {
+                error!("Redis is unavailable, the update is queued: {}", e);
+                self.queue(pipeline);
+            }
+            _ => (),
+        }
+
+        result.map_err(Into::into)
     }
+
+    async fn replay_pending(&self) -> redis::RedisResult<()> {
+        loop {
+            let next = self.pending.lock().unwrap().pop_front();
+            let pipeline = match next {
+                Some(pipeline) => pipeline,
+                None => return Ok(()),
+            };
+
+            match self.query(&pipeline).await {
+                Err(e) if is_unavailable(&e) => {
+                    self.pending.lock().unwrap().push_front(pipeline);
+                    return Err(e);
+                }
+                // retrying it would not help
+                Err(e) => error!("Failed to replay a queued update: {}", e),
+                Ok(()) => (),
+            }
+        }
+    }
+
+    fn queue(&self, pipeline: redis::Pipeline) {
+        let mut pending = self.pending.lock().unwrap();
+
+        if pending.len() >= MAX_PENDING_UPDATES {
+            error!("Too many queued Redis updates, dropping the oldest one");
+            pending.pop_front();
+        }
+
+        pending.push_back(pipeline);
+    }
+
+    /// Reconnects in the background if the current connection is broken,
+    /// e.g. after Redis was restarted. There is a single reconnecting task at
+    /// a time however many requests fail meanwhile.
+    pub fn recover(&self, error: &redis::RedisError) {
+        if !is_unavailable(error) || self.reconnecting.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let redis = self.clone();
+        actix_rt::spawn(async move { redis.reconnect().await });
+    }
+
+    /// Retries with a growing delay until connected, then replays the queued
+    /// updates.
+    async fn reconnect(self) {
+        let mut delay = MIN_RECONNECT_DELAY;
+
+        loop {
+            match self.client.get_multiplexed_async_connection().await {
+                Ok((connection, driver)) => {
+                    actix_rt::spawn(driver);
+                    *self.connection.write().unwrap() = Some(connection);
+                    break;
+                }
+                Err(e) => error!("Failed to reconnect to redis: {}", e),
+            }
+
+            actix_rt::time::delay_for(delay).await;
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+
+        info!("Connected to redis");
+        // a failure while replaying starts reconnecting again
+        self.reconnecting.store(false, Ordering::SeqCst);
+
+        if let Err(e) = self.replay_pending().await {
+            error!("Failed to replay queued updates: {}", e);
+        }
+    }
+
+    /// Fails the same as a broken connection until connected.
+    pub fn connection(&self) -> redis::RedisResult<RedisConnection> {
+        self.connection.read().unwrap().clone().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotConnected, "Not connected to redis").into()
+        })
+    }
+}
+
+fn is_unavailable(error: &redis::RedisError) -> bool {
+    error.is_io_error() || error.is_connection_dropped() || error.is_timeout()
 }
 
 pub mod helpers;
+pub mod login_throttle;
+pub mod password_reset;
+pub mod tag_scores;
+pub mod token_denylist;
@@ -3,6 +3,8 @@ use crate::{config::user_tags_redis_key, errors::Error};
 use octo_budget_lib::auth_token::UserId;
 use redis::Pipeline;
 
+/// Like all updates of tag counters it is queued if Redis is unavailable, see
+/// `Redis::execute_or_queue`.
 pub async fn increment_tags(
     user_id: UserId,
     tags: Vec<String>,
@@ -16,7 +18,7 @@ pub async fn increment_tags(
         pipeline.cmd("zincrby").arg(&key).arg("1").arg(tag);
     }
 
-    redis.execute(pipeline).await
+    redis.execute_or_queue(pipeline).await
 }
 
 pub async fn decrement_tags(
@@ -32,21 +34,81 @@ pub async fn decrement_tags(
         pipeline.cmd("zincrby").arg(&key).arg("-1").arg(tag);
     }
 
-    pipeline.cmd("zremrangebyscore").arg(&key).arg("0").arg("0");
+    // scores rebuilt with a half-life are fractions, they can go below zero
+    pipeline
+        .cmd("zremrangebyscore")
+        .arg(&key)
+        .arg("-inf")
+        .arg("0");
 
-    redis.execute(pipeline).await
+    redis.execute_or_queue(pipeline).await
+}
+
+// ARGV[1] is the tag to move scores into, blank to just remove the tags.
+// Descendants are moved too, into descendants of ARGV[1], the same as in
+// `db::tags::replaced`.
+const MOVE_TAGS_SCRIPT: &str = "
+    local members = redis.call('zrange', KEYS[1], 0, -1, 'withscores')
+    for i = 1, #members, 2 do
+        local tag, score = members[i], members[i + 1]
+        for j = 2, #ARGV do
+            local source = ARGV[j]
+            if tag == source or string.sub(tag, 1, #source + 1) == source .. '/' then
+                if ARGV[1] ~= '' and tonumber(score) > 0 then
+                    redis.call('zincrby', KEYS[1], score, ARGV[1] .. string.sub(tag, #source + 1))
+                end
+                redis.call('zrem', KEYS[1], tag)
+                break
+            end
+        end
+    end
+";
+
+/// Adds scores of `tags` to the score of `into` and removes them, the same
+/// for their descendants. Without `into` the tags are just removed.
+pub async fn move_tags(
+    user_id: UserId,
+    tags: &[String],
+    into: Option<&str>,
+    redis: &Redis,
+) -> Result<(), Error> {
+    if tags.is_empty() {
+        return Ok(());
+    }
+
+    let mut pipeline = Pipeline::with_capacity(1);
+
+    // a script, unlike reading scores first, can be queued as any other update
+    pipeline
+        .cmd("eval")
+        .arg(MOVE_TAGS_SCRIPT)
+        .arg(1)
+        .arg(user_tags_redis_key(user_id))
+        .arg(into.unwrap_or_default())
+        .arg(tags)
+        .ignore();
+
+    redis.execute_or_queue(pipeline).await
 }
 
 pub async fn read_redis_tags(user_id: UserId, redis: &Redis) -> Result<Vec<String>, Error> {
     let redis_key = user_tags_redis_key(user_id);
 
-    redis::cmd("zrevrange")
+    let query = redis::cmd("zrevrange")
         .arg(redis_key)
         .arg("0")
         .arg("-1")
-        .query_async(&mut redis.connection())
-        .await
-        .map_err(Into::into)
+        .to_owned();
+    let result = match redis.connection() {
+        Ok(mut connection) => query.query_async(&mut connection).await,
+        Err(e) => Err(e),
+    };
+
+    if let Err(ref e) = result {
+        redis.recover(e);
+    }
+
+    result.map_err(Into::into)
 }
 
 #[cfg(test)]
@@ -63,10 +125,10 @@ mod tests {
 
         impl Session {
             pub async fn new() -> Self {
-                let redis = crate::redis::Redis::new().await;
+                let redis = Redis::connect().await.expect("Failed to connect to redis");
 
                 let _: () = redis::cmd("flushall")
-                    .query_async(&mut redis.connection())
+                    .query_async(&mut redis.connection().unwrap())
                     .await
                     .expect("failed to cleanup redis");
 
@@ -91,7 +153,7 @@ mod tests {
             }
 
             pub fn conn(&mut self) -> RedisConnection {
-                self.0.connection()
+                self.0.connection().expect("Not connected to redis")
             }
 
             pub fn redis(&self) -> &Redis {
@@ -250,6 +312,91 @@ mod tests {
         assert_eq!(vec!["xxx"], tags);
     }
 
+    #[actix_rt::test]
+    async fn decrement_tags_deletes_negative_scores() {
+        let mut session = test_redis::Session::new().await;
+        let user_id = "1";
+
+        session.zadd(user_id, "0.5", "xxx").await;
+        session.zadd(user_id, "1.5", "foo").await;
+
+        decrement_tags(user_id_1(), tags_vec!["xxx", "foo"], session.redis())
+            .await
+            .expect("failed to decrement");
+
+        let tags = read_redis_tags(user_id_1(), session.redis())
+            .await
+            .expect("failed to get tags");
+        assert_eq!(vec!["foo"], tags);
+    }
+
+    #[actix_rt::test]
+    async fn move_tags_into_other_tag() {
+        let mut session = test_redis::Session::new().await;
+        let user_id = "1";
+
+        session.zadd(user_id, "2", "cafe").await;
+        session.zadd(user_id, "3", "car").await;
+        session.zadd(user_id, "2", "food").await;
+
+        move_tags(
+            user_id_1(),
+            &tags_vec!["cafe", "food", "unknown"],
+            Some("eating out"),
+            session.redis(),
+        )
+        .await
+        .expect("failed to move tags");
+
+        let tags = read_redis_tags(user_id_1(), session.redis())
+            .await
+            .expect("failed to get tags");
+        assert_eq!(vec!["eating out", "car"], tags);
+    }
+
+    #[actix_rt::test]
+    async fn move_tags_with_descendants() {
+        let mut session = test_redis::Session::new().await;
+        let user_id = "1";
+
+        session.zadd(user_id, "2", "food").await;
+        session.zadd(user_id, "5", "food/cafe").await;
+        session.zadd(user_id, "1", "meals/cafe").await;
+        session.zadd(user_id, "3", "foodstuff").await;
+
+        move_tags(
+            user_id_1(),
+            &tags_vec!["food"],
+            Some("meals"),
+            session.redis(),
+        )
+        .await
+        .expect("failed to move tags");
+
+        let tags = read_redis_tags(user_id_1(), session.redis())
+            .await
+            .expect("failed to get tags");
+        assert_eq!(vec!["meals/cafe", "foodstuff", "meals"], tags);
+    }
+
+    #[actix_rt::test]
+    async fn move_tags_without_target_removes_them() {
+        let mut session = test_redis::Session::new().await;
+        let user_id = "1";
+
+        session.zadd(user_id, "2", "cafe").await;
+        session.zadd(user_id, "3", "car").await;
+
+        move_tags(user_id_1(), &tags_vec!["car"], None, session.redis())
+            .await
+            .expect("failed to move tags");
+
+        let tags = read_redis_tags(user_id_1(), session.redis())
+            .await
+            .expect("failed to get tags");
+        assert_eq!(vec!["cafe"], tags);
+    }
+
     fn user_id_1() -> UserId {
         1.into()
     }
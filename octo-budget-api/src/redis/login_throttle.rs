@@ -0,0 +1,269 @@
+use actix_web::{
+    error::ResponseError,
+    http::{header, StatusCode},
+    HttpRequest, HttpResponse,
+};
+use failure::Fail;
+use log::error;
+use serde_json::json;
+use std::net::IpAddr;
+
+use super::Redis;
+use crate::config::{
+    is_behind_trusted_proxy, login_failures_redis_key, login_lock_redis_key, LOGIN_BASE_DELAY_SECS,
+    LOGIN_FAILURES_TTL_SECS, LOGIN_IP_ATTEMPTS, LOGIN_LOCKOUT_SECS, LOGIN_USERNAME_ATTEMPTS,
+    PASSWORD_RESET_ATTEMPTS,
+};
+
+const X_FORWARDED_FOR: &str = "X-Forwarded-For";
+
+/// Returned instead of checking credentials while a login is throttled.
+#[derive(Debug, Fail, PartialEq)]
+#[fail(display = "Login is throttled for {} seconds", retry_after)]
+pub struct Throttled {
+    retry_after: u64,
+}
+
+impl ResponseError for Throttled {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::TOO_MANY_REQUESTS
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::TooManyRequests()
+            .header(header::RETRY_AFTER, self.retry_after.to_string())
+            .json(json!({
+                "detail": format!(
+                    "Request was throttled. Expected available in {} seconds.",
+                    self.retry_after
+                )
+            }))
+    }
+}
+
+struct Subject {
+    scope: &'static str,
+    id: String,
+    attempts: u32,
+}
+
+impl Subject {
+    fn failures_key(&self) -> String {
+        login_failures_redis_key(self.scope, &self.id)
+    }
+
+    fn lock_key(&self) -> String {
+        login_lock_redis_key(self.scope, &self.id)
+    }
+}
+
+/// Slows down password guessing for a username and for the client IP address.
+///
+/// After the allowed number of failed logins every further failure locks the
+/// login for twice as long as the previous one, up to the lockout. Like the
+/// token denylist it fails open: a broken Redis must not stop everybody from
+/// logging in.
+pub struct LoginThrottle {
+    subjects: Vec<Subject>,
+}
+
+impl LoginThrottle {
+    pub fn new(username: &str, req: &HttpRequest) -> Self {
+        let mut subjects = vec![Subject {
+            scope: "username",
+            id: username.to_lowercase(),
+            attempts: *LOGIN_USERNAME_ATTEMPTS,
+        }];
+
+        if let Some(ip) = client_ip(req, is_behind_trusted_proxy()) {
+            subjects.push(Subject {
+                scope: "ip",
+                id: ip,
+                attempts: *LOGIN_IP_ATTEMPTS,
+            });
+        }
+
+        Self { subjects }
+    }
+
+    /// Limits password reset emails to an address. Every reset request counts
+    /// as a failure, whether the address is registered or not.
+    pub fn password_reset(email: &str) -> Self {
+        Self {
+            subjects: vec![Subject {
+                scope: "reset_email",
+                id: email.to_lowercase(),
+                attempts: *PASSWORD_RESET_ATTEMPTS,
+            }],
+        }
+    }
+
+    pub async fn check(&self, redis: &Redis) -> Result<(), Throttled> {
+        let mut pipeline = redis::pipe();
+        for subject in &self.subjects {
+            pipeline.cmd("ttl").arg(subject.lock_key());
+        }
+
+        let result = match redis.connection() {
+            Ok(mut connection) => pipeline.query_async(&mut connection).await,
+            Err(e) => Err(e),
+        };
+
+        let ttls: Vec<i64> = match result {
+            Ok(ttls) => ttls,
+            Err(e) => {
+                error!("Failed to check login throttling: {}", e);
+                return Ok(());
+            }
+        };
+
+        match ttls.into_iter().max() {
+            Some(ttl) if ttl > 0 => Err(Throttled {
+                retry_after: ttl as u64,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    pub async fn failed(&self, redis: &Redis) {
+        let mut pipeline = redis::pipe();
+        for subject in &self.subjects {
+            pipeline
+                .cmd("incr")
+                .arg(subject.failures_key())
+                .cmd("expire")
+                .arg(subject.failures_key())
+                .arg(*LOGIN_FAILURES_TTL_SECS)
+                .ignore();
+        }
+
+        let result = match redis.connection() {
+            Ok(mut connection) => pipeline.query_async(&mut connection).await,
+            Err(e) => Err(e),
+        };
+
+        let failures: Vec<u32> = match result {
+            Ok(failures) => failures,
+            Err(e) => {
+                error!("Failed to count failed login: {}", e);
+                return;
+            }
+        };
+
+        let mut pipeline = redis::pipe();
+        for (subject, failures) in self.subjects.iter().zip(failures) {
+            let delay = lock_delay(
+                failures,
+                subject.attempts,
+                *LOGIN_BASE_DELAY_SECS,
+                *LOGIN_LOCKOUT_SECS,
+            );
+
+            if let Some(delay) = delay {
+                pipeline
+                    .cmd("set")
+                    .arg(subject.lock_key())
+                    .arg("1")
+                    .arg("ex")
+                    .arg(delay)
+                    .ignore();
+            }
+        }
+
+        if let Err(e) = redis.execute(pipeline).await {
+            error!("Failed to throttle login: {}", e);
+        }
+    }
+
+    /// Forgets failures of the username. Failures of the IP address are kept,
+    /// otherwise one known password would let it guess others without limit.
+    pub async fn succeeded(&self, redis: &Redis) {
+        let username = &self.subjects[0];
+        let query = redis::cmd("del")
+            .arg(username.failures_key())
+            .arg(username.lock_key())
+            .to_owned();
+        let result: Result<(), _> = match redis.connection() {
+            Ok(mut connection) => query.query_async(&mut connection).await,
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = result {
+            error!("Failed to reset login throttling: {}", e);
+        }
+    }
+}
+
+/// Address of the client. Only the last `X-Forwarded-For` entry is the one the
+/// trusted proxy appended, earlier ones are sent by the client and can be
+/// anything, so without such a proxy the header is not used at all.
+fn client_ip(req: &HttpRequest, trusted_proxy: bool) -> Option<String> {
+    let forwarded = req
+        .headers()
+        .get(X_FORWARDED_FOR)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next())
+        .and_then(|ip| ip.trim().parse::<IpAddr>().ok())
+        .filter(|_| trusted_proxy);
+
+    forwarded
+        .or_else(|| req.peer_addr().map(|addr| addr.ip()))
+        .map(|ip| ip.to_string())
+}
+
+/// Seconds to lock the login for after `failures` failed attempts, if any.
+fn lock_delay(failures: u32, attempts: u32, base_delay: u64, lockout: u64) -> Option<u64> {
+    let extra = failures.checked_sub(attempts).filter(|extra| *extra > 0)?;
+    let delay = base_delay.saturating_mul(2u64.saturating_pow(extra - 1));
+
+    Some(delay.min(lockout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    fn request(forwarded_for: Option<&str>) -> HttpRequest {
+        let request = TestRequest::default().peer_addr("10.0.0.1:43210".parse().unwrap());
+
+        match forwarded_for {
+            Some(value) => request.header(X_FORWARDED_FOR, value),
+            None => request,
+        }
+        .to_http_request()
+    }
+
+    #[test]
+    fn client_ip_is_peer_address_without_trusted_proxy() {
+        let ip = client_ip(&request(Some("1.2.3.4")), false);
+
+        assert_eq!(Some("10.0.0.1".to_string()), ip);
+    }
+
+    #[test]
+    fn client_ip_is_appended_by_trusted_proxy() {
+        let ip = |forwarded_for| client_ip(&request(forwarded_for), true);
+
+        assert_eq!(Some("5.6.7.8".to_string()), ip(Some("1.2.3.4, 5.6.7.8")));
+        assert_eq!(Some("5.6.7.8".to_string()), ip(Some("5.6.7.8")));
+        assert_eq!(Some("10.0.0.1".to_string()), ip(Some("1.2.3.4, nonsense")));
+        assert_eq!(Some("10.0.0.1".to_string()), ip(None));
+    }
+
+    #[test]
+    fn no_delay_for_allowed_attempts() {
+        assert_eq!(None, lock_delay(0, 5, 1, 900));
+        assert_eq!(None, lock_delay(5, 5, 1, 900));
+    }
+
+    #[test]
+    fn delay_doubles_up_to_lockout() {
+        let delays: Vec<_> = (6..=16)
+            .map(|n| lock_delay(n, 5, 1, 900).unwrap())
+            .collect();
+
+        assert_eq!(vec![1, 2, 4, 8, 16, 32, 64, 128, 256, 512, 900], delays);
+        assert_eq!(Some(900), lock_delay(u32::MAX, 5, 1, 900));
+    }
+}
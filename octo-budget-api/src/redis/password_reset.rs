@@ -0,0 +1,41 @@
+use super::Redis;
+use crate::config::password_reset_redis_key;
+use crate::db::refresh_tokens::{generate, hash};
+use crate::errors::Error;
+
+/// How long a password reset link stays valid.
+const EXPIRE_IN_SECS: usize = 60 * 60;
+
+/// Stores a new reset token of the user and returns it in plain text.
+pub async fn issue_reset_token(user_id: i32, redis: &Redis) -> Result<String, Error> {
+    let token = generate();
+
+    let _: () = redis::cmd("set")
+        .arg(password_reset_redis_key(&hash(&token)))
+        .arg(user_id)
+        .arg("ex")
+        .arg(EXPIRE_IN_SECS)
+        .query_async(&mut redis.connection()?)
+        .await?;
+
+    Ok(token)
+}
+
+/// The user the token was issued for, unless it is expired or used.
+pub async fn find_reset_token(token: &str, redis: &Redis) -> Result<Option<i32>, Error> {
+    redis::cmd("get")
+        .arg(password_reset_redis_key(&hash(token)))
+        .query_async(&mut redis.connection()?)
+        .await
+        .map_err(Into::into)
+}
+
+/// Makes sure the token is used only once, `false` if somebody was faster.
+pub async fn consume_reset_token(token: &str, redis: &Redis) -> Result<bool, Error> {
+    let deleted: i32 = redis::cmd("del")
+        .arg(password_reset_redis_key(&hash(token)))
+        .query_async(&mut redis.connection()?)
+        .await?;
+
+    Ok(deleted == 1)
+}
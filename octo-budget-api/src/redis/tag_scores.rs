@@ -0,0 +1,65 @@
+use redis::Pipeline;
+
+use super::Redis;
+use crate::config::{user_tags_rebuild_redis_key, user_tags_redis_key};
+use crate::db::{
+    queries::{GetTagScores, UserTagScores},
+    ConnectionPool,
+};
+use crate::errors::Error;
+
+/// Recomputes the scores of tags from records, for a single user or for
+/// everybody, and returns the number of users whose scores were rebuilt.
+///
+/// Scores are written into a temporary key first and then renamed over the
+/// current one, so readers never see a half-built set.
+///
+/// With a half-life the scores are the weights of uses at the time of the
+/// rebuild. Later uses are still counted with `increment_tags` as 1, which is
+/// the weight of a use made now, so the order is right until the scores age
+/// and the rebuild has to be run again. `decrement_tags` takes 1 off even for
+/// old uses, tags whose score drops to zero or below are removed.
+pub async fn rebuild(
+    user_id: Option<i32>,
+    half_life_days: Option<f64>,
+    pool: &ConnectionPool,
+    redis: &Redis,
+) -> Result<usize, Error> {
+    let users = pool
+        .execute(GetTagScores {
+            user_id,
+            half_life_days,
+        })
+        .await?;
+
+    for user in &users {
+        replace_scores(user, redis).await?;
+    }
+
+    Ok(users.len())
+}
+
+async fn replace_scores(user: &UserTagScores, redis: &Redis) -> Result<(), Error> {
+    let key = user_tags_redis_key(user.user_id);
+    let tmp_key = user_tags_rebuild_redis_key(user.user_id);
+
+    let mut pipeline = Pipeline::with_capacity(3);
+    pipeline.atomic().cmd("del").arg(&tmp_key).ignore();
+
+    if user.scores.is_empty() {
+        pipeline.cmd("del").arg(&key).ignore();
+    } else {
+        pipeline.cmd("zadd").arg(&tmp_key);
+        for (tag, score) in &user.scores {
+            pipeline.arg(*score).arg(tag);
+        }
+        pipeline
+            .ignore()
+            .cmd("rename")
+            .arg(&tmp_key)
+            .arg(&key)
+            .ignore();
+    }
+
+    redis.execute(pipeline).await
+}
@@ -0,0 +1,98 @@
+use futures::Future;
+use log::error;
+use octo_budget_lib::auth_token::{AuthToken, Revocation, UserId};
+use std::pin::Pin;
+
+use super::Redis;
+use crate::config::{revoked_token_redis_key, token_generation_redis_key};
+use crate::errors::Error;
+
+/// Access tokens revoked before they expire.
+///
+/// Single tokens are denylisted by `jti` until they would have expired
+/// anyway. Logging out of all sessions bumps the user's token generation, so
+/// every token issued before that is rejected.
+pub struct TokenDenylist(Redis);
+
+impl TokenDenylist {
+    pub fn new(redis: Redis) -> Self {
+        Self(redis)
+    }
+}
+
+impl Revocation for TokenDenylist {
+    fn is_revoked(&self, token: &AuthToken) -> Pin<Box<dyn Future<Output = bool>>> {
+        let connection = self.0.connection();
+        let query = redis::cmd("mget")
+            .arg(revoked_token_redis_key(token.jti()))
+            .arg(token_generation_redis_key(token.user_id()))
+            .to_owned();
+        let generation = token.generation();
+
+        Box::pin(async move {
+            let result: Result<(Option<String>, Option<i64>), _> = match connection {
+                Ok(mut connection) => query.query_async(&mut connection).await,
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok((denylisted, current_generation)) => {
+                    denylisted.is_some() || generation < current_generation.unwrap_or_default()
+                }
+                // an unreachable denylist must not lock everybody out
+                Err(e) => {
+                    error!("Failed to check token revocation: {}", e);
+                    false
+                }
+            }
+        })
+    }
+}
+
+pub async fn revoke_token(token: &AuthToken, redis: &Redis) -> Result<(), Error> {
+    let ttl = token.expires_in_secs();
+
+    // tokens issued before `jti` was introduced can only be revoked all at once
+    if token.jti().is_empty() || ttl <= 0 {
+        return Ok(());
+    }
+
+    redis::cmd("set")
+        .arg(revoked_token_redis_key(token.jti()))
+        .arg("1")
+        .arg("ex")
+        .arg(ttl)
+        .query_async(&mut redis.connection()?)
+        .await
+        .map_err(Into::into)
+}
+
+pub async fn revoke_all_tokens(user_id: UserId, redis: &Redis) -> Result<(), Error> {
+    let _: i64 = redis::cmd("incr")
+        .arg(token_generation_redis_key(user_id))
+        .query_async(&mut redis.connection()?)
+        .await?;
+
+    Ok(())
+}
+
+/// Generation new tokens of the user are issued with. It is the initial one
+/// if the denylist is unreachable, so users can still log in, the same as
+/// revoked tokens are accepted then.
+pub async fn token_generation(user_id: UserId, redis: &Redis) -> i64 {
+    let query = redis::cmd("get")
+        .arg(token_generation_redis_key(user_id))
+        .to_owned();
+    let result: Result<Option<i64>, _> = match redis.connection() {
+        Ok(mut connection) => query.query_async(&mut connection).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(generation) => generation.unwrap_or_default(),
+        Err(e) => {
+            error!("Failed to get token generation: {}", e);
+            0
+        }
+    }
+}
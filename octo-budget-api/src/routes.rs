@@ -10,8 +10,23 @@ pub fn init_routes(cfg: &mut web::ServiceConfig) {
                 .service(actix_files::Files::new("/", "./reactapp/build")),
         )
         .service(web::scope("/auth/jwt").service(apps::AuthService))
+        .service(
+            web::scope("/auth")
+                .service(apps::RegistrationService)
+                .service(apps::PasswordResetService),
+        )
         .service(web::scope("/api/tags").service(apps::TagsService))
-        .service(web::scope("/api/user").service(apps::users_app::show))
+        .service(
+            web::scope("/api/user")
+                .service(apps::UsersService)
+                .service(apps::PasswordService)
+                .service(apps::TokensService),
+        )
         .service(web::scope("/api/records").service(apps::RecordsService))
-        .service(web::scope("/api/budgets").service(apps::BudgetsService));
+        .service(web::scope("/api/reports").service(apps::ReportsService))
+        .service(
+            web::scope("/api/budgets")
+                .service(apps::BudgetsService)
+                .service(apps::YearBudgetsService),
+        );
 }
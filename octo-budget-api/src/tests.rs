@@ -22,16 +22,47 @@ impl RequestJwtAuthExt for TestRequest {
     }
 }
 
+pub trait RequestTokenAuthExt {
+    fn token_auth(self, token: &str) -> Self;
+}
+
+impl RequestTokenAuthExt for TestRequest {
+    fn token_auth(self, token: &str) -> Self {
+        self.header(
+            actix_web::http::header::AUTHORIZATION,
+            format!("Token {}", token),
+        )
+    }
+}
+
 #[macro_export]
 macro_rules! await_test_server {
     ($service:ident) => {{
+        let redis = crate::redis::Redis::connect()
+            .await
+            .expect("Failed to connect to redis");
+
+        await_test_server!($service, redis)
+    }};
+    ($service:ident, $redis:expr) => {{
+        let pool = crate::db::ConnectionPool::new();
+        let redis = $redis;
+        let mailer: std::sync::Arc<dyn crate::mailer::Mailer> =
+            std::sync::Arc::new(crate::tests::outbox());
+
         actix_web::test::init_service(
             actix_web::App::new()
-                .data(crate::db::ConnectionPool::new())
-                .data(crate::redis::Redis::new().await)
-                .app_data(octo_budget_lib::auth_token::ApiJwtTokenAuthConfig::new(
-                    crate::config::AUTH_TOKEN_SECRET.as_bytes(),
-                ))
+                .data(pool.clone())
+                .data(redis.clone())
+                .data(mailer)
+                .app_data(
+                    octo_budget_lib::auth_token::ApiJwtTokenAuthConfig::new(
+                        crate::config::AUTH_TOKEN_SECRET.as_bytes(),
+                    )
+                    .revocation(crate::redis::token_denylist::TokenDenylist::new(redis))
+                    .revocation(crate::db::inactive_users::InactiveUsers::new(pool.clone()))
+                    .personal_tokens(crate::db::personal_tokens::PersonalTokens::new(pool)),
+                )
                 .service($service),
         )
         .await
@@ -52,6 +83,13 @@ macro_rules! tags_vec {
     };
 }
 
+/// Emails sent by the test server end up here.
+pub fn outbox() -> crate::mailer::Outbox {
+    let dir = std::env::temp_dir().join("octo-budget-api-outbox");
+
+    crate::mailer::Outbox::new(Some(dir))
+}
+
 pub fn setup_env() {
     use dotenv::dotenv;
 
@@ -1,10 +1,10 @@
 use bigdecimal::BigDecimal;
-use chrono::naive::NaiveDateTime;
+use chrono::naive::{NaiveDate, NaiveDateTime};
 use diesel::*;
 
 use crate::db::{
     builders::UserBuilder,
-    models::{AuthUser, Budget, Record},
+    models::{AuthUser, Budget, Record, UserSettings, YearBudget},
     ConnectionPool, PooledConnection,
 };
 
@@ -37,13 +37,36 @@ impl DbSession {
         &self.pooled_conn
     }
 
-    // pub fn count_records(&self) -> i64 {
-    //     use crate::db::schema::records_record::table as records;
-    //
-    //     records.count().first(&self.pooled_conn).unwrap()
-    // }
+    pub fn count_records(&self) -> i64 {
+        use crate::db::schema::records_record::table as records;
+
+        records.count().first(&self.pooled_conn).unwrap()
+    }
+
+    pub fn set_user_settings(&self, settings: UserSettings) {
+        use crate::db::schema::user_settings;
+
+        insert_into(user_settings::table)
+            .values(&settings)
+            .execute(&self.pooled_conn)
+            .unwrap();
+    }
 
-    pub fn create_budget(&mut self, budget: Budget) {
+    pub fn create_exchange_rate(&self, on: NaiveDate, base: &str, quote: &str, rate: f64) {
+        use crate::db::{models::NewExchangeRate, schema::exchange_rates};
+
+        insert_into(exchange_rates::table)
+            .values(NewExchangeRate {
+                date: on,
+                base: base.to_string(),
+                quote: quote.to_string(),
+                rate: BigDecimal::from(rate),
+            })
+            .execute(&self.pooled_conn)
+            .unwrap();
+    }
+
+    pub fn create_budget(&mut self, budget: Budget) -> Budget {
         use crate::db::schema::budgets_budget::dsl::*;
 
         insert_into(budgets_budget)
@@ -55,9 +78,58 @@ impl DbSession {
                 tags.eq(budget.tags),
                 tags_type.eq(budget.tags_type),
                 user_id.eq(budget.user_id),
+                comment.eq(budget.comment),
             ))
             .get_result::<Budget>(&self.pooled_conn)
-            .unwrap();
+            .unwrap()
+    }
+
+    pub fn find_budget(&self, budget_id: i32) -> Budget {
+        use crate::db::schema::budgets_budget::table as budgets;
+
+        budgets
+            .find(budget_id)
+            .first(&self.pooled_conn)
+            .expect("failed to find budget")
+    }
+
+    pub fn count_budgets(&self) -> i64 {
+        use crate::db::schema::budgets_budget::table as budgets;
+
+        budgets.count().first(&self.pooled_conn).unwrap()
+    }
+
+    pub fn create_year_budget(&mut self, budget: YearBudget) -> YearBudget {
+        use crate::db::schema::budgets_yearbudget::dsl::*;
+
+        insert_into(budgets_yearbudget)
+            .values((
+                name.eq(budget.name),
+                amount.eq(budget.amount),
+                amount_currency.eq(budget.amount_currency),
+                year.eq(budget.year),
+                tags.eq(budget.tags),
+                tags_type.eq(budget.tags_type),
+                user_id.eq(budget.user_id),
+                comment.eq(budget.comment),
+            ))
+            .get_result::<YearBudget>(&self.pooled_conn)
+            .unwrap()
+    }
+
+    pub fn find_year_budget(&self, budget_id: i32) -> YearBudget {
+        use crate::db::schema::budgets_yearbudget::table as budgets;
+
+        budgets
+            .find(budget_id)
+            .first(&self.pooled_conn)
+            .expect("failed to find year budget")
+    }
+
+    pub fn count_year_budgets(&self) -> i64 {
+        use crate::db::schema::budgets_yearbudget::table as budgets;
+
+        budgets.count().first(&self.pooled_conn).unwrap()
     }
 
     pub fn create_record2(&self, id_of_the_user: i32) -> Record {
@@ -78,7 +150,7 @@ impl DbSession {
             .unwrap()
     }
 
-    pub fn create_record(&mut self, record: Record) {
+    pub fn create_record(&mut self, record: Record) -> Record {
         use crate::db::schema::records_record::dsl::*;
         use diesel::*;
 
@@ -90,9 +162,10 @@ impl DbSession {
                 tags.eq(record.tags),
                 transaction_type.eq(record.transaction_type),
                 user_id.eq(record.user_id),
+                comment.eq(record.comment),
             ))
             .get_result::<Record>(&self.pooled_conn)
-            .unwrap();
+            .unwrap()
     }
 
     pub fn create_records2(&self, id_of_the_user: i32, count: usize) -> Vec<Record> {
@@ -151,14 +224,30 @@ impl DbSession {
         }
     }
 
+    pub fn find_user_by_name(&self, name: &str) -> AuthUser {
+        use crate::db::schema::auth_user::dsl::*;
+
+        auth_user
+            .filter(username.eq(name))
+            .first(&self.pooled_conn)
+            .expect("Failed to find user")
+    }
+
+    pub fn count_users(&self) -> i64 {
+        use crate::db::schema::auth_user::table as users;
+
+        users.count().first(&self.pooled_conn).unwrap()
+    }
+
     pub fn create_user(&self, builder: UserBuilder) -> AuthUser {
         use crate::db::schema::auth_user::dsl::*;
         use diesel::*;
 
+        let user_tags = builder.tags.clone();
         let user = builder.finish();
         let new_password = djangohashers::make_password(&user.password);
 
-        insert_into(auth_user)
+        let user: AuthUser = insert_into(auth_user)
             .values((
                 username.eq(user.username),
                 password.eq(&new_password),
@@ -169,10 +258,17 @@ impl DbSession {
                 first_name.eq(user.first_name),
                 last_name.eq(user.last_name),
                 date_joined.eq(user.date_joined),
-                tags.eq(user.tags),
             ))
             .get_result(&self.pooled_conn)
-            .unwrap()
+            .unwrap();
+
+        crate::db::tags::save_user_tags(user.id, &user_tags, &self.pooled_conn).unwrap();
+
+        user
+    }
+
+    pub fn user_tags(&self, owner_id: i32) -> Vec<String> {
+        crate::db::tags::load_user_tags(owner_id, &self.pooled_conn).unwrap()
     }
 }
 
@@ -182,7 +278,15 @@ impl Drop for DbSession {
             return;
         }
 
-        for table_name in ["auth_user", "records_record", "budgets_budget"].iter() {
+        for table_name in [
+            "auth_user",
+            "records_record",
+            "budgets_budget",
+            "budgets_yearbudget",
+            "exchange_rates",
+        ]
+        .iter()
+        {
             self.pooled_conn
                 .execute(&format!("TRUNCATE TABLE {} CASCADE", table_name))
                 .expect("Error executing TRUNCATE");
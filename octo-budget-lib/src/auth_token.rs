@@ -1,15 +1,17 @@
 use actix_http::Payload;
 use actix_web::{
-    error::{ErrorUnauthorized, ParseError},
+    error::{ErrorForbidden, ErrorUnauthorized, ParseError},
+    http::Method,
     FromRequest, HttpRequest,
 };
 use failure::Error;
 use futures::future::{err, ok, Future};
 use log::error;
 use serde_derive::{Deserialize, Serialize};
-use std::{fmt, pin::Pin};
+use std::{fmt, pin::Pin, rc::Rc};
 
 const DEFAULT_EXPIRE_IN_HOURS: i64 = 24;
+const JTI_LENGTH: usize = 32;
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Copy, Clone)]
 pub struct UserId(i32);
@@ -47,12 +49,15 @@ impl PartialEq<UserId> for i32 {
 }
 
 impl UserId {
-    fn auth(req: &HttpRequest) -> actix_web::Result<Self> {
-        let config = req.app_data::<ApiJwtTokenAuthConfig>().ok_or_else(|| {
+    fn config(req: &HttpRequest) -> actix_web::Result<&ApiJwtTokenAuthConfig> {
+        req.app_data::<ApiJwtTokenAuthConfig>().ok_or_else(|| {
             error!("Application is not configured with JWT secret!");
             ErrorUnauthorized(ParseError::Header)
-        })?; // TODO: add beter error
+        }) // TODO: add beter error
+    }
 
+    /// Scheme and the token itself from the `Authorization` header.
+    fn credentials(req: &HttpRequest) -> actix_web::Result<(&str, &str)> {
         let auth_header = req
             .headers()
             .get(actix_web::http::header::AUTHORIZATION)
@@ -62,29 +67,116 @@ impl UserId {
 
         let mut parts = auth_header.split_whitespace();
 
-        if let Some(token_type) = parts.next() {
-            if token_type != "JWT" {
-                return Err(ErrorUnauthorized("Wrong token type!"));
-            }
+        match (parts.next(), parts.next()) {
+            (Some(scheme), Some(token)) => Ok((scheme, token)),
+            _ => Err(ErrorUnauthorized("Wrong token format!")),
         }
+    }
+
+    fn auth(req: &HttpRequest) -> actix_web::Result<(AuthToken, Vec<Rc<dyn Revocation>>)> {
+        let config = Self::config(req)?;
 
-        match parts.next() {
-            Some(token) => AuthToken::from(token, config.secret)
-                .map(|auth_token| auth_token.user_id())
+        match Self::credentials(req)? {
+            ("JWT", token) => AuthToken::from(token, config.secret)
+                .map(|auth_token| (auth_token, config.revocations.clone()))
                 .map_err(|_| ErrorUnauthorized("Bad token!")),
-            None => Err(ErrorUnauthorized("Wrong token format!")),
+            _ => Err(ErrorUnauthorized("Wrong token type!")),
+        }
+    }
+
+    fn personal_token_auth(
+        req: &HttpRequest,
+        token: String,
+    ) -> Pin<Box<dyn Future<Output = actix_web::Result<Self>>>> {
+        let tokens = match Self::config(req).map(|config| config.personal_tokens.clone()) {
+            Ok(Some(tokens)) => tokens,
+            Ok(None) => return Box::pin(err(ErrorUnauthorized("Wrong token type!"))),
+            Err(e) => return Box::pin(err(e)),
+        };
+        let method = req.method().clone();
+
+        Box::pin(async move {
+            match tokens.authenticate(&token).await {
+                Some(grant) if grant.scope.allows(&method) => Ok(grant.user_id),
+                Some(_) => Err(ErrorForbidden("Token is read-only!")),
+                None => Err(ErrorUnauthorized("Bad token!")),
+            }
+        })
+    }
+}
+
+/// Tells whether a token with a valid signature was revoked before it expired,
+/// e.g. because the user logged out.
+pub trait Revocation {
+    fn is_revoked(&self, token: &AuthToken) -> Pin<Box<dyn Future<Output = bool>>>;
+}
+
+/// What a personal access token is allowed to do.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Scope {
+    /// only safe methods, e.g. `GET`
+    Read,
+    ReadWrite,
+}
+
+impl Scope {
+    pub fn allows(self, method: &Method) -> bool {
+        match self {
+            Scope::Read => method.is_safe(),
+            Scope::ReadWrite => true,
         }
     }
 }
 
-#[derive(Default, Debug)]
+/// The owner of a personal access token and what the token allows.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct TokenGrant {
+    pub user_id: UserId,
+    pub scope: Scope,
+}
+
+/// Looks up long-lived tokens sent with the `Token` scheme. Unlike JWT they
+/// are opaque, so only the application that stored them can check them.
+pub trait PersonalTokens {
+    fn authenticate(&self, token: &str) -> Pin<Box<dyn Future<Output = Option<TokenGrant>>>>;
+}
+
+#[derive(Default)]
 pub struct ApiJwtTokenAuthConfig {
     secret: &'static [u8],
+    revocations: Vec<Rc<dyn Revocation>>,
+    personal_tokens: Option<Rc<dyn PersonalTokens>>,
+}
+
+impl fmt::Debug for ApiJwtTokenAuthConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiJwtTokenAuthConfig")
+            .field("revocations", &self.revocations.len())
+            .field("personal_tokens", &self.personal_tokens.is_some())
+            .finish()
+    }
 }
 
 impl ApiJwtTokenAuthConfig {
     pub fn new(secret: &'static [u8]) -> Self {
-        Self { secret }
+        Self {
+            secret,
+            revocations: Vec::new(),
+            personal_tokens: None,
+        }
+    }
+
+    /// Adds a check, a token is rejected if any of them revokes it.
+    pub fn revocation(mut self, revocation: impl Revocation + 'static) -> Self {
+        self.revocations.push(Rc::new(revocation));
+        self
+    }
+
+    /// Enables the `Token` scheme for `UserId`, `AuthToken` still accepts
+    /// only JWT.
+    pub fn personal_tokens(mut self, tokens: impl PersonalTokens + 'static) -> Self {
+        self.personal_tokens = Some(Rc::new(tokens));
+        self
     }
 }
 
@@ -94,11 +186,36 @@ impl FromRequest for UserId {
     type Config = ApiJwtTokenAuthConfig;
 
     #[inline]
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        if let Ok(("Token", token)) = UserId::credentials(req) {
+            return UserId::personal_token_auth(req, token.to_string());
+        }
+
+        let token = AuthToken::from_request(req, payload);
+
+        Box::pin(async move { token.await.map(|token| token.user_id()) })
+    }
+}
+
+impl FromRequest for AuthToken {
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+    type Config = ApiJwtTokenAuthConfig;
+
     fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
-        Box::pin(match UserId::auth(req) {
-            Ok(user_id) => ok(user_id),
-            Err(e) => err(e),
-        })
+        match UserId::auth(req) {
+            Ok((token, revocations)) if revocations.is_empty() => Box::pin(ok(token)),
+            Ok((token, revocations)) => Box::pin(async move {
+                for revocation in revocations {
+                    if revocation.is_revoked(&token).await {
+                        return Err(ErrorUnauthorized("Token is revoked!"));
+                    }
+                }
+
+                Ok(token)
+            }),
+            Err(e) => Box::pin(err(e)),
+        }
     }
 }
 
@@ -106,16 +223,28 @@ impl FromRequest for UserId {
 pub struct AuthToken {
     user_id: UserId,
     expire_in_hours: i64,
+    jti: String,
+    generation: i64,
+    exp: Option<i64>,
 }
 
 impl AuthToken {
     pub fn new(user_id: i32) -> Self {
+        use rand::{distributions::Alphanumeric, thread_rng, Rng};
+
         let user_id = user_id.into();
         let expire_in_hours = DEFAULT_EXPIRE_IN_HOURS;
+        let jti = thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(JTI_LENGTH)
+            .collect();
 
         Self {
             user_id,
             expire_in_hours,
+            jti,
+            generation: 0,
+            exp: None,
         }
     }
 
@@ -123,6 +252,32 @@ impl AuthToken {
         self.user_id
     }
 
+    /// Unique id of the token, used to revoke it on logout.
+    pub fn jti(&self) -> &str {
+        &self.jti
+    }
+
+    /// Tokens of older generations are revoked when the user logs out of
+    /// all sessions.
+    pub fn generation(&self) -> i64 {
+        self.generation
+    }
+
+    /// Seconds left until the token expires.
+    pub fn expires_in_secs(&self) -> i64 {
+        use time::OffsetDateTime;
+
+        match self.exp {
+            Some(exp) => exp - OffsetDateTime::now_utc().unix_timestamp(),
+            None => self.expire_in_hours * 3600,
+        }
+    }
+
+    pub fn with_generation(mut self, generation: i64) -> Self {
+        self.generation = generation;
+        self
+    }
+
     pub fn encrypt(&self, secret: &[u8]) -> String {
         use jsonwebtoken::{encode, EncodingKey, Header};
 
@@ -142,10 +297,20 @@ impl AuthToken {
         use jsonwebtoken::{decode, DecodingKey, Validation};
 
         let secret = DecodingKey::from_secret(secret);
-        let token_data = decode::<Data>(token, &secret, &Validation::default())?;
-        let user_id = token_data.claims.user_id;
-
-        Ok(Self::new(user_id))
+        let Data {
+            user_id,
+            exp,
+            jti,
+            gen,
+        } = decode::<Data>(token, &secret, &Validation::default())?.claims;
+
+        Ok(Self {
+            user_id: user_id.into(),
+            expire_in_hours: DEFAULT_EXPIRE_IN_HOURS,
+            jti,
+            generation: gen,
+            exp: Some(exp),
+        })
     }
 
     pub fn data(&self) -> Data {
@@ -157,6 +322,8 @@ impl AuthToken {
         Data {
             exp,
             user_id: self.user_id.into(),
+            jti: self.jti.clone(),
+            gen: self.generation,
         }
     }
 }
@@ -165,6 +332,11 @@ impl AuthToken {
 pub struct Data {
     pub user_id: i32,
     pub exp: i64,
+    // tokens issued before logout was introduced have neither of these
+    #[serde(default)]
+    pub jti: String,
+    #[serde(default)]
+    pub gen: i64,
     // pub username: &'a str,
     // pub email: &'a str,
 }
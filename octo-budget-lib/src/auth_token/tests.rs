@@ -1,5 +1,5 @@
 use super::*;
-use jsonwebtoken::{decode, Validation, DecodingKey};
+use jsonwebtoken::{decode, DecodingKey, Validation};
 
 const TEST_SECRET: &[u8] = b"foo-bar-secret";
 const TEST_USER_ID: i32 = 112233;
@@ -11,7 +11,7 @@ fn test_dec_key() -> DecodingKey<'static> {
 #[test]
 fn create_token() {
     let token = AuthToken::new(TEST_USER_ID).encrypt(TEST_SECRET);
-    assert_eq!(128, token.len());
+    assert_eq!(193, token.len());
 
     let decoded = decode::<Data>(&token, &test_dec_key(), &Validation::default()).unwrap();
     assert_eq!(TEST_USER_ID, decoded.claims.user_id);
@@ -21,7 +21,12 @@ fn create_token() {
 #[should_panic(expected = "InvalidSignature")]
 fn create_token_with_invalid_secret() {
     let token = AuthToken::new(TEST_USER_ID).encrypt(TEST_SECRET);
-    decode::<Data>(&token, &DecodingKey::from_secret(b"wrong secret"), &Validation::default()).unwrap();
+    decode::<Data>(
+        &token,
+        &DecodingKey::from_secret(b"wrong secret"),
+        &Validation::default(),
+    )
+    .unwrap();
 }
 
 #[test]
@@ -46,6 +51,126 @@ fn verify_token_with_wrong_signature() {
     assert!(AuthToken::from(&valid_token, TEST_SECRET).is_err());
 }
 
+#[test]
+fn verify_token_keeps_jti_and_generation() {
+    let token = AuthToken::new(TEST_USER_ID).with_generation(3);
+    let decoded = AuthToken::from(&token.encrypt(TEST_SECRET), TEST_SECRET).unwrap();
+
+    assert_eq!(32, token.jti().len());
+    assert_eq!(token.jti(), decoded.jti());
+    assert_eq!(3, decoded.generation());
+    assert!((23 * 3600..=24 * 3600).contains(&decoded.expires_in_secs()));
+}
+
+#[test]
+fn every_token_gets_unique_jti() {
+    assert_ne!(
+        AuthToken::new(TEST_USER_ID).jti(),
+        AuthToken::new(TEST_USER_ID).jti()
+    );
+}
+
+#[test]
+fn verify_token_issued_without_jti() {
+    use jsonwebtoken::{encode, EncodingKey, Header};
+
+    let claims = serde_json::json!({ "user_id": TEST_USER_ID, "exp": 9_999_999_999i64 });
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(TEST_SECRET),
+    )
+    .unwrap();
+    let decoded = AuthToken::from(&token, TEST_SECRET).unwrap();
+
+    assert_eq!(TEST_USER_ID, decoded.user_id());
+    assert_eq!("", decoded.jti());
+    assert_eq!(0, decoded.generation());
+}
+
+mod extractor {
+    use super::*;
+    use actix_web::{http::StatusCode, test::TestRequest};
+    use futures::executor::block_on;
+
+    const READ_TOKEN: &str = "read-token";
+    const WRITE_TOKEN: &str = "write-token";
+
+    struct FakeTokens;
+
+    impl PersonalTokens for FakeTokens {
+        fn authenticate(&self, token: &str) -> Pin<Box<dyn Future<Output = Option<TokenGrant>>>> {
+            let scope = match token {
+                READ_TOKEN => Some(Scope::Read),
+                WRITE_TOKEN => Some(Scope::ReadWrite),
+                _ => None,
+            };
+            let grant = scope.map(|scope| TokenGrant {
+                user_id: TEST_USER_ID.into(),
+                scope,
+            });
+
+            Box::pin(futures::future::ready(grant))
+        }
+    }
+
+    fn user_id(request: TestRequest, header: String) -> Result<UserId, StatusCode> {
+        let config = ApiJwtTokenAuthConfig::new(TEST_SECRET).personal_tokens(FakeTokens);
+        let (req, mut payload) = request
+            .app_data(config)
+            .header("Authorization", header)
+            .to_http_parts();
+
+        block_on(UserId::from_request(&req, &mut payload))
+            .map_err(|e| e.as_response_error().status_code())
+    }
+
+    #[test]
+    fn jwt() {
+        let header = format!("JWT {}", make_token(1, TEST_SECRET));
+
+        assert_eq!(
+            Ok(TEST_USER_ID.into()),
+            user_id(TestRequest::post(), header)
+        );
+    }
+
+    #[test]
+    fn personal_token() {
+        let read = || format!("Token {}", READ_TOKEN);
+        let write = || format!("Token {}", WRITE_TOKEN);
+
+        assert_eq!(Ok(TEST_USER_ID.into()), user_id(TestRequest::get(), read()));
+        assert_eq!(
+            Ok(TEST_USER_ID.into()),
+            user_id(TestRequest::get(), write())
+        );
+        assert_eq!(
+            Ok(TEST_USER_ID.into()),
+            user_id(TestRequest::post(), write())
+        );
+        assert_eq!(
+            Err(StatusCode::FORBIDDEN),
+            user_id(TestRequest::post(), read())
+        );
+        assert_eq!(
+            Err(StatusCode::UNAUTHORIZED),
+            user_id(TestRequest::get(), "Token unknown".to_string())
+        );
+    }
+
+    #[test]
+    fn personal_token_is_not_jwt() {
+        let config = ApiJwtTokenAuthConfig::new(TEST_SECRET).personal_tokens(FakeTokens);
+        let (req, mut payload) = TestRequest::get()
+            .app_data(config)
+            .header("Authorization", format!("Token {}", WRITE_TOKEN))
+            .to_http_parts();
+
+        assert!(block_on(AuthToken::from_request(&req, &mut payload)).is_err());
+    }
+}
+
 fn make_token(hours_from_now: i64, secret: &[u8]) -> String {
     AuthToken::new(TEST_USER_ID)
         .expire_in_hours(hours_from_now)
@@ -0,0 +1,57 @@
+//! Rebuilds the order of tags in Redis from the records in Postgres.
+//!
+//! Usage: `rebuild_tags [--user <id>] [--half-life <days>]`. Without `--user`
+//! tags of all users are rebuilt. With `--half-life` a use of a tag counts
+//! less the older the record is, otherwise every use counts the same.
+
+use dotenv::dotenv;
+
+use octo_budget_api::{db::ConnectionPool, redis::tag_scores, redis::Redis};
+
+const USAGE: &str = "Usage: rebuild_tags [--user <id>] [--half-life <days>]";
+
+fn parse_args() -> Result<(Option<i32>, Option<f64>), String> {
+    let mut user_id = None;
+    let mut half_life_days = None;
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        let value = args.next().ok_or_else(|| USAGE.to_string())?;
+
+        match arg.as_str() {
+            "--user" => {
+                user_id = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("Wrong user id: {}", value))?,
+                )
+            }
+            "--half-life" => match value.parse::<f64>() {
+                Ok(days) if days > 0.0 => half_life_days = Some(days),
+                _ => return Err(format!("Wrong half-life: {}", value)),
+            },
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    Ok((user_id, half_life_days))
+}
+
+#[actix_rt::main]
+async fn main() -> Result<(), String> {
+    dotenv().ok();
+
+    let (user_id, half_life_days) = parse_args()?;
+    let pool = ConnectionPool::new();
+    let redis = Redis::connect()
+        .await
+        .map_err(|e| format!("Failed to connect to redis: {}", e))?;
+
+    let users = tag_scores::rebuild(user_id, half_life_days, &pool, &redis)
+        .await
+        .map_err(|e| format!("Failed to rebuild tags: {}", e))?;
+
+    println!("Rebuilt tags of {} users", users);
+
+    Ok(())
+}